@@ -0,0 +1,381 @@
+//! Access checks for arbitrary filesystem paths
+//!
+//! [`PermissionManager::check_path`](crate::PermissionManager::check_path)
+//! answers whether the process may read, write, execute or create a given path.
+//! It first resolves which protected category the path falls under (a user
+//! folder such as Documents, a network or removable volume, or system territory
+//! guarded by full disk access) and then probes each requested mode with the
+//! operation itself, so sandbox and TCC denials show up exactly as a real access
+//! would see them:
+//!
+//! | Mode      | Probe                                                              |
+//! |-----------|--------------------------------------------------------------------|
+//! | `Read`    | list the directory or open the file for reading                    |
+//! | `Write`   | open the file for writing without truncating it; for a directory, create and remove a probe file in it |
+//! | `Execute` | execute permission bits (Unix) or an executable extension (Windows) |
+//! | `Create`  | create and remove a probe file in the nearest existing ancestor     |
+//!
+//! Probes never modify existing files. The probe file of a `Write` check on a
+//! directory and of a `Create` check is visible to others while it exists: it
+//! updates the directory's modification time and wakes inotify, FSEvents or
+//! `ReadDirectoryChangesW` watchers on the directory. Modes other than `Create`
+//! fail with [`ErrorKind::NotFound`](crate::ErrorKind::NotFound) when the path
+//! does not exist.
+
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+#[cfg(target_os = "linux")]
+use crate::platforms::linux::filesystem::user_dir;
+use crate::types::{PermissionError, PermissionStatus, PermissionType};
+
+/// Kind of access to a path
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccessMode {
+    /// Read a file or list a directory
+    Read,
+    /// Modify a file, or add files to a directory
+    Write,
+    /// Run a file, or enter a directory
+    Execute,
+    /// Create the path if it does not exist, or replace it if it does
+    Create,
+}
+
+/// Outcome of probing one [`AccessMode`]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ModeAccess {
+    /// Mode that was probed
+    pub mode: AccessMode,
+    /// `Authorized` or `Denied`, `Restricted` when forbidden by policy, or why
+    /// the probe failed
+    pub status: Result<PermissionStatus, PermissionError>,
+}
+
+/// Result of [`PermissionManager::check_path`](crate::PermissionManager::check_path)
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PathAccess {
+    /// Path that was checked, as given
+    pub path: PathBuf,
+    /// Protected category the path falls under, or `None` for unprotected
+    /// locations such as a project directory
+    pub category: Option<PermissionType>,
+    /// Every requested mode with its status, in request order
+    pub modes: Vec<ModeAccess>,
+}
+
+impl PathAccess {
+    /// Status of `mode`, if it was requested
+    pub fn status(&self, mode: AccessMode) -> Option<&Result<PermissionStatus, PermissionError>> {
+        self.modes
+            .iter()
+            .find(|access| access.mode == mode)
+            .map(|access| &access.status)
+    }
+
+    /// Whether `mode` was requested and is authorized
+    pub fn allows(&self, mode: AccessMode) -> bool {
+        matches!(self.status(mode), Some(Ok(PermissionStatus::Authorized)))
+    }
+}
+
+/// Probe `mode` on `path`
+pub(crate) fn probe(path: &Path, mode: AccessMode) -> Result<PermissionStatus, PermissionError> {
+    let outcome = match mode {
+        AccessMode::Read => probe_read(path),
+        AccessMode::Write => probe_write(path),
+        AccessMode::Execute => probe_execute(path),
+        AccessMode::Create => probe_create(path),
+    };
+    match outcome {
+        Ok(true) => Ok(PermissionStatus::Authorized),
+        Ok(false) => Ok(PermissionStatus::Denied),
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => Ok(PermissionStatus::Denied),
+        Err(e) => Err(PermissionError::from(e).with_probe(path.display().to_string())),
+    }
+}
+
+fn probe_read(path: &Path) -> io::Result<bool> {
+    if fs::metadata(path)?.is_dir() {
+        fs::read_dir(path)?;
+    } else {
+        fs::File::open(path)?;
+    }
+    Ok(true)
+}
+
+fn probe_write(path: &Path) -> io::Result<bool> {
+    if fs::metadata(path)?.is_dir() {
+        probe_file_in(path)
+    } else {
+        OpenOptions::new().write(true).open(path)?;
+        Ok(true)
+    }
+}
+
+#[cfg(unix)]
+fn probe_execute(path: &Path) -> io::Result<bool> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    // Ask the kernel, which applies the owner, group or other bits that match
+    // the effective user, as exec itself does
+    let result =
+        unsafe { libc::faccessat(libc::AT_FDCWD, path.as_ptr(), libc::X_OK, libc::AT_EACCESS) };
+    if result == 0 {
+        return Ok(true);
+    }
+    let error = io::Error::last_os_error();
+    match error.kind() {
+        io::ErrorKind::PermissionDenied => Ok(false),
+        _ => Err(error),
+    }
+}
+
+#[cfg(windows)]
+fn probe_execute(path: &Path) -> io::Result<bool> {
+    if fs::metadata(path)?.is_dir() {
+        return Ok(true);
+    }
+    let extensions = std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".into());
+    let extension = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()).to_uppercase());
+    Ok(extension.is_some_and(|ext| {
+        extensions
+            .split(';')
+            .any(|known| known.to_uppercase() == ext)
+    }))
+}
+
+fn probe_create(path: &Path) -> io::Result<bool> {
+    let path = std::path::absolute(path)?;
+    let mut dir = path.parent().unwrap_or(&path);
+    while !dir.exists() {
+        match dir.parent() {
+            Some(parent) => dir = parent,
+            None => return Err(io::ErrorKind::NotFound.into()),
+        }
+    }
+    probe_file_in(dir)
+}
+
+/// Create and remove an empty file in `dir`
+fn probe_file_in(dir: &Path) -> io::Result<bool> {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    let name = format!(
+        ".kodegen-access-probe-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    let probe = dir.join(name);
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)?;
+    fs::remove_file(&probe)?;
+    Ok(true)
+}
+
+/// Absolute form of `path` with symlinks resolved as far as it exists
+fn resolve(path: &Path) -> PathBuf {
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut existing = absolute.as_path();
+    let mut missing = Vec::new();
+    loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            return missing
+                .iter()
+                .rev()
+                .fold(canonical, |path, part| path.join(part));
+        }
+        match (existing.parent(), existing.components().next_back()) {
+            (Some(parent), Some(Component::Normal(part))) => {
+                missing.push(part.to_owned());
+                existing = parent;
+            },
+            _ => return absolute,
+        }
+    }
+}
+
+/// Protected category `path` falls under, if any
+///
+/// User folders are matched first, then network and removable volumes, then
+/// locations guarded by full disk access.
+pub fn protected_category(path: impl AsRef<Path>) -> Option<PermissionType> {
+    let path = resolve(path.as_ref());
+    let folders = [
+        PermissionType::DesktopFolder,
+        PermissionType::DocumentsFolder,
+        PermissionType::DownloadsFolder,
+        PermissionType::Photos,
+        PermissionType::MediaLibrary,
+    ];
+    let home = dirs::home_dir().map(|home| resolve(&home));
+    for typ in folders {
+        let Some(dir) = user_dir(typ).map(|dir| resolve(&dir)) else {
+            continue;
+        };
+        // Folders unset in user-dirs fall back to the home directory itself
+        if home.as_ref() != Some(&dir) && path.starts_with(&dir) {
+            return Some(typ);
+        }
+    }
+
+    volume_category(&path).or_else(|| full_disk_category(&path, home.as_deref()))
+}
+
+/// Folder behind a folder permission
+#[cfg(not(target_os = "linux"))]
+fn user_dir(typ: PermissionType) -> Option<PathBuf> {
+    match typ {
+        PermissionType::DesktopFolder => dirs::desktop_dir(),
+        PermissionType::DocumentsFolder => dirs::document_dir(),
+        PermissionType::DownloadsFolder => dirs::download_dir(),
+        PermissionType::Photos => dirs::picture_dir(),
+        PermissionType::MediaLibrary => dirs::audio_dir(),
+        _ => None,
+    }
+}
+
+/// File system types of network mounts in `/proc/self/mounts`
+#[cfg(target_os = "linux")]
+const NETWORK_FILESYSTEMS: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "fuse.sshfs",
+    "afs",
+    "ceph",
+    "glusterfs",
+    "9p",
+];
+
+#[cfg(target_os = "linux")]
+fn volume_category(path: &Path) -> Option<PermissionType> {
+    let mounts = fs::read_to_string("/proc/self/mounts").ok()?;
+    // The deepest mount point containing the path decides
+    let (mount_point, fs_type) = mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(' ');
+            let _device = fields.next()?;
+            // Mount points escape spaces as \040
+            let mount_point = PathBuf::from(fields.next()?.replace("\\040", " "));
+            let fs_type = fields.next()?;
+            path.starts_with(&mount_point)
+                .then_some((mount_point, fs_type))
+        })
+        .max_by_key(|(mount_point, _)| mount_point.components().count())?;
+
+    if NETWORK_FILESYSTEMS.contains(&fs_type) {
+        Some(PermissionType::NetworkVolumes)
+    } else if mount_point.starts_with("/media") || mount_point.starts_with("/run/media") {
+        Some(PermissionType::RemovableVolumes)
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn volume_category(path: &Path) -> Option<PermissionType> {
+    if path.starts_with("/Network") {
+        Some(PermissionType::NetworkVolumes)
+    } else if path.starts_with("/Volumes") && path.components().count() > 2 {
+        Some(PermissionType::RemovableVolumes)
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn volume_category(path: &Path) -> Option<PermissionType> {
+    use std::path::Prefix;
+
+    match path.components().next() {
+        Some(Component::Prefix(prefix))
+            if matches!(prefix.kind(), Prefix::UNC(..) | Prefix::VerbatimUNC(..)) =>
+        {
+            Some(PermissionType::NetworkVolumes)
+        },
+        _ => None,
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn volume_category(_path: &Path) -> Option<PermissionType> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn full_disk_category(path: &Path, home: Option<&Path>) -> Option<PermissionType> {
+    // Locations TCC keeps from apps without full disk access
+    const USER: &[&str] = &[
+        "Library/Mail",
+        "Library/Messages",
+        "Library/Safari",
+        "Library/Cookies",
+        "Library/HomeKit",
+        "Library/Suggestions",
+        "Library/Metadata/CoreSpotlight",
+        "Library/Application Support/com.apple.TCC",
+        "Library/Containers/com.apple.mail",
+    ];
+    const SYSTEM: &[&str] = &[
+        "/Library/Application Support/com.apple.TCC",
+        "/private/var/db",
+    ];
+
+    let in_home = home.is_some_and(|home| USER.iter().any(|dir| path.starts_with(home.join(dir))));
+    (in_home || SYSTEM.iter().any(|dir| path.starts_with(dir)))
+        .then_some(PermissionType::FullDiskAccess)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn full_disk_category(path: &Path, home: Option<&Path>) -> Option<PermissionType> {
+    // The user's own home is never protected, even where it lives under a
+    // system directory (`/root` for root, `/var/home` on Fedora Silverblue)
+    if home.is_some_and(|home| path.starts_with(home)) {
+        return None;
+    }
+
+    #[cfg(windows)]
+    let system: Vec<PathBuf> = [
+        "SystemRoot",
+        "ProgramFiles",
+        "ProgramFiles(x86)",
+        "ProgramData",
+    ]
+    .iter()
+    .filter_map(|var| std::env::var_os(var).map(PathBuf::from))
+    .map(|dir| resolve(&dir))
+    .collect();
+    // Only where a denial means "needs root"; a locked path under `/var` or
+    // `/opt` is an ordinary POSIX denial that no permission grant changes
+    #[cfg(not(windows))]
+    let system: Vec<PathBuf> = ["/etc", "/root", "/boot"]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+
+    // Other users' home directories need elevated access too; a home directory
+    // directly under the root (like `/root`) has no siblings to protect
+    let other_home = home.is_some_and(|home| {
+        home.parent().is_some_and(|homes| {
+            homes.parent().is_some()
+                && path.starts_with(homes)
+                && path != homes
+        })
+    });
+    (other_home || system.iter().any(|dir| path.starts_with(dir)))
+        .then_some(PermissionType::FullDiskAccess)
+}
@@ -0,0 +1,434 @@
+//! Tamper-evident audit log of permission checks and requests, behind the
+//! `audit` feature
+//!
+//! An [`AuditLog`] attached with
+//! [`PermissionManager::with_audit`](crate::PermissionManager::with_audit)
+//! appends one JSON object per line for every check and request:
+//!
+//! ```text
+//! {"seq":0,"time":"2026-01-05T09:30:12.042Z","operation":"request","permission":"camera","tool":"snapshot","agent":"coder","outcome":"authorized","prompted":true,"backend":"linux","prev":"0000…","hash":"9f2c…"}
+//! ```
+//!
+//! `prompted` is only true for the request that put a dialog on screen. Callers
+//! sharing another caller's prompt, requests given up on while waiting for their
+//! turn and requests the backend answers from a decision on record log `false`.
+//!
+//! [`check_path`](crate::PermissionManager::check_path) records one entry per
+//! probed mode, with the mode (`read`, `write`, `execute` or `create`) as
+//! `operation` and the protected category as `permission`. Paths outside every
+//! category are not recorded.
+//!
+//! `hash` is the SHA-256 of the line up to (not including) `,"hash"`, which
+//! contains `prev`, the hash of the line before; the first line's `prev` is all
+//! zeros. Editing, removing or reordering a line without rewriting the lines
+//! after it breaks the chain, which [`verify`] reports.
+//!
+//! **[`verify`] alone proves neither that the log is unedited nor that it is
+//! complete.** The hash is not keyed, so anyone who can write the file can
+//! recompute every hash after the line they changed, and dropping lines from the
+//! end leaves a valid shorter chain. Both are only detected against a
+//! [`head`](AuditLog::head) hash kept somewhere the log's writers cannot change,
+//! checked with [`verify_head`].
+//!
+//! Appends take an advisory lock on a `.lock` file beside the log and continue
+//! from the last entry in the file, so several handles and processes can share
+//! one log. Async callers write on Tokio's blocking pool.
+
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use sha2::{Digest, Sha256};
+
+use crate::access::AccessMode;
+use crate::policy::Caller;
+use crate::types::{PermissionError, PermissionStatus, PermissionType};
+
+/// `prev` of the first entry
+const GENESIS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Separator between the hashed part of a line and its hash
+const HASH_FIELD: &str = ",\"hash\":\"";
+
+/// Separator before the previous entry's hash
+const PREV_FIELD: &str = ",\"prev\":\"";
+
+/// Kind of operation recorded in an entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Operation {
+    Check,
+    Request,
+    Access(AccessMode),
+}
+
+/// What an entry records, besides its place in the chain
+pub(crate) struct Entry {
+    pub(crate) operation: Operation,
+    pub(crate) permission: PermissionType,
+    pub(crate) caller: Caller,
+    pub(crate) outcome: Result<PermissionStatus, PermissionError>,
+    pub(crate) prompted: bool,
+    pub(crate) backend: &'static str,
+}
+
+struct Chain {
+    file: File,
+    // Length of the file after the last entry this handle read or wrote
+    len: u64,
+    next_seq: u64,
+    head: String,
+}
+
+impl Chain {
+    /// Continue from the last entry in the file, which other handles may have
+    /// appended since this one last wrote
+    fn sync(&mut self) -> io::Result<()> {
+        let len = self.file.metadata()?.len();
+        if len == self.len {
+            return Ok(());
+        }
+        let (next_seq, head) = if len == 0 {
+            (0, GENESIS.to_string())
+        } else {
+            read_tail(&mut self.file, len)?
+        };
+        self.len = len;
+        self.next_seq = next_seq;
+        self.head = head;
+        Ok(())
+    }
+}
+
+/// Append-only, hash-chained JSONL log of permission operations
+pub struct AuditLog {
+    path: PathBuf,
+    chain: Mutex<Chain>,
+}
+
+impl AuditLog {
+    /// Open or create the log at `path`, continuing its chain
+    ///
+    /// An existing file is verified first; if it fails verification it is left
+    /// untouched and an error is returned rather than extending a broken chain.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PermissionError> {
+        let path = path.as_ref().to_path_buf();
+        let probe = || path.display().to_string();
+
+        // Keep writers out while the existing chain is read
+        let _lock = lock(&path).map_err(|e| PermissionError::from(e).with_probe(probe()))?;
+        let summary = if path.exists() {
+            verify(&path).map_err(|violation| {
+                PermissionError::system(format!("audit log failed verification: {}", violation))
+                    .with_probe(probe())
+            })?
+        } else {
+            AuditSummary {
+                entries: 0,
+                head: GENESIS.to_string(),
+            }
+        };
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| PermissionError::from(e).with_probe(probe()))?;
+        let len = file
+            .metadata()
+            .map_err(|e| PermissionError::from(e).with_probe(probe()))?
+            .len();
+
+        Ok(Self {
+            path,
+            chain: Mutex::new(Chain {
+                file,
+                len,
+                next_seq: summary.entries,
+                head: summary.head,
+            }),
+        })
+    }
+
+    /// Path of the log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Hash of the last entry this handle wrote or read, or all zeros for an
+    /// empty log
+    pub fn head(&self) -> String {
+        self.chain
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .head
+            .clone()
+    }
+
+    /// Append an entry from async code, without blocking the runtime
+    ///
+    /// The write runs on the blocking pool when called within a Tokio runtime,
+    /// and on the calling thread otherwise.
+    pub(crate) async fn record_async(self: Arc<Self>, entry: Entry) -> Result<(), PermissionError> {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return self.record(&entry);
+        };
+        match runtime.spawn_blocking(move || self.record(&entry)).await {
+            Ok(written) => written,
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(e) => Err(PermissionError::system(format!("audit entry was not written: {}", e))),
+        }
+    }
+
+    /// Append an entry, failing if it could not be written in full
+    pub(crate) fn record(&self, entry: &Entry) -> Result<(), PermissionError> {
+        let error =
+            |e: io::Error| PermissionError::from(e).with_probe(self.path.display().to_string());
+        // A panic while holding the mutex leaves nothing half-done that `sync`
+        // does not recover from
+        let mut chain = self.chain.lock().unwrap_or_else(|e| e.into_inner());
+        let _lock = lock(&self.path).map_err(error)?;
+        chain.sync().map_err(error)?;
+
+        let body = format!(
+            "{{\"seq\":{},\"time\":\"{}\",\"operation\":\"{}\",\"permission\":\"{}\",\"tool\":{},\"agent\":{},{},\"prompted\":{},\"backend\":{}{}{}\"",
+            chain.next_seq,
+            humantime::format_rfc3339_millis(SystemTime::now()),
+            match entry.operation {
+                Operation::Check => "check",
+                Operation::Request => "request",
+                Operation::Access(AccessMode::Read) => "read",
+                Operation::Access(AccessMode::Write) => "write",
+                Operation::Access(AccessMode::Execute) => "execute",
+                Operation::Access(AccessMode::Create) => "create",
+            },
+            entry.permission.as_str(),
+            json_string(entry.caller.tool.as_deref()),
+            json_string(entry.caller.agent.as_deref()),
+            outcome(&entry.outcome),
+            entry.prompted,
+            json_string(Some(entry.backend)),
+            PREV_FIELD,
+            chain.head,
+        );
+        let hash = hex_digest(&body);
+        let line = format!("{}{}{}\"}}\n", body, HASH_FIELD, hash);
+
+        chain.file.write_all(line.as_bytes()).map_err(error)?;
+        chain.len += line.len() as u64;
+        chain.next_seq += 1;
+        chain.head = hash;
+        Ok(())
+    }
+}
+
+/// Take the exclusive lock on `<path>.lock`, released when the file is closed
+fn lock(path: &Path) -> io::Result<File> {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".lock");
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(name)?;
+    file.lock()?;
+    Ok(file)
+}
+
+/// Sequence number following the last entry of `file`, and that entry's hash
+///
+/// Reads backwards from the end of the file until the whole last line is in.
+fn read_tail(file: &mut File, len: u64) -> io::Result<(u64, String)> {
+    let malformed = || io::Error::new(io::ErrorKind::InvalidData, "last audit entry is malformed");
+    let mut window = 4096;
+    loop {
+        let start = len.saturating_sub(window);
+        let mut buf = vec![0; (len - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut buf)?;
+
+        // A window starting mid-character only garbles lines before the last
+        let text = String::from_utf8_lossy(&buf);
+        let text = text.strip_suffix('\n').ok_or_else(malformed)?;
+        let line = match text.rfind('\n') {
+            Some(i) => &text[i + 1..],
+            None if start == 0 => text,
+            None => {
+                window *= 4;
+                continue;
+            },
+        };
+        let entry = parse_entry(line).ok_or_else(malformed)?;
+        if hex_digest(entry.body) != entry.hash {
+            return Err(malformed());
+        }
+        return Ok((entry.seq + 1, entry.hash.to_string()));
+    }
+}
+
+impl fmt::Debug for AuditLog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AuditLog")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+/// Result of verifying an intact log
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditSummary {
+    /// Number of entries
+    pub entries: u64,
+    /// Hash of the last entry, or all zeros for an empty log
+    pub head: String,
+}
+
+/// Why a log failed verification
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuditViolation {
+    /// The file could not be read
+    Unreadable(String),
+    /// A line is not an entry, e.g. cut off by truncation mid-line
+    Malformed {
+        /// Line number, from 1
+        line: u64,
+    },
+    /// A line was edited after it was written
+    Edited {
+        /// Line number, from 1
+        line: u64,
+    },
+    /// A line does not follow the one before it: lines were removed, inserted
+    /// or reordered
+    OutOfSequence {
+        /// Line number, from 1
+        line: u64,
+    },
+    /// The expected head hash is not in the chain: entries were removed from
+    /// the end, or the chain was rewritten up to it
+    Truncated {
+        /// Entries remaining in the log
+        entries: u64,
+    },
+}
+
+impl fmt::Display for AuditViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Unreadable(e) => write!(f, "log could not be read: {}", e),
+            Self::Malformed { line } => write!(f, "line {} is malformed", line),
+            Self::Edited { line } => write!(f, "line {} was edited", line),
+            Self::OutOfSequence { line } => write!(f, "line {} is out of sequence", line),
+            Self::Truncated { entries } => {
+                write!(f, "log was truncated after {} entries", entries)
+            },
+        }
+    }
+}
+
+impl std::error::Error for AuditViolation {}
+
+/// Check every entry of the log at `path` against the hash chain
+pub fn verify(path: impl AsRef<Path>) -> Result<AuditSummary, AuditViolation> {
+    verify_chain(path.as_ref(), |_| {})
+}
+
+/// Verify the log and check that `head` is one of its entries
+///
+/// Pass the [`head`](AuditLog::head) recorded at the end of a session; the log
+/// may have grown since, but it must still contain that entry.
+pub fn verify_head(path: impl AsRef<Path>, head: &str) -> Result<AuditSummary, AuditViolation> {
+    let mut found = head == GENESIS;
+    let summary = verify_chain(path.as_ref(), |hash| found |= hash == head)?;
+    if found {
+        Ok(summary)
+    } else {
+        Err(AuditViolation::Truncated {
+            entries: summary.entries,
+        })
+    }
+}
+
+fn verify_chain(path: &Path, mut visit: impl FnMut(&str)) -> Result<AuditSummary, AuditViolation> {
+    let contents =
+        std::fs::read_to_string(path).map_err(|e| AuditViolation::Unreadable(e.to_string()))?;
+    let mut head = GENESIS.to_string();
+    let mut entries = 0u64;
+
+    let mut rest = contents.as_str();
+    while !rest.is_empty() {
+        let line_number = entries + 1;
+        // Every entry ends in a newline; a missing one means a cut-off write
+        let Some((line, tail)) = rest.split_once('\n') else {
+            return Err(AuditViolation::Malformed { line: line_number });
+        };
+        rest = tail;
+
+        let entry = parse_entry(line).ok_or(AuditViolation::Malformed { line: line_number })?;
+        if hex_digest(entry.body) != entry.hash {
+            return Err(AuditViolation::Edited { line: line_number });
+        }
+        if entry.seq != entries || entry.prev != head {
+            return Err(AuditViolation::OutOfSequence { line: line_number });
+        }
+
+        visit(entry.hash);
+        head = entry.hash.to_string();
+        entries += 1;
+    }
+
+    Ok(AuditSummary { entries, head })
+}
+
+/// Chain fields of one log line
+struct ParsedEntry<'a> {
+    seq: u64,
+    prev: &'a str,
+    // Hashed part of the line
+    body: &'a str,
+    hash: &'a str,
+}
+
+/// Split a line (without its newline) into its chain fields
+fn parse_entry(line: &str) -> Option<ParsedEntry<'_>> {
+    let (body, hash) = line
+        .strip_suffix("\"}")
+        .and_then(|line| line.rsplit_once(HASH_FIELD))?;
+    let (_, prev) = body.rsplit_once(PREV_FIELD)?;
+    let prev = prev.strip_suffix('"')?;
+    let seq = body
+        .strip_prefix("{\"seq\":")
+        .and_then(|s| s.split(',').next())
+        .and_then(|s| s.parse().ok())?;
+    Some(ParsedEntry {
+        seq,
+        prev,
+        body,
+        hash,
+    })
+}
+
+/// `"outcome":…` fields of an entry
+fn outcome(outcome: &Result<PermissionStatus, PermissionError>) -> String {
+    match outcome {
+        Ok(status) => format!("\"outcome\":\"{}\"", status.as_str()),
+        Err(e) => format!(
+            "\"outcome\":\"error\",\"error\":\"{}\",\"message\":{}",
+            e.kind().as_str(),
+            json_string(Some(&e.to_string()))
+        ),
+    }
+}
+
+/// JSON string literal, or `null`
+fn json_string(value: Option<&str>) -> String {
+    // Serializing a string cannot fail
+    serde_json::to_string(&value).unwrap_or_default()
+}
+
+/// Lowercase hex SHA-256 of a hashed line body
+fn hex_digest(body: &str) -> String {
+    format!("{:x}", Sha256::digest(body.as_bytes()))
+}
@@ -0,0 +1,139 @@
+//! Checking and requesting many permissions at once
+//!
+//! [`PermissionManager::check_many`](crate::PermissionManager::check_many) and
+//! [`PermissionManager::request_many`](crate::PermissionManager::request_many)
+//! return one [`BatchResult`] per input, in input order, even for duplicate types.
+//! Their `_stream` variants yield each result as soon as it is known, so a UI can
+//! show progress while prompts resolve; [`BatchResult::index`] says where it
+//! belongs.
+//!
+//! Every permission runs in its own task. A task that panics yields an
+//! [`ErrorKind::Unknown`] error for its permission instead of being dropped.
+//! [`BatchOptions::concurrency`] bounds how many tasks run at a time; tasks start
+//! in input order, so prompts still appear in that order. Dropping a stream
+//! aborts the tasks it started, which dismisses their prompts as dropping a
+//! single request does.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::stream::{BoxStream, Stream, StreamExt};
+use tokio::task::JoinHandle;
+
+use crate::request::RequestOptions;
+use crate::types::{ErrorKind, PermissionError, PermissionStatus, PermissionType};
+
+/// Options for batch checks and requests
+#[derive(Clone, Debug, Default)]
+pub struct BatchOptions {
+    /// Most permissions handled at a time; unbounded when `None`
+    pub concurrency: Option<usize>,
+    /// Options applied to every request of a batch request
+    pub request: RequestOptions,
+}
+
+impl BatchOptions {
+    /// Options with unbounded concurrency and default request options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle at most `limit` permissions at a time (at least one)
+    pub fn with_concurrency(mut self, limit: usize) -> Self {
+        self.concurrency = Some(limit.max(1));
+        self
+    }
+
+    /// Apply `options` to every request
+    pub fn with_request_options(mut self, options: RequestOptions) -> Self {
+        self.request = options;
+        self
+    }
+}
+
+/// Outcome for one permission of a batch
+#[derive(Clone, Debug)]
+pub struct BatchResult {
+    /// Position of the permission in the input
+    pub index: usize,
+    /// Permission that was checked or requested
+    pub permission: PermissionType,
+    /// Its status, or why it could not be determined
+    pub result: Result<PermissionStatus, PermissionError>,
+}
+
+/// Stream of [`BatchResult`]s in completion order
+///
+/// Dropping the stream aborts the permissions still in progress.
+pub struct BatchStream {
+    inner: BoxStream<'static, BatchResult>,
+}
+
+impl Stream for BatchStream {
+    type Item = BatchResult;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+impl BatchStream {
+    /// Every result, in input order
+    pub(crate) async fn collect_ordered(self) -> Vec<BatchResult> {
+        let mut results: Vec<BatchResult> = self.collect().await;
+        results.sort_by_key(|result| result.index);
+        results
+    }
+}
+
+/// Run `start(typ)` for every type in its own task, at most `concurrency` at a
+/// time
+pub(crate) fn spawn<F>(
+    types: &[PermissionType],
+    concurrency: Option<usize>,
+    start: impl Fn(PermissionType) -> F + Send + 'static,
+) -> BatchStream
+where
+    F: Future<Output = Result<PermissionStatus, PermissionError>> + Send + 'static,
+{
+    let limit = concurrency.unwrap_or(types.len()).max(1);
+    let types = types.to_vec();
+    let inner = futures::stream::iter(types.into_iter().enumerate())
+        .map(move |(index, permission)| {
+            // Spawned only once a slot is free, so tasks start in input order
+            let task = Task(tokio::spawn(start(permission)));
+            async move {
+                let result = task.await.unwrap_or_else(|e| {
+                    Err(PermissionError::new(ErrorKind::Unknown)
+                        .with_message(format!("task for {} failed", permission))
+                        .with_source(e))
+                });
+                BatchResult {
+                    index,
+                    permission,
+                    result,
+                }
+            }
+        })
+        .buffer_unordered(limit)
+        .boxed();
+    BatchStream { inner }
+}
+
+/// Task handle that aborts the task when dropped
+struct Task<T>(JoinHandle<T>);
+
+impl<T> Future for Task<T> {
+    type Output = Result<T, tokio::task::JoinError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+impl<T> Drop for Task<T> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
@@ -0,0 +1,181 @@
+//! Blocking API for synchronous programs
+//!
+//! [`PermissionManager`] wraps the async [`crate::PermissionManager`] and runs
+//! requests on the crate's private runtime while the calling thread waits, so it
+//! can be called from any thread: one with no Tokio runtime at all, or one
+//! inside a runtime of either flavor. The calling thread is parked while a
+//! request's prompt is open.
+//!
+//! ```rust,no_run
+//! use kodegen_native_permissions::PermissionType;
+//! use kodegen_native_permissions::blocking::PermissionManager;
+//!
+//! fn main() {
+//!     let manager = PermissionManager::new();
+//!     match manager.request_permission(PermissionType::Camera) {
+//!         Ok(status) => println!("Camera: {}", status),
+//!         Err(e) => eprintln!("Camera request failed: {}", e),
+//!     }
+//! }
+//! ```
+//!
+//! A blocking manager obtained with [`crate::PermissionManager::blocking`]
+//! shares the backend, cache, prompt queue, policy and audit log of the async
+//! one, so sync and async callers in the same process see the same statuses.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::access::{AccessMode, PathAccess};
+use crate::batch::{BatchOptions, BatchResult};
+use crate::remediation::Remediation;
+use crate::report::PermissionReport;
+use crate::request::RequestOptions;
+use crate::runtime;
+use crate::traits::PermissionHandler;
+use crate::types::{PermissionError, PermissionStatus, PermissionType, Support};
+
+/// Synchronous facade over [`crate::PermissionManager`]
+#[derive(Clone, Default)]
+pub struct PermissionManager {
+    inner: crate::PermissionManager,
+}
+
+impl PermissionManager {
+    /// Create a manager using the platform backend
+    ///
+    /// Loads administrative policy like [`crate::PermissionManager::new`].
+    pub fn new() -> Self {
+        crate::PermissionManager::new().blocking()
+    }
+
+    /// Create a manager that dispatches to a custom backend
+    pub fn with_backend(backend: Arc<dyn PermissionHandler>) -> Self {
+        crate::PermissionManager::with_backend(backend).blocking()
+    }
+
+    /// The async manager this facade shares its state with
+    pub fn as_async(&self) -> &crate::PermissionManager {
+        &self.inner
+    }
+
+    /// How the backend supports `typ`
+    pub fn support(&self, typ: PermissionType) -> Support {
+        self.inner.support(typ)
+    }
+
+    /// How the user can grant `typ` when it is denied or restricted
+    ///
+    /// See [`crate::PermissionManager::remediation`].
+    pub fn remediation(&self, typ: PermissionType) -> Remediation {
+        self.inner.remediation(typ)
+    }
+
+    /// Open the system settings where the user grants `typ`
+    ///
+    /// See [`crate::PermissionManager::open_settings`].
+    pub fn open_settings(&self, typ: PermissionType) -> Result<(), PermissionError> {
+        self.inner.open_settings(typ)
+    }
+
+    /// Check permission status without prompting (uses cache if available)
+    ///
+    /// Runs the backend's synchronous check on the calling thread, which the
+    /// built-in backends support with or without a runtime.
+    pub fn check_permission(
+        &self,
+        typ: PermissionType,
+    ) -> Result<PermissionStatus, PermissionError> {
+        self.inner.check_permission(typ)
+    }
+
+    /// Check permission status with the evidence behind it
+    ///
+    /// See [`crate::PermissionManager::check_permission_detailed`].
+    pub fn check_permission_detailed(
+        &self,
+        typ: PermissionType,
+    ) -> Result<PermissionReport, PermissionError> {
+        let manager = self.inner.clone();
+        runtime::block_on(async move { manager.check_permission_detailed(typ).await })
+    }
+
+    /// Request permission, waiting until the user answers
+    pub fn request_permission(
+        &self,
+        typ: PermissionType,
+    ) -> Result<PermissionStatus, PermissionError> {
+        self.request_permission_with(typ, RequestOptions::default())
+    }
+
+    /// Request permission with a timeout, cancellation token or presentation
+    /// options
+    ///
+    /// See [`crate::PermissionManager::request_permission_with`].
+    pub fn request_permission_with(
+        &self,
+        typ: PermissionType,
+        options: RequestOptions,
+    ) -> Result<PermissionStatus, PermissionError> {
+        let manager = self.inner.clone();
+        runtime::block_on(async move { manager.request_permission_with(typ, options).await })
+    }
+
+    /// Request several permissions, prompting for them one at a time
+    pub fn request_permissions(
+        &self,
+        types: &[PermissionType],
+    ) -> HashMap<PermissionType, Result<PermissionStatus, PermissionError>> {
+        let manager = self.inner.clone();
+        let types = types.to_vec();
+        runtime::block_on(async move { manager.request_permissions(&types).await })
+    }
+
+    /// Check several permissions, returning one result per type in input order
+    ///
+    /// See [`crate::PermissionManager::check_many`].
+    pub fn check_many(&self, types: &[PermissionType], options: &BatchOptions) -> Vec<BatchResult> {
+        let manager = self.inner.clone();
+        let types = types.to_vec();
+        let options = options.clone();
+        runtime::block_on(async move { manager.check_many(&types, &options).await })
+    }
+
+    /// Request several permissions, returning one result per type in input order
+    ///
+    /// See [`crate::PermissionManager::request_many`].
+    pub fn request_many(
+        &self,
+        types: &[PermissionType],
+        options: &BatchOptions,
+    ) -> Vec<BatchResult> {
+        let manager = self.inner.clone();
+        let types = types.to_vec();
+        let options = options.clone();
+        runtime::block_on(async move { manager.request_many(&types, &options).await })
+    }
+
+    /// Check which `modes` of access the process has to `path`
+    ///
+    /// See [`crate::PermissionManager::check_path`].
+    pub fn check_path(&self, path: impl AsRef<Path>, modes: &[AccessMode]) -> PathAccess {
+        self.inner.check_path(path, modes)
+    }
+
+    /// Drop the cached status for a specific permission
+    pub fn invalidate(&self, typ: PermissionType) {
+        self.inner.invalidate(typ);
+    }
+
+    /// Clear all cached permission statuses
+    pub fn clear_cache(&self) {
+        self.inner.clear_cache();
+    }
+}
+
+impl From<crate::PermissionManager> for PermissionManager {
+    fn from(inner: crate::PermissionManager) -> Self {
+        Self { inner }
+    }
+}
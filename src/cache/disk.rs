@@ -0,0 +1,397 @@
+//! Status cache persisted across process restarts
+//!
+//! A [`DiskCache`] lets short-lived processes, such as one CLI invocation per
+//! tool call, reuse statuses probed by an earlier process instead of asking
+//! D-Bus and the portal again. Entries are stored in one file per application
+//! under the user cache directory (`$XDG_CACHE_HOME/kodegen/permissions` on
+//! Linux), headed by the [`SessionIdentity`] that wrote them: when the boot,
+//! user or login session changes, the whole file is discarded. Reads and writes
+//! take an advisory lock on a `.lock` file beside it, and files are replaced
+//! atomically, so concurrent processes never see a partial write.
+//!
+//! Writes made from inside a Tokio runtime are queued and applied in order on
+//! the blocking thread pool, several at a time, so checks never wait for the
+//! disk; writes made without a runtime land before the call returns. Dropping
+//! the cache waits for queued writes.
+//!
+//! Persistence is best effort: an unreadable or unwritable cache behaves like
+//! an empty one.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::types::{PermissionStatus, PermissionType};
+
+/// First line of every cache file, bumped when the format changes
+const FORMAT: &str = "kodegen-permission-cache 1";
+
+/// Environment variables naming the login session, most specific first
+const SESSION_VARS: [&str; 4] = [
+    "XDG_SESSION_ID",
+    "SECURITYSESSIONID",
+    "SESSIONNAME",
+    "DBUS_SESSION_BUS_ADDRESS",
+];
+
+/// Boot, user and login session that cached statuses are valid for
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SessionIdentity {
+    /// Identifier of the current boot (`/proc/sys/kernel/random/boot_id` on Linux)
+    pub boot_id: Option<String>,
+    /// User the process runs as
+    pub user: Option<String>,
+    /// Login session the process belongs to
+    pub session: Option<String>,
+}
+
+impl SessionIdentity {
+    /// Identity of the running process
+    ///
+    /// The boot id is only known on Linux; elsewhere a reboot that keeps the
+    /// same session name is caught by the TTLs alone.
+    pub fn current() -> Self {
+        Self {
+            boot_id: boot_id(),
+            user: user(),
+            session: SESSION_VARS
+                .iter()
+                .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty())),
+        }
+    }
+
+    /// Header lines identifying the session in a cache file
+    fn header(&self) -> String {
+        let field = |value: &Option<String>| {
+            // Keep every value on its own line whatever it contains
+            value
+                .as_deref()
+                .map_or_else(|| "-".to_string(), |v| v.replace(['\n', '\r'], " "))
+        };
+        format!(
+            "{}\nboot_id {}\nuser {}\nsession {}\n",
+            FORMAT,
+            field(&self.boot_id),
+            field(&self.user),
+            field(&self.session)
+        )
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn boot_id() -> Option<String> {
+    let id = fs::read_to_string("/proc/sys/kernel/random/boot_id").ok()?;
+    Some(id.trim().to_string()).filter(|id| !id.is_empty())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn boot_id() -> Option<String> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn user() -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+
+    fs::metadata("/proc/self")
+        .ok()
+        .map(|meta| meta.uid().to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn user() -> Option<String> {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok()
+}
+
+/// Persistent backing store for a [`PermissionCache`](super::PermissionCache)
+///
+/// Attach one with [`CachePolicy::with_disk`](super::CachePolicy::with_disk).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiskCache {
+    path: PathBuf,
+    identity: SessionIdentity,
+}
+
+impl DiskCache {
+    /// Cache file for `app_id` in the user cache directory, valid for the
+    /// current session
+    ///
+    /// Returns `None` when the platform has no cache directory.
+    pub fn new(app_id: &str) -> Option<Self> {
+        let name: String = app_id
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect();
+        let name = if name.is_empty() { "default" } else { &name };
+        let dir = dirs::cache_dir()?.join("kodegen").join("permissions");
+        Some(Self::at(
+            dir.join(format!("{}.cache", name)),
+            SessionIdentity::current(),
+        ))
+    }
+
+    /// Cache file at `path`, valid for `identity`
+    pub fn at(path: impl Into<PathBuf>, identity: SessionIdentity) -> Self {
+        Self {
+            path: path.into(),
+            identity,
+        }
+    }
+
+    /// Path of the cache file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Session the cached statuses are valid for
+    pub fn identity(&self) -> &SessionIdentity {
+        &self.identity
+    }
+
+    /// Every stored status with its age, unless the file belongs to another
+    /// session
+    pub(crate) fn load(&self) -> Vec<(PermissionType, PermissionStatus, Duration)> {
+        let Some(_lock) = self.lock(false) else {
+            return Vec::new();
+        };
+        self.read()
+            .into_iter()
+            .filter_map(|(typ, status, stored_at)| {
+                let age = SystemTime::now().duration_since(stored_at).ok()?;
+                Some((typ, status, age))
+            })
+            .collect()
+    }
+
+    /// Apply `changes` in order with a single rewrite
+    fn apply(&self, changes: &[Change]) {
+        self.update(|entries| {
+            for change in changes {
+                match *change {
+                    Change::Store(typ, status, stored_at) => {
+                        entries.retain(|(cached, ..)| *cached != typ);
+                        entries.extend(status.map(|status| (typ, status, stored_at)));
+                    },
+                    Change::Clear => entries.clear(),
+                }
+            }
+        });
+    }
+
+    /// Rewrite the file under an exclusive lock
+    fn update(
+        &self,
+        change: impl FnOnce(&mut Vec<(PermissionType, PermissionStatus, SystemTime)>),
+    ) {
+        let Some(_lock) = self.lock(true) else {
+            return;
+        };
+        let mut entries = self.read();
+        change(&mut entries);
+
+        let mut contents = self.identity.header();
+        for (typ, status, stored_at) in entries {
+            let millis = stored_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis();
+            contents.push_str(&format!(
+                "{} {} {}\n",
+                typ.as_str(),
+                status.as_str(),
+                millis
+            ));
+        }
+
+        // Write a sibling file and rename it over the cache so readers never
+        // see a partial write
+        let staging = self.sibling("tmp");
+        let written = File::create(&staging)
+            .and_then(|mut file| file.write_all(contents.as_bytes()))
+            .and_then(|()| fs::rename(&staging, &self.path));
+        if written.is_err() {
+            let _ = fs::remove_file(&staging);
+        }
+    }
+
+    /// Entries of the file, or none if it is missing, malformed or written by
+    /// another session
+    fn read(&self) -> Vec<(PermissionType, PermissionStatus, SystemTime)> {
+        let Ok(contents) = fs::read_to_string(&self.path) else {
+            return Vec::new();
+        };
+        let Some(body) = contents.strip_prefix(&self.identity.header()) else {
+            return Vec::new();
+        };
+        body.lines()
+            .filter_map(|line| {
+                let mut fields = line.split(' ');
+                let typ = fields.next()?.parse().ok()?;
+                let status = fields.next()?.parse().ok()?;
+                let millis = fields.next()?.parse().ok()?;
+                Some((typ, status, UNIX_EPOCH + Duration::from_millis(millis)))
+            })
+            .collect()
+    }
+
+    /// Take the advisory lock beside the cache file, creating its directory
+    fn lock(&self, exclusive: bool) -> Option<File> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).ok()?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.sibling("lock"))
+            .ok()?;
+        let locked = if exclusive {
+            file.lock()
+        } else {
+            file.lock_shared()
+        };
+        // The lock is released when the file is closed
+        locked.ok().map(|()| file)
+    }
+
+    /// `<cache file>.<extension>`
+    fn sibling(&self, extension: &str) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(".");
+        name.push(extension);
+        PathBuf::from(name)
+    }
+}
+
+/// A change to the cache file, waiting to be written
+#[derive(Clone, Copy, Debug)]
+enum Change {
+    /// Store a status, or remove the type when it is `None`
+    Store(PermissionType, Option<PermissionStatus>, SystemTime),
+    /// Remove every status
+    Clear,
+}
+
+/// Queues changes to a [`DiskCache`] and writes them in order
+#[derive(Debug)]
+pub(crate) struct DiskWriter {
+    disk: DiskCache,
+    queue: Arc<Queue>,
+}
+
+#[derive(Debug, Default)]
+struct Queue {
+    state: Mutex<QueueState>,
+    // Signalled whenever a flush finishes
+    idle: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    changes: Vec<Change>,
+    flushing: bool,
+}
+
+impl DiskWriter {
+    pub(crate) fn new(disk: DiskCache) -> Self {
+        Self {
+            disk,
+            queue: Arc::default(),
+        }
+    }
+
+    /// Store `status` for `typ`, or remove `typ` when `status` is `None`
+    pub(crate) fn store(&self, typ: PermissionType, status: Option<PermissionStatus>) {
+        self.push(Change::Store(typ, status, SystemTime::now()));
+    }
+
+    /// Remove every stored status
+    pub(crate) fn clear(&self) {
+        self.push(Change::Clear);
+    }
+
+    fn push(&self, change: Change) {
+        let mut state = lock(&self.queue.state);
+        state.changes.push(change);
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            drop(state);
+            self.flush();
+            return;
+        };
+        // A running flush picks the change up before it finishes
+        if !state.flushing {
+            state.flushing = true;
+            let flush = Flush {
+                disk: self.disk.clone(),
+                queue: Arc::clone(&self.queue),
+            };
+            runtime.spawn_blocking(move || drop(flush));
+        }
+    }
+
+    /// Write every queued change before returning
+    fn flush(&self) {
+        let mut state = lock(&self.queue.state);
+        while state.flushing {
+            state = self
+                .queue
+                .idle
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+        if state.changes.is_empty() {
+            return;
+        }
+        state.flushing = true;
+        drop(state);
+        self.queue.drain(&self.disk);
+    }
+}
+
+impl Drop for DiskWriter {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// Background flush, done when dropped so that a task cancelled by runtime
+/// shutdown still writes and releases the queue
+struct Flush {
+    disk: DiskCache,
+    queue: Arc<Queue>,
+}
+
+impl Drop for Flush {
+    fn drop(&mut self) {
+        self.queue.drain(&self.disk);
+    }
+}
+
+impl Queue {
+    /// Write queued changes until none are left, then mark the queue idle
+    fn drain(&self, disk: &DiskCache) {
+        loop {
+            let changes = {
+                let mut state = lock(&self.state);
+                if state.changes.is_empty() {
+                    state.flushing = false;
+                    self.idle.notify_all();
+                    return;
+                }
+                std::mem::take(&mut state.changes)
+            };
+            disk.apply(&changes);
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
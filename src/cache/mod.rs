@@ -0,0 +1,224 @@
+//! Permission status cache with TTL and invalidation policies
+
+mod disk;
+
+use std::collections::HashSet;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use dashmap::DashMap;
+use tokio::time::Instant;
+
+use crate::types::{PermissionStatus, PermissionType};
+
+use disk::DiskWriter;
+pub use disk::{DiskCache, SessionIdentity};
+
+/// Cache shared by every manager configured with [`CachePolicy::shared`]
+static SHARED_CACHE: LazyLock<Arc<PermissionCache>> =
+    LazyLock::new(|| Arc::new(PermissionCache::new()));
+
+/// Controls how long permission statuses are cached by a
+/// [`PermissionManager`](crate::PermissionManager)
+///
+/// `Authorized` results are positive; every other status is negative and
+/// expires after `negative_ttl`, so a grant made in system settings is picked
+/// up without calling `clear_cache`. A TTL of `None` never expires.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachePolicy {
+    /// Lifetime of `Authorized` results
+    pub positive_ttl: Option<Duration>,
+    /// Lifetime of `Denied`, `Restricted`, `NotDetermined` and other non-grants
+    pub negative_ttl: Option<Duration>,
+    /// Permission types that are always probed and never cached
+    pub uncached: HashSet<PermissionType>,
+    /// Use the process-wide cache shared by all managers with `shared` set
+    pub shared: bool,
+    /// Persist statuses across processes; takes precedence over `shared`
+    pub disk: Option<DiskCache>,
+}
+
+impl Default for CachePolicy {
+    /// Positive results live for 5 minutes, negative results for 10 seconds, and
+    /// Bluetooth/WiFi (which follow radio state) are never cached.
+    fn default() -> Self {
+        Self {
+            positive_ttl: Some(Duration::from_secs(300)),
+            negative_ttl: Some(Duration::from_secs(10)),
+            uncached: HashSet::from([PermissionType::Bluetooth, PermissionType::WiFi]),
+            shared: false,
+            disk: None,
+        }
+    }
+}
+
+impl CachePolicy {
+    /// Cache every result until it is explicitly cleared
+    pub fn never_expire() -> Self {
+        Self {
+            positive_ttl: None,
+            negative_ttl: None,
+            uncached: HashSet::new(),
+            shared: false,
+            disk: None,
+        }
+    }
+
+    /// Disable caching entirely; every check reaches the backend
+    pub fn disabled() -> Self {
+        Self {
+            positive_ttl: Some(Duration::ZERO),
+            negative_ttl: Some(Duration::ZERO),
+            uncached: HashSet::new(),
+            shared: false,
+            disk: None,
+        }
+    }
+
+    /// Set the lifetime of `Authorized` results
+    pub fn with_positive_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.positive_ttl = ttl;
+        self
+    }
+
+    /// Set the lifetime of non-`Authorized` results
+    pub fn with_negative_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.negative_ttl = ttl;
+        self
+    }
+
+    /// Never cache `typ`
+    pub fn with_uncached(mut self, typ: PermissionType) -> Self {
+        self.uncached.insert(typ);
+        self
+    }
+
+    /// Use the process-wide cache shared by all managers
+    pub fn with_shared(mut self, shared: bool) -> Self {
+        self.shared = shared;
+        self
+    }
+
+    /// Persist statuses in `disk` so later processes start with them
+    ///
+    /// The manager gets a private cache loaded from `disk` and written through
+    /// on every change, so `shared` no longer applies.
+    pub fn with_disk(mut self, disk: DiskCache) -> Self {
+        self.disk = Some(disk);
+        self
+    }
+
+    /// Lifetime applied to `status` for `typ`, `Some(ZERO)` meaning "do not cache"
+    fn ttl_for(&self, typ: PermissionType, status: PermissionStatus) -> Option<Duration> {
+        if self.uncached.contains(&typ) {
+            return Some(Duration::ZERO);
+        }
+        match status {
+            PermissionStatus::Authorized => self.positive_ttl,
+            _ => self.negative_ttl,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct CacheEntry {
+    status: PermissionStatus,
+    stored_at: Instant,
+}
+
+/// Lock-free permission status cache
+///
+/// Expiry is evaluated against the [`CachePolicy`] of the reading manager, so
+/// managers sharing one cache may still apply different TTLs.
+#[derive(Debug, Default)]
+pub struct PermissionCache {
+    entries: DashMap<PermissionType, CacheEntry>,
+    disk: Option<DiskWriter>,
+}
+
+impl PermissionCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a cache backed by `disk`, starting with the statuses stored there
+    /// that are still fresh under `policy`
+    pub fn with_disk(disk: DiskCache, policy: &CachePolicy) -> Self {
+        let cache = Self::default();
+        for (typ, status, age) in disk.load() {
+            if policy.ttl_for(typ, status) == Some(Duration::ZERO) {
+                continue;
+            }
+            // Entries older than the monotonic clock's origin are stale anyway
+            if let Some(stored_at) = Instant::now().checked_sub(age) {
+                cache
+                    .entries
+                    .insert(typ, CacheEntry { status, stored_at });
+            }
+        }
+        Self {
+            disk: Some(DiskWriter::new(disk)),
+            ..cache
+        }
+    }
+
+    /// The process-wide cache used by [`CachePolicy::shared`] managers
+    pub fn shared() -> Arc<Self> {
+        Arc::clone(&SHARED_CACHE)
+    }
+
+    /// Cached status for `typ`, if present and not expired under `policy`
+    pub fn get(&self, typ: PermissionType, policy: &CachePolicy) -> Option<PermissionStatus> {
+        let entry = *self.entries.get(&typ)?;
+        match policy.ttl_for(typ, entry.status) {
+            Some(ttl) if entry.stored_at.elapsed() >= ttl => {
+                // Only evict the entry we judged stale, not a fresher concurrent insert
+                self.entries
+                    .remove_if(&typ, |_, current| current.stored_at == entry.stored_at);
+                None
+            },
+            _ => Some(entry.status),
+        }
+    }
+
+    /// Store `status` for `typ` unless `policy` excludes it from caching
+    pub fn insert(&self, typ: PermissionType, status: PermissionStatus, policy: &CachePolicy) {
+        // Uncached types never have an entry to drop
+        if policy.uncached.contains(&typ) {
+            return;
+        }
+        if policy.ttl_for(typ, status) == Some(Duration::ZERO) {
+            self.invalidate(typ);
+            return;
+        }
+        self.entries.insert(
+            typ,
+            CacheEntry {
+                status,
+                stored_at: Instant::now(),
+            },
+        );
+        if let Some(disk) = &self.disk {
+            disk.store(typ, Some(status));
+        }
+    }
+
+    /// Drop the cached status for `typ`
+    pub fn invalidate(&self, typ: PermissionType) {
+        if self.entries.remove(&typ).is_none() {
+            return;
+        }
+        if let Some(disk) = &self.disk {
+            disk.store(typ, None);
+        }
+    }
+
+    /// Drop every cached status
+    pub fn clear(&self) {
+        self.entries.clear();
+        if let Some(disk) = &self.disk {
+            disk.clear();
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! Stable string identifiers and categories for the public enums
+//!
+//! Identifiers are snake_case (`"full_disk_access"`, `"not_determined"`) and,
+//! like [`ErrorKind::code`], are never renamed once released. They are what the
+//! `serde` feature reads and writes, so values exchanged as JSON between MCP
+//! tools stay compatible across versions. `Display` stays human-readable and is
+//! not meant to be parsed.
+//!
+//! ```rust
+//! use kodegen_native_permissions::{PermissionCategory, PermissionType};
+//!
+//! let typ: PermissionType = "full_disk_access".parse().unwrap();
+//! assert_eq!(typ, PermissionType::FullDiskAccess);
+//! assert_eq!(typ.as_str(), "full_disk_access");
+//! assert_eq!(typ.category(), PermissionCategory::Files);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::access::AccessMode;
+use crate::types::{ErrorKind, PermissionStatus, PermissionType};
+
+/// Error returned when parsing an unknown identifier
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseIdError {
+    what: &'static str,
+    value: String,
+}
+
+impl ParseIdError {
+    fn new(what: &'static str, value: &str) -> Self {
+        Self {
+            what,
+            value: value.to_string(),
+        }
+    }
+
+    /// The string that failed to parse
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for ParseIdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown {}: {:?}", self.what, self.value)
+    }
+}
+
+impl Error for ParseIdError {}
+
+/// Implement `as_str`, `FromStr` and, with the `serde` feature, string
+/// (de)serialization from one variant-to-identifier table
+///
+/// The table also provides `VARIANTS`, so listings such as
+/// [`PermissionType::all`] cannot drift from the identifiers.
+macro_rules! identifiers {
+    ($ty:ident, $what:literal { $($variant:ident => $id:literal,)+ }) => {
+        impl $ty {
+            /// Every variant, in table order
+            #[allow(dead_code)] // Not every type has a listing
+            const VARIANTS: &'static [Self] = &[$(Self::$variant,)+];
+
+            /// Stable snake_case identifier
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $id,)+
+                }
+            }
+        }
+
+        impl FromStr for $ty {
+            type Err = ParseIdError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($id => Ok(Self::$variant),)+
+                    _ => Err(ParseIdError::new($what, s)),
+                }
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $ty {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $ty {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let id = <String as serde::Deserialize>::deserialize(deserializer)?;
+                id.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+identifiers!(PermissionType, "permission type" {
+    Camera => "camera",
+    Microphone => "microphone",
+    Location => "location",
+    Calendar => "calendar",
+    Reminders => "reminders",
+    Contacts => "contacts",
+    Bluetooth => "bluetooth",
+    FullDiskAccess => "full_disk_access",
+    ScreenCapture => "screen_capture",
+    Accessibility => "accessibility",
+    AccessibilityMouse => "accessibility_mouse",
+    InputMonitoring => "input_monitoring",
+    Photos => "photos",
+    SpeechRecognition => "speech_recognition",
+    DesktopFolder => "desktop_folder",
+    DocumentsFolder => "documents_folder",
+    DownloadsFolder => "downloads_folder",
+    AppleEvents => "apple_events",
+    DeveloperTools => "developer_tools",
+    AdminFiles => "admin_files",
+    AddressBook => "address_book",
+    All => "all",
+    Calls => "calls",
+    FaceID => "face_id",
+    FileProviderDomain => "file_provider_domain",
+    FileProviderPresence => "file_provider_presence",
+    FocusStatus => "focus_status",
+    MediaLibrary => "media_library",
+    Motion => "motion",
+    NearbyInteraction => "nearby_interaction",
+    PhotosAdd => "photos_add",
+    PostEvent => "post_event",
+    RemoteDesktop => "remote_desktop",
+    Siri => "siri",
+    NetworkVolumes => "network_volumes",
+    RemovableVolumes => "removable_volumes",
+    UbiquitousFileProvider => "ubiquitous_file_provider",
+    WillfulWrite => "willful_write",
+    WiFi => "wifi",
+    Notification => "notification",
+});
+
+identifiers!(PermissionStatus, "permission status" {
+    NotDetermined => "not_determined",
+    Authorized => "authorized",
+    Denied => "denied",
+    Restricted => "restricted",
+    PromptRequired => "prompt_required",
+    Unknown => "unknown",
+    NotApplicable => "not_applicable",
+});
+
+identifiers!(ErrorKind, "error kind" {
+    Denied => "denied",
+    Restricted => "restricted",
+    UserCancelled => "user_cancelled",
+    Cancelled => "cancelled",
+    Timeout => "timeout",
+    ServiceUnavailable => "service_unavailable",
+    BusUnavailable => "bus_unavailable",
+    SandboxBlocked => "sandbox_blocked",
+    NotSupportedOnPlatform => "not_supported_on_platform",
+    System => "system",
+    Platform => "platform",
+    InvalidConfig => "invalid_config",
+    NotFound => "not_found",
+    Unknown => "unknown",
+});
+
+identifiers!(AccessMode, "access mode" {
+    Read => "read",
+    Write => "write",
+    Execute => "execute",
+    Create => "create",
+});
+
+/// Broad grouping of permission types, e.g. for settings screens or summaries
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PermissionCategory {
+    /// Capture devices and media libraries
+    Media,
+    /// Folders, volumes and file providers
+    Files,
+    /// Input, automation and system integration
+    System,
+    /// Radios and nearby devices
+    Connectivity,
+    /// Location, contacts, calendars and other personal records
+    PersonalData,
+}
+
+identifiers!(PermissionCategory, "permission category" {
+    Media => "media",
+    Files => "files",
+    System => "system",
+    Connectivity => "connectivity",
+    PersonalData => "personal_data",
+});
+
+impl PermissionCategory {
+    /// Every category, in declaration order
+    pub fn all() -> impl Iterator<Item = PermissionCategory> {
+        Self::VARIANTS.iter().copied()
+    }
+
+    /// Permission types in this category, in declaration order
+    pub fn permissions(self) -> impl Iterator<Item = PermissionType> {
+        PermissionType::all().filter(move |typ| typ.category() == self)
+    }
+}
+
+impl PermissionType {
+    /// Every concrete permission type, in declaration order
+    ///
+    /// [`PermissionType::All`] is left out since it is not a permission of its own.
+    pub fn all() -> impl Iterator<Item = PermissionType> {
+        Self::VARIANTS
+            .iter()
+            .copied()
+            .filter(|typ| *typ != PermissionType::All)
+    }
+
+    /// Category this permission belongs to
+    pub fn category(self) -> PermissionCategory {
+        use PermissionType::*;
+
+        match self {
+            Camera | Microphone | ScreenCapture | SpeechRecognition | Photos | PhotosAdd
+            | MediaLibrary => PermissionCategory::Media,
+            FullDiskAccess
+            | DesktopFolder
+            | DocumentsFolder
+            | DownloadsFolder
+            | AdminFiles
+            | NetworkVolumes
+            | RemovableVolumes
+            | FileProviderDomain
+            | FileProviderPresence
+            | UbiquitousFileProvider
+            | WillfulWrite => PermissionCategory::Files,
+            Accessibility | AccessibilityMouse | InputMonitoring | AppleEvents | DeveloperTools
+            | PostEvent | RemoteDesktop | Notification | FocusStatus | Siri | FaceID | Motion
+            | All => PermissionCategory::System,
+            Bluetooth | WiFi | NearbyInteraction => PermissionCategory::Connectivity,
+            Location | Calendar | Reminders | Contacts | AddressBook | Calls => {
+                PermissionCategory::PersonalData
+            },
+        }
+    }
+}
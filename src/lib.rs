@@ -27,9 +27,39 @@
 
 #![recursion_limit = "256"]
 
+pub mod access;
+#[cfg(feature = "audit")]
+pub mod audit;
+pub mod batch;
+pub mod blocking;
+pub mod cache;
+pub mod ids;
 pub mod manager;
+pub mod manifest;
+pub mod policy;
+pub mod remediation;
+pub mod report;
+pub mod request;
 pub mod traits;
 pub mod types;
+pub mod watch;
+
+// Private runtime bridging the sync API to async platform probes and requests
+mod runtime;
+
+// Request coalescing and prompt serialization
+mod scheduler;
+
+// Spans for the optional `tracing` feature
+mod trace;
+
+// TOML readers shared by manifests and policy files
+#[cfg(feature = "toml")]
+mod toml;
+
+// Scriptable fake backend for tests
+#[cfg(feature = "testing")]
+pub mod testing;
 
 // Windows-specific configuration module
 #[cfg(target_os = "windows")]
@@ -45,9 +75,33 @@ pub mod platforms;
 pub mod platforms;
 
 // Clean re-exports
+pub use access::{AccessMode, ModeAccess, PathAccess};
+#[cfg(feature = "audit")]
+pub use audit::{AuditLog, AuditSummary, AuditViolation};
+pub use batch::{BatchOptions, BatchResult, BatchStream};
+pub use cache::{CachePolicy, DiskCache, PermissionCache, SessionIdentity};
+pub use ids::{ParseIdError, PermissionCategory};
 pub use manager::PermissionManager;
+pub use manifest::{
+    FeatureReadiness, FeatureSpec, Manifest, Readiness, ReadinessReport, Requirement,
+    RequirementState,
+};
+pub use policy::{Caller, Policy, PolicyAction, PolicyDecision, PolicyRule};
+pub use remediation::Remediation;
+pub use report::{Confidence, Evidence, PermissionReport};
+pub use request::{LocationAccuracy, RequestOptions, WindowIdentifier};
 pub use traits::PermissionHandler;
-pub use types::{PermissionError, PermissionStatus, PermissionType};
+pub use types::{ErrorKind, PermissionError, PermissionStatus, PermissionType, Support};
+pub use watch::{PermissionChange, PermissionWatch};
+
+#[cfg(target_os = "macos")]
+pub use platforms::macos::MacOSHandler;
+
+#[cfg(target_os = "windows")]
+pub use platforms::windows::WindowsHandler;
+
+#[cfg(target_os = "linux")]
+pub use platforms::linux::LinuxHandler;
 
 // Re-export Windows config functions
 #[cfg(target_os = "windows")]
@@ -1,103 +1,352 @@
 //! Permission manager with caching and async support
 
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
-use tokio::sync::oneshot;
+use crate::access::{self, AccessMode, ModeAccess, PathAccess};
+#[cfg(feature = "audit")]
+use crate::audit::{AuditLog, Entry, Operation};
+use crate::batch::{self, BatchOptions, BatchResult, BatchStream};
+use crate::cache::{CachePolicy, PermissionCache};
+use crate::policy::{Caller, Policy, PolicyDecision};
+use crate::remediation::Remediation;
+use crate::report::{self, Confidence, PermissionReport};
+use crate::request::RequestOptions;
+use crate::scheduler::RequestScheduler;
+use crate::trace::Timed;
+use crate::traits::PermissionHandler;
+use crate::types::{ErrorKind, PermissionError, PermissionStatus, PermissionType, Support};
+use crate::watch::{self, PermissionWatch};
 
-use crate::types::{PermissionError, PermissionStatus, PermissionType};
-
-#[cfg(target_os = "macos")]
-use crate::platforms::macos::handler::MacOSHandler;
+/// Name recorded for statuses decided by the administrative policy
+const POLICY: &str = "policy";
 
 /// Thread-safe permission manager with caching and async support
+///
+/// All permission checks and requests are dispatched to a [`PermissionHandler`]
+/// backend. [`PermissionManager::new`] uses the built-in handler for the current
+/// platform; [`PermissionManager::with_backend`] accepts any other implementation,
+/// including one that wraps [`PermissionManager::platform_backend`].
+///
+/// Results are cached according to a [`CachePolicy`]; see
+/// [`PermissionManager::with_cache_policy`].
+///
+/// An administrative [`Policy`] is applied before the backend; see
+/// [`PermissionManager::with_policy`] and [`PermissionManager::with_caller`].
 pub struct PermissionManager {
-    cache: Arc<RwLock<HashMap<PermissionType, PermissionStatus>>>,
+    backend: Arc<dyn PermissionHandler>,
+    cache: Arc<PermissionCache>,
+    cache_policy: CachePolicy,
+    scheduler: Arc<RequestScheduler>,
+    policy: Arc<Policy>,
+    caller: Caller,
+    #[cfg(feature = "audit")]
+    audit: Option<Arc<AuditLog>>,
 }
 
 impl PermissionManager {
-    /// Create a new permission manager instance
+    /// Create a new permission manager instance using the platform backend
+    ///
+    /// With the `toml` feature, the administrative policy files at
+    /// [`Policy::default_paths`] are loaded and enforced.
     pub fn new() -> Self {
+        let manager = Self::with_backend(Self::platform_backend());
+        #[cfg(feature = "toml")]
+        let manager = manager.with_policy(Policy::load());
+        manager
+    }
+
+    /// Create a permission manager that dispatches to a custom backend
+    ///
+    /// The backend may replace the platform implementation entirely or decorate
+    /// the handler returned by [`PermissionManager::platform_backend`]. No
+    /// policy files are loaded; see [`PermissionManager::with_policy`].
+    pub fn with_backend(backend: Arc<dyn PermissionHandler>) -> Self {
         Self {
-            cache: Arc::new(RwLock::new(HashMap::new())),
+            backend,
+            cache: Arc::new(PermissionCache::new()),
+            cache_policy: CachePolicy::default(),
+            scheduler: Arc::new(RequestScheduler::default()),
+            policy: Arc::new(Policy::new()),
+            caller: Caller::new(),
+            #[cfg(feature = "audit")]
+            audit: None,
         }
     }
 
+    /// Replace the cache policy of this manager
+    ///
+    /// With [`CachePolicy::disk`] set, the manager starts from the statuses
+    /// persisted by earlier processes and writes its own back. With
+    /// [`CachePolicy::shared`] set, it reads and writes the process-wide cache
+    /// shared by every other shared manager; otherwise it starts with a fresh
+    /// private cache.
+    pub fn with_cache_policy(mut self, policy: CachePolicy) -> Self {
+        self.cache = if let Some(disk) = &policy.disk {
+            Arc::new(PermissionCache::with_disk(disk.clone(), &policy))
+        } else if policy.shared {
+            PermissionCache::shared()
+        } else {
+            Arc::new(PermissionCache::new())
+        };
+        self.cache_policy = policy;
+        self
+    }
+
+    /// Cache policy applied by this manager
+    pub fn cache_policy(&self) -> &CachePolicy {
+        &self.cache_policy
+    }
+
+    /// Replace the administrative policy applied before the backend
+    pub fn with_policy(mut self, policy: Policy) -> Self {
+        self.policy = Arc::new(policy);
+        self
+    }
+
+    /// Administrative policy applied by this manager
+    pub fn policy(&self) -> &Policy {
+        &self.policy
+    }
+
+    /// Act on behalf of `caller` when matching policy rules
+    ///
+    /// Clones of a manager share its cache and prompt queue, so
+    /// `manager.clone().with_caller(..)` gives each tool its own identity
+    /// without duplicating state. Policy decisions are never cached.
+    pub fn with_caller(mut self, caller: Caller) -> Self {
+        self.caller = caller;
+        self
+    }
+
+    /// Caller this manager acts for
+    pub fn caller(&self) -> &Caller {
+        &self.caller
+    }
+
+    /// Record every check and request in `log`
+    ///
+    /// Entries name this manager's [`Caller`], and clones made afterwards write
+    /// to the same log. If an entry cannot be written, the operation fails with
+    /// the write error instead of returning its result. Async checks and
+    /// requests write on Tokio's blocking pool, so a slow or contended log file
+    /// never stalls the runtime. Requires the `audit` feature.
+    #[cfg(feature = "audit")]
+    pub fn with_audit(mut self, log: Arc<AuditLog>) -> Self {
+        self.audit = Some(log);
+        self
+    }
+
+    /// Audit log this manager writes to, if any
+    #[cfg(feature = "audit")]
+    pub fn audit_log(&self) -> Option<&Arc<AuditLog>> {
+        self.audit.as_ref()
+    }
+
+    /// Policy rule deciding `typ` for this manager's caller, if any
+    pub fn policy_decision(&self, typ: PermissionType) -> Option<PolicyDecision> {
+        self.policy.decide(typ, &self.caller)
+    }
+
+    /// Built-in permission handler for the current platform
+    pub fn platform_backend() -> Arc<dyn PermissionHandler> {
+        #[cfg(target_os = "macos")]
+        return Arc::new(crate::platforms::macos::MacOSHandler::new());
+
+        #[cfg(target_os = "windows")]
+        return Arc::new(crate::platforms::windows::WindowsHandler::new());
+
+        #[cfg(target_os = "linux")]
+        return Arc::new(crate::platforms::linux::LinuxHandler::new());
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+        return Arc::new(UnsupportedHandler);
+    }
+
+    /// Backend this manager dispatches to
+    pub fn backend(&self) -> &Arc<dyn PermissionHandler> {
+        &self.backend
+    }
+
+    /// How the backend supports `typ` on this platform
+    ///
+    /// Lets callers tell a status the OS reported apart from one the backend
+    /// derived, and from permissions that do not exist here at all.
+    pub fn support(&self, typ: PermissionType) -> Support {
+        self.backend.support(typ)
+    }
+
+    /// How the user can grant `typ` when it is denied or restricted
+    ///
+    /// When the administrative policy forbids `typ`, the only remedy is a policy
+    /// change and the guidance says so; otherwise it comes from the backend, and
+    /// depends on the platform and on any sandbox the process runs in.
+    pub fn remediation(&self, typ: PermissionType) -> Remediation {
+        match self.policy_decision(typ) {
+            Some(decision) if decision.status() == PermissionStatus::Restricted => {
+                Remediation::new(typ).with_step(format!(
+                    "{} is {}; only an administrator can change the policy",
+                    typ, decision
+                ))
+            },
+            _ => self.backend.remediation(typ),
+        }
+    }
+
+    /// Open the system settings where the user grants `typ`
+    ///
+    /// On Linux this launches the desktop's own settings panel, see
+    /// [`platforms::linux::settings`](crate::platforms::linux::settings). Useful
+    /// after a denial, together with [`remediation`](Self::remediation).
+    pub fn open_settings(&self, typ: PermissionType) -> Result<(), PermissionError> {
+        self.backend
+            .open_settings(typ)
+            .map_err(|e| e.or_backend(self.backend.name()))
+    }
+
     /// Synchronously check permission status (uses cache if available)
     pub fn check_permission(
         &self,
         typ: PermissionType,
     ) -> Result<PermissionStatus, PermissionError> {
+        let timed = Timed::check(typ, self.backend.name());
+        let _entered = timed.enter();
+        let (status, decided_by) = self.check_unaudited(typ);
+        timed.finish(&status);
+        self.audit(typ, status, decided_by)
+    }
+
+    /// Body of [`check_permission`](Self::check_permission), also returning who
+    /// decided the status
+    fn check_unaudited(
+        &self,
+        typ: PermissionType,
+    ) -> (Result<PermissionStatus, PermissionError>, &'static str) {
+        if let Some(decision) = self.policy_decision(typ) {
+            return (Ok(decision.status()), POLICY);
+        }
+        let backend = self.backend.name();
+        if self.support(typ) == Support::NotApplicable {
+            return (Ok(PermissionStatus::NotApplicable), backend);
+        }
+
         // Try cache first
-        if let Ok(cache) = self.cache.read()
-            && let Some(status) = cache.get(&typ)
-        {
-            return Ok(*status);
+        if let Some(status) = self.cache.get(typ, &self.cache_policy) {
+            return (Ok(status), backend);
         }
 
-        // Platform-specific check logic
-        let status = match typ {
-            #[cfg(target_os = "macos")]
-            PermissionType::Camera | PermissionType::Microphone => {
-                crate::platforms::macos::av_permissions::check_permission(typ)
-            },
-            #[cfg(target_os = "macos")]
-            PermissionType::Location => {
-                crate::platforms::macos::location_permissions::check_permission()
-            },
-            #[cfg(target_os = "macos")]
-            PermissionType::Calendar | PermissionType::Reminders => {
-                crate::platforms::macos::event_kit_permissions::check_permission(typ)
-            },
-            #[cfg(target_os = "macos")]
-            PermissionType::Contacts => {
-                crate::platforms::macos::contacts_permissions::check_permission()
-            },
-            #[cfg(target_os = "macos")]
-            PermissionType::Bluetooth => {
-                crate::platforms::macos::bluetooth_permissions::check_permission()
-            },
-            #[cfg(target_os = "macos")]
-            PermissionType::Accessibility | PermissionType::AccessibilityMouse => {
-                MacOSHandler::new().check_accessibility()
-            },
-            #[cfg(target_os = "macos")]
-            PermissionType::WiFi => {
-                MacOSHandler::new().check_wifi()
-            },
-            #[cfg(target_os = "macos")]
-            PermissionType::ScreenCapture => {
-                MacOSHandler::new().check_screen_recording()
-            },
-            #[cfg(target_os = "macos")]
-            PermissionType::InputMonitoring => {
-                MacOSHandler::new().check_input_monitoring()
-            },
-            #[cfg(target_os = "macos")]
-            PermissionType::Notification => {
-                crate::platforms::macos::notification_permissions::check_permission()
-            },
-            #[cfg(target_os = "macos")]
-            _ => crate::platforms::macos::tcc_permissions::check_permission(typ),
+        let status = self
+            .backend
+            .check_permission(typ)
+            .map_err(|e| e.or_backend(backend));
 
-            #[cfg(target_os = "windows")]
-            _ => crate::platforms::windows::check_permission(typ),
+        // Update cache on success
+        if let Ok(s) = &status {
+            self.cache.insert(typ, *s, &self.cache_policy);
+        }
 
-            #[cfg(target_os = "linux")]
-            _ => crate::platforms::linux::check_permission(typ),
+        (status, backend)
+    }
 
-            #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
-            _ => Err(PermissionError::Unknown),
-        };
+    /// Asynchronously check permission status (uses cache if available)
+    ///
+    /// Unlike [`check_permission`](Self::check_permission), backends with
+    /// async-native probes never block the calling runtime.
+    pub async fn check_permission_async(
+        &self,
+        typ: PermissionType,
+    ) -> Result<PermissionStatus, PermissionError> {
+        let timed = Timed::check(typ, self.backend.name());
+        let (status, decided_by) = timed.instrument(self.check_async_unaudited(typ, true)).await;
+        timed.finish(&status);
+        self.audit_async(typ, status, decided_by).await
+    }
 
-        // Update cache on success
-        if let Ok(s) = &status
-            && let Ok(mut cache) = self.cache.write()
-        {
-            cache.insert(typ, *s);
+    /// Body of [`check_permission_async`](Self::check_permission_async), reading
+    /// the cache only if `cached`
+    async fn check_async_unaudited(
+        &self,
+        typ: PermissionType,
+        cached: bool,
+    ) -> (Result<PermissionStatus, PermissionError>, &'static str) {
+        if let Some(decision) = self.policy_decision(typ) {
+            return (Ok(decision.status()), POLICY);
+        }
+        let backend = self.backend.name();
+        if self.support(typ) == Support::NotApplicable {
+            return (Ok(PermissionStatus::NotApplicable), backend);
+        }
+        if cached && let Some(status) = self.cache.get(typ, &self.cache_policy) {
+            return (Ok(status), backend);
         }
-        
-        status
+
+        let status = self
+            .backend
+            .check_permission_async(typ)
+            .await
+            .map_err(|e| e.or_backend(backend));
+
+        if let Ok(s) = &status {
+            self.cache.insert(typ, *s, &self.cache_policy);
+        }
+        (status, backend)
+    }
+
+    /// Check `typ` as [`check_permission_async`](Self::check_permission_async)
+    /// does, but always asking the backend and without auditing
+    pub(crate) async fn recheck(
+        &self,
+        typ: PermissionType,
+    ) -> Result<PermissionStatus, PermissionError> {
+        self.check_async_unaudited(typ, false).await.0
+    }
+
+    /// Check permission status and report how it was determined
+    ///
+    /// Always probes the backend (the cache has no provenance) and refreshes the
+    /// cache with the result. The report lists every probe that ran and rates the
+    /// status as verified by the OS, derived heuristically, or assumed. A status
+    /// decided by the administrative policy cites the rule as its only evidence.
+    pub async fn check_permission_detailed(
+        &self,
+        typ: PermissionType,
+    ) -> Result<PermissionReport, PermissionError> {
+        let support = self.support(typ);
+        let backend = self.backend.name();
+        let timed = Timed::check(typ, backend);
+        let checked = async {
+            if let Some(decision) = self.policy_decision(typ) {
+                let report = report::collect(typ, support, async move {
+                    let origin = decision.rule.origin.as_ref();
+                    let target = origin.map_or_else(|| POLICY.into(), |p| p.display().to_string());
+                    report::record(Confidence::Verified, POLICY, target, &decision);
+                    Ok(decision.status())
+                })
+                .await;
+                (report, POLICY)
+            } else if support == Support::NotApplicable {
+                let not_applicable = async { Ok(PermissionStatus::NotApplicable) };
+                let report = report::collect(typ, support, not_applicable).await;
+                (report, backend)
+            } else {
+                let report = report::collect(typ, support, self.backend.check_permission_async(typ))
+                    .await
+                    .map_err(|e| e.or_backend(backend));
+                if let Ok(report) = &report {
+                    self.cache.insert(typ, report.status, &self.cache_policy);
+                }
+                (report, backend)
+            }
+        };
+        let (report, decided_by) = timed.instrument(checked).await;
+
+        let status = report.as_ref().map(|r| r.status).map_err(Clone::clone);
+        timed.finish(&status);
+        self.audit_async(typ, status, decided_by).await?;
+        report
     }
 
     /// Asynchronously request permission (shows native OS dialog)
@@ -105,124 +354,368 @@ impl PermissionManager {
     /// This method triggers the native OS permission dialog and awaits the result
     /// using a tokio oneshot channel. The OS handles user interaction asynchronously,
     /// and results are delivered via OS thread callbacks.
+    ///
+    /// Concurrent requests for the same permission share one prompt, and prompts
+    /// for different permissions are shown one at a time, first come first served.
     pub async fn request_permission(
         &self,
         typ: PermissionType,
     ) -> Result<PermissionStatus, PermissionError> {
-        let cache = self.cache.clone();
-        
-        // ✅ Step 1: Create tokio oneshot channel
-        // The sender works from any thread (including OS callbacks)
-        // The receiver integrates with tokio's async runtime
-        let (tx, rx) = oneshot::channel();
-        
-        // ✅ Step 2: Call platform code directly - NO spawn_blocking!
-        // Platform functions trigger OS dialogs and return immediately
-        // Results arrive later via OS thread callbacks that call tx.send()
-        #[cfg(target_os = "macos")]
-        match typ {
-            PermissionType::Camera | PermissionType::Microphone => {
-                crate::platforms::macos::av_permissions::request_permission(typ, tx);
-            },
-            PermissionType::Location => {
-                crate::platforms::macos::location_permissions::request_permission(tx);
-            },
-            PermissionType::Calendar | PermissionType::Reminders => {
-                crate::platforms::macos::event_kit_permissions::request_permission(typ, tx);
-            },
-            PermissionType::Contacts => {
-                crate::platforms::macos::contacts_permissions::request_permission(tx);
-            },
-            PermissionType::Bluetooth => {
-                crate::platforms::macos::bluetooth_permissions::request_permission(tx);
-            },
-            PermissionType::Accessibility | PermissionType::AccessibilityMouse => {
-                MacOSHandler::new().request_accessibility(tx);
-            },
-            PermissionType::WiFi => {
-                // WiFi doesn't have a request API, just return current status
-                return MacOSHandler::new().check_wifi();
-            },
-            PermissionType::ScreenCapture => {
-                MacOSHandler::new().request_screen_recording(tx);
-            },
-            PermissionType::InputMonitoring => {
-                MacOSHandler::new().request_input_monitoring(tx);
-            },
-            PermissionType::Notification => {
-                crate::platforms::macos::notification_permissions::request_permission(tx);
-            },
-            _ => {
-                // TCC permissions have synchronous request methods
-                return crate::platforms::macos::tcc_permissions::request_permission(typ);
+        self.request_permission_with(typ, RequestOptions::default())
+            .await
+    }
+
+    /// Request permission with a timeout and/or cancellation token
+    ///
+    /// Resolves with an [`ErrorKind::Timeout`] or [`ErrorKind::Cancelled`] error
+    /// when the respective limit is hit first. Either outcome, as well as dropping
+    /// the returned future, closes the backend's result channel so it can dismiss
+    /// the prompt (the Linux portal request is closed; macOS and Windows system
+    /// prompts cannot be withdrawn and their late answer is discarded). A prompt
+    /// shared with other callers stays open until all of them have given up.
+    pub async fn request_permission_with(
+        &self,
+        typ: PermissionType,
+        options: RequestOptions,
+    ) -> Result<PermissionStatus, PermissionError> {
+        self.schedule_request(typ, options).await
+    }
+
+    /// Join or schedule the backend request for `typ`
+    ///
+    /// Scheduling happens before the returned future is first polled, so prompts
+    /// appear in the order this is called.
+    fn schedule_request(
+        &self,
+        typ: PermissionType,
+        options: RequestOptions,
+    ) -> impl Future<Output = Result<PermissionStatus, PermissionError>> + Send + use<> {
+        let decision = self.policy_decision(typ);
+        // Nothing to ask for a permission the policy decides, or that does not
+        // exist on this platform. Otherwise share the in-flight request for this
+        // type, or queue a new one that reaches the backend once its prompt's turn
+        // comes up.
+        let (request, prompted) = (decision.is_none()
+            && self.support(typ) != Support::NotApplicable)
+            .then(|| self.scheduler.join(typ, &options, &self.backend))
+            .unzip();
+        let backend = self.backend.name();
+        #[cfg(feature = "audit")]
+        let audit = self.audit.clone().map(|log| {
+            let decided_by = if decision.is_some() { POLICY } else { backend };
+            (log, self.caller.clone(), prompted, decided_by)
+        });
+        // Dropping the flag spares the backend from reporting the prompt
+        #[cfg(not(feature = "audit"))]
+        drop(prompted);
+        let cache = Arc::clone(&self.cache);
+        let cache_policy = self.cache_policy.clone();
+        let timed = Timed::request(typ, backend);
+
+        let result = async move {
+            if let Some(decision) = decision {
+                return Ok(decision.status());
+            }
+            let Some(request) = request else {
+                return Ok(PermissionStatus::NotApplicable);
+            };
+
+            // Await the result, racing it against timeout and cancellation
+            // Returning early drops this caller's share; once every share is gone
+            // the receiver is dropped, which tells the backend to close its prompt
+            let cancelled = async {
+                match &options.cancel {
+                    Some(token) => token.cancelled().await,
+                    None => std::future::pending().await,
+                }
+            };
+            let timed_out = async {
+                match options.timeout {
+                    Some(timeout) => tokio::time::sleep(timeout).await,
+                    None => std::future::pending().await,
+                }
+            };
+            let result = tokio::select! {
+                result = request => result.map_err(|e| e.or_backend(backend))?,
+                _ = cancelled => return Err(PermissionError::new(ErrorKind::Cancelled)),
+                _ = timed_out => return Err(PermissionError::new(ErrorKind::Timeout)),
+            };
+
+            // Update cache on success
+            cache.insert(typ, result, &cache_policy);
+
+            Ok(result)
+        };
+
+        async move {
+            let result = timed.instrument(result).await;
+            timed.finish(&result);
+            #[cfg(feature = "audit")]
+            if let Some((log, caller, prompted, decided_by)) = audit {
+                log.record_async(Entry {
+                    operation: Operation::Request,
+                    permission: typ,
+                    caller,
+                    outcome: result.clone(),
+                    prompted: prompted.is_some_and(|prompted| prompted.get()),
+                    backend: decided_by,
+                })
+                .await?;
             }
+            result
         }
-        
-        #[cfg(target_os = "windows")]
-        crate::platforms::windows::request_permission(typ, tx);
-        
-        #[cfg(target_os = "linux")]
-        crate::platforms::linux::request_permission(typ, tx);
-        
-        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
-        {
-            let _ = tx; // Consume tx to avoid unused variable warning
-            return Err(PermissionError::Unknown);
-        }
-        
-        // ✅ Step 3: Directly await the tokio receiver - clean async!
-        // rx.await returns Result<Result<PermissionStatus, PermissionError>, RecvError>
-        // Outer Result is for channel errors (sender dropped)
-        // Inner Result is for permission errors
-        let result = rx.await
-            .map_err(|_| PermissionError::SystemError("Permission channel closed".into()))??;
-        
-        // ✅ Step 4: Update cache on success
-        if let Ok(mut cache_guard) = cache.write() {
-            cache_guard.insert(typ, result);
-        }
-        
-        Ok(result)
     }
 
     /// Request multiple permissions concurrently
     /// 
-    /// Returns a map of permission types to their results. Permissions are requested
-    /// in parallel using tokio tasks for maximum efficiency.
+    /// Returns a map of permission types to their results. Requests that need no
+    /// UI run in parallel; prompts are shown one at a time in the order of `types`.
+    /// See [`request_many`](Self::request_many) for results in input order.
     pub async fn request_permissions(
         &self,
         types: &[PermissionType],
     ) -> HashMap<PermissionType, Result<PermissionStatus, PermissionError>> {
-        let mut tasks = Vec::new();
-        
-        for &typ in types {
-            let manager = self.clone();
-            let task = tokio::spawn(async move {
-                (typ, manager.request_permission(typ).await)
-            });
-            tasks.push(task);
-        }
-
-        let mut results = HashMap::new();
-        for task in tasks {
-            if let Ok((typ, result)) = task.await {
-                results.insert(typ, result);
-            }
+        self.request_many(types, &BatchOptions::default())
+            .await
+            .into_iter()
+            .map(|item| (item.permission, item.result))
+            .collect()
+    }
+
+    /// Check several permissions, returning one result per type in input order
+    ///
+    /// See [`batch`] for concurrency and failure handling.
+    pub async fn check_many(
+        &self,
+        types: &[PermissionType],
+        options: &BatchOptions,
+    ) -> Vec<BatchResult> {
+        self.check_many_stream(types, options)
+            .collect_ordered()
+            .await
+    }
+
+    /// Check several permissions, yielding each result as it completes
+    pub fn check_many_stream(
+        &self,
+        types: &[PermissionType],
+        options: &BatchOptions,
+    ) -> BatchStream {
+        let manager = self.clone();
+        batch::spawn(types, options.concurrency, move |typ| {
+            let manager = manager.clone();
+            async move { manager.check_permission_async(typ).await }
+        })
+    }
+
+    /// Request several permissions, returning one result per type in input order
+    ///
+    /// Prompts are shown one at a time in input order, as with
+    /// [`request_permission`](Self::request_permission); see [`batch`] for
+    /// concurrency and failure handling.
+    pub async fn request_many(
+        &self,
+        types: &[PermissionType],
+        options: &BatchOptions,
+    ) -> Vec<BatchResult> {
+        self.request_many_stream(types, options)
+            .collect_ordered()
+            .await
+    }
+
+    /// Request several permissions, yielding each result as it is answered
+    pub fn request_many_stream(
+        &self,
+        types: &[PermissionType],
+        options: &BatchOptions,
+    ) -> BatchStream {
+        let manager = self.clone();
+        let request = options.request.clone();
+        batch::spawn(types, options.concurrency, move |typ| {
+            manager.schedule_request(typ, request.clone())
+        })
+    }
+
+    /// Record a finished check in the audit log, if one is attached
+    ///
+    /// A failed write replaces the result, so nothing goes unaudited.
+    #[cfg(feature = "audit")]
+    fn audit(
+        &self,
+        typ: PermissionType,
+        outcome: Result<PermissionStatus, PermissionError>,
+        backend: &'static str,
+    ) -> Result<PermissionStatus, PermissionError> {
+        if let Some(log) = &self.audit {
+            log.record(&self.entry(Operation::Check, typ, outcome.clone(), backend))?;
+        }
+        outcome
+    }
+
+    /// [`audit`](Self::audit) for a `mode` probe of a path under `category`
+    #[cfg(feature = "audit")]
+    fn audit_access(
+        &self,
+        category: PermissionType,
+        mode: AccessMode,
+        outcome: Result<PermissionStatus, PermissionError>,
+        backend: &'static str,
+    ) -> Result<PermissionStatus, PermissionError> {
+        if let Some(log) = &self.audit {
+            log.record(&self.entry(Operation::Access(mode), category, outcome.clone(), backend))?;
+        }
+        outcome
+    }
+
+    /// [`audit`](Self::audit) for async callers, writing on the blocking pool
+    #[cfg(feature = "audit")]
+    async fn audit_async(
+        &self,
+        typ: PermissionType,
+        outcome: Result<PermissionStatus, PermissionError>,
+        backend: &'static str,
+    ) -> Result<PermissionStatus, PermissionError> {
+        if let Some(log) = &self.audit {
+            let entry = self.entry(Operation::Check, typ, outcome.clone(), backend);
+            Arc::clone(log).record_async(entry).await?;
+        }
+        outcome
+    }
+
+    /// Audit entry for an operation made by this manager's caller without a prompt
+    #[cfg(feature = "audit")]
+    fn entry(
+        &self,
+        operation: Operation,
+        typ: PermissionType,
+        outcome: Result<PermissionStatus, PermissionError>,
+        backend: &'static str,
+    ) -> Entry {
+        Entry {
+            operation,
+            permission: typ,
+            caller: self.caller.clone(),
+            outcome,
+            prompted: false,
+            backend,
         }
+    }
+
+    /// Without the `audit` feature there is no log to write to
+    #[cfg(not(feature = "audit"))]
+    fn audit(
+        &self,
+        _typ: PermissionType,
+        outcome: Result<PermissionStatus, PermissionError>,
+        _backend: &'static str,
+    ) -> Result<PermissionStatus, PermissionError> {
+        outcome
+    }
 
-        results
+    #[cfg(not(feature = "audit"))]
+    async fn audit_async(
+        &self,
+        _typ: PermissionType,
+        outcome: Result<PermissionStatus, PermissionError>,
+        _backend: &'static str,
+    ) -> Result<PermissionStatus, PermissionError> {
+        outcome
+    }
+
+    #[cfg(not(feature = "audit"))]
+    fn audit_access(
+        &self,
+        _category: PermissionType,
+        _mode: AccessMode,
+        outcome: Result<PermissionStatus, PermissionError>,
+        _backend: &'static str,
+    ) -> Result<PermissionStatus, PermissionError> {
+        outcome
+    }
+
+    /// Check which `modes` of access the process has to `path`
+    ///
+    /// The result names the protected category the path falls under. If the
+    /// administrative policy forbids that category, every mode reports
+    /// [`PermissionStatus::Restricted`] without touching the path; otherwise each
+    /// mode is probed on the calling thread as described in [`access`]. Each
+    /// mode of a path under a category is recorded in the audit log, if one is
+    /// attached.
+    pub fn check_path(&self, path: impl AsRef<Path>, modes: &[AccessMode]) -> PathAccess {
+        let path = path.as_ref();
+        let category = access::protected_category(path);
+        let forbidden = category
+            .and_then(|typ| self.policy_decision(typ))
+            .is_some_and(|decision| decision.status() == PermissionStatus::Restricted);
+        let decided_by = if forbidden { POLICY } else { self.backend.name() };
+        let modes = modes
+            .iter()
+            .map(|&mode| {
+                let status = if forbidden {
+                    Ok(PermissionStatus::Restricted)
+                } else {
+                    access::probe(path, mode)
+                };
+                let status = match category {
+                    Some(category) => self.audit_access(category, mode, status, decided_by),
+                    None => status,
+                };
+                ModeAccess { mode, status }
+            })
+            .collect();
+        PathAccess {
+            path: path.to_path_buf(),
+            category,
+            modes,
+        }
+    }
+
+    /// Blocking facade sharing this manager's backend, cache and prompt queue
+    ///
+    /// For synchronous callers with no Tokio runtime of their own; see
+    /// [`blocking`](crate::blocking).
+    pub fn blocking(&self) -> crate::blocking::PermissionManager {
+        self.clone().into()
+    }
+
+    /// Watch permissions for status changes
+    ///
+    /// Returns a stream yielding a [`PermissionChange`](crate::PermissionChange)
+    /// each time one of `types` changes status. Permissions the backend can observe
+    /// natively (see [`PermissionHandler::watch_changes`]) are re-checked when the
+    /// backend signals them; the rest are polled every
+    /// [`DEFAULT_POLL_INTERVAL`](crate::watch::DEFAULT_POLL_INTERVAL). Statuses
+    /// are the ones [`check_permission_async`](Self::check_permission_async)
+    /// reports, so the policy applies, and they refresh the cache. Checks that
+    /// fail are skipped and, with the `tracing` feature, logged as `WARN` events.
+    /// Must be called within a Tokio runtime.
+    pub fn watch(&self, types: &[PermissionType]) -> PermissionWatch {
+        self.watch_with_interval(types, watch::DEFAULT_POLL_INTERVAL)
+    }
+
+    /// Watch permissions, polling non-native types every `interval`
+    pub fn watch_with_interval(
+        &self,
+        types: &[PermissionType],
+        interval: Duration,
+    ) -> PermissionWatch {
+        watch::spawn(self.clone(), types, interval)
     }
 
     /// Manually refresh the cache for a specific permission
     pub fn refresh_cache(&self, typ: PermissionType) {
+        self.cache.invalidate(typ);
         let _ = self.check_permission(typ);
     }
 
+    /// Drop the cached status for a specific permission
+    pub fn invalidate(&self, typ: PermissionType) {
+        self.cache.invalidate(typ);
+    }
+
     /// Clear all cached permission statuses
     pub fn clear_cache(&self) {
-        if let Ok(mut cache) = self.cache.write() {
-            cache.clear();
-        }
+        self.cache.clear();
     }
 }
 
@@ -236,7 +729,37 @@ impl Default for PermissionManager {
 impl Clone for PermissionManager {
     fn clone(&self) -> Self {
         Self {
+            backend: Arc::clone(&self.backend),
             cache: Arc::clone(&self.cache),
+            cache_policy: self.cache_policy.clone(),
+            scheduler: Arc::clone(&self.scheduler),
+            policy: Arc::clone(&self.policy),
+            caller: self.caller.clone(),
+            #[cfg(feature = "audit")]
+            audit: self.audit.clone(),
         }
     }
 }
+
+/// Fallback backend for platforms without a native implementation
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+struct UnsupportedHandler;
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+impl PermissionHandler for UnsupportedHandler {
+    fn name(&self) -> &'static str {
+        "unsupported"
+    }
+
+    fn check_permission(&self, _typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
+        Err(PermissionError::new(ErrorKind::NotSupportedOnPlatform))
+    }
+
+    fn request_permission(
+        &self,
+        _typ: PermissionType,
+        tx: tokio::sync::oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        tx.send(Err(PermissionError::new(ErrorKind::NotSupportedOnPlatform))).ok();
+    }
+}
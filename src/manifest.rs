@@ -0,0 +1,322 @@
+//! Declarative permission requirements and readiness evaluation
+//!
+//! A [`Manifest`] lists the features of an application and the permissions each
+//! one needs, marked required or optional with a reason. Evaluating it against a
+//! [`PermissionManager`] tells which features are usable, degraded or blocked,
+//! and which permissions are worth requesting next.
+//!
+//! Manifests can be built in code, parsed from TOML with the `toml` feature, or
+//! deserialized from JSON or any other format with the `serde` feature. Both
+//! formats use the same field names:
+//!
+//! ```toml
+//! [[features]]
+//! name = "voice_notes"
+//!
+//! [[features.permissions]]
+//! permission = "microphone"
+//! reason = "Record the note"
+//!
+//! [[features.permissions]]
+//! permission = "location"
+//! required = false
+//! reason = "Tag notes with where they were taken"
+//! ```
+//!
+//! `required` defaults to `true`.
+
+use std::collections::{HashMap, HashSet};
+
+use futures::future::join_all;
+
+use crate::manager::PermissionManager;
+use crate::types::{PermissionError, PermissionStatus, PermissionType};
+
+/// Permissions needed by each feature of an application
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Manifest {
+    /// Features in the order they should be reported
+    pub features: Vec<FeatureSpec>,
+}
+
+/// One feature and the permissions it needs
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeatureSpec {
+    /// Name of the feature, as shown to users or agents
+    pub name: String,
+    /// Permissions the feature uses
+    pub permissions: Vec<Requirement>,
+}
+
+/// A permission needed by a feature
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Requirement {
+    /// Permission needed
+    pub permission: PermissionType,
+    /// Whether the feature is unusable without it, rather than merely degraded
+    #[cfg_attr(feature = "serde", serde(default = "required_by_default"))]
+    pub required: bool,
+    /// Why the feature needs it
+    pub reason: String,
+}
+
+#[cfg(feature = "serde")]
+fn required_by_default() -> bool {
+    true
+}
+
+impl Manifest {
+    /// Empty manifest
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a feature
+    pub fn with_feature(mut self, feature: FeatureSpec) -> Self {
+        self.features.push(feature);
+        self
+    }
+
+    /// Every permission mentioned by the manifest, once each, in manifest order
+    pub fn permissions(&self) -> Vec<PermissionType> {
+        let mut seen = HashSet::new();
+        self.features
+            .iter()
+            .flat_map(|feature| &feature.permissions)
+            .map(|requirement| requirement.permission)
+            .filter(|typ| seen.insert(*typ))
+            .collect()
+    }
+
+    /// Check every permission in the manifest and report feature readiness
+    ///
+    /// Each permission is checked once through
+    /// [`PermissionManager::check_permission_async`], so cached statuses are
+    /// reused and nothing is prompted.
+    pub async fn evaluate(&self, manager: &PermissionManager) -> ReadinessReport {
+        let permissions = self.permissions();
+        let checks = permissions
+            .iter()
+            .map(|typ| manager.check_permission_async(*typ));
+        let statuses: HashMap<_, _> = permissions.iter().zip(join_all(checks).await).collect();
+
+        let features: Vec<FeatureReadiness> = self
+            .features
+            .iter()
+            .map(|feature| {
+                let requirements: Vec<RequirementState> = feature
+                    .permissions
+                    .iter()
+                    .map(|requirement| RequirementState {
+                        requirement: requirement.clone(),
+                        status: statuses[&requirement.permission].clone(),
+                    })
+                    .collect();
+                let readiness = if requirements
+                    .iter()
+                    .any(|state| state.requirement.required && !state.is_met())
+                {
+                    Readiness::Blocked
+                } else if requirements.iter().any(|state| !state.is_met()) {
+                    Readiness::Degraded
+                } else {
+                    Readiness::Usable
+                };
+                FeatureReadiness {
+                    name: feature.name.clone(),
+                    readiness,
+                    requirements,
+                }
+            })
+            .collect();
+
+        // Required permissions first, since they unblock features outright
+        let mut seen = HashSet::new();
+        let to_request = [true, false]
+            .into_iter()
+            .flat_map(|required| {
+                features
+                    .iter()
+                    .flat_map(|feature| &feature.requirements)
+                    .filter(move |state| state.requirement.required == required)
+            })
+            .filter(|state| state.is_requestable())
+            .map(|state| state.requirement.permission)
+            .filter(|typ| seen.insert(*typ))
+            .collect();
+
+        ReadinessReport {
+            features,
+            to_request,
+        }
+    }
+}
+
+impl FeatureSpec {
+    /// Feature with no permissions yet
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            permissions: Vec::new(),
+        }
+    }
+
+    /// Add a permission the feature cannot work without
+    pub fn with_required(mut self, permission: PermissionType, reason: impl Into<String>) -> Self {
+        self.permissions.push(Requirement {
+            permission,
+            required: true,
+            reason: reason.into(),
+        });
+        self
+    }
+
+    /// Add a permission the feature can work without, with reduced functionality
+    pub fn with_optional(mut self, permission: PermissionType, reason: impl Into<String>) -> Self {
+        self.permissions.push(Requirement {
+            permission,
+            required: false,
+            reason: reason.into(),
+        });
+        self
+    }
+}
+
+/// How usable a feature is with the current permissions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Readiness {
+    /// Every permission is granted
+    Usable,
+    /// Every required permission is granted, but some optional ones are not
+    Degraded,
+    /// A required permission is not granted
+    Blocked,
+}
+
+/// Outcome of checking one [`Requirement`]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RequirementState {
+    /// Requirement that was checked
+    pub requirement: Requirement,
+    /// Current status of the permission, or why it could not be checked
+    pub status: Result<PermissionStatus, PermissionError>,
+}
+
+impl RequirementState {
+    /// Whether the permission is granted or not gated on this platform
+    pub fn is_met(&self) -> bool {
+        matches!(
+            self.status,
+            Ok(PermissionStatus::Authorized | PermissionStatus::NotApplicable)
+        )
+    }
+
+    /// Whether requesting the permission could change its status
+    ///
+    /// Denied and restricted permissions can only be changed in system settings.
+    pub fn is_requestable(&self) -> bool {
+        matches!(
+            self.status,
+            Ok(PermissionStatus::NotDetermined
+                | PermissionStatus::PromptRequired
+                | PermissionStatus::Unknown)
+        )
+    }
+}
+
+/// Readiness of one feature
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FeatureReadiness {
+    /// Name of the feature
+    pub name: String,
+    /// Overall readiness
+    pub readiness: Readiness,
+    /// Every requirement with its current status, in manifest order
+    pub requirements: Vec<RequirementState>,
+}
+
+impl FeatureReadiness {
+    /// Requirements that are not met
+    pub fn unmet(&self) -> impl Iterator<Item = &RequirementState> {
+        self.requirements.iter().filter(|state| !state.is_met())
+    }
+}
+
+/// Result of [`Manifest::evaluate`]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ReadinessReport {
+    /// Readiness of every feature, in manifest order
+    pub features: Vec<FeatureReadiness>,
+    /// Permissions worth requesting, required ones first
+    pub to_request: Vec<PermissionType>,
+}
+
+impl ReadinessReport {
+    /// Readiness of the feature called `name`
+    pub fn feature(&self, name: &str) -> Option<&FeatureReadiness> {
+        self.features.iter().find(|feature| feature.name == name)
+    }
+
+    /// Whether every feature is fully usable
+    pub fn is_ready(&self) -> bool {
+        self.features
+            .iter()
+            .all(|feature| feature.readiness == Readiness::Usable)
+    }
+}
+
+#[cfg(feature = "toml")]
+mod toml {
+    use toml_edit::TableLike;
+
+    use super::{FeatureSpec, Manifest, Requirement};
+    use crate::toml::{invalid, parse, permission, string, tables};
+    use crate::types::PermissionError;
+
+    fn requirement(table: &dyn TableLike) -> Result<Requirement, PermissionError> {
+        let required = match table.get("required") {
+            Some(item) => item
+                .as_bool()
+                .ok_or_else(|| invalid("`required` must be a boolean"))?,
+            None => true,
+        };
+        Ok(Requirement {
+            permission: permission(string(table, "permission")?)?,
+            required,
+            reason: string(table, "reason")?.to_string(),
+        })
+    }
+
+    impl Manifest {
+        /// Parse a manifest from TOML
+        ///
+        /// Fails with [`ErrorKind::InvalidConfig`](crate::ErrorKind::InvalidConfig)
+        /// on malformed TOML, missing fields or unknown permission identifiers.
+        pub fn from_toml(source: &str) -> Result<Self, PermissionError> {
+            let document = parse(source, "manifest")?;
+            let Some(features) = document.as_table().get("features") else {
+                return Ok(Self::new());
+            };
+
+            let mut manifest = Self::new();
+            for table in tables(features, "features")? {
+                let mut feature = FeatureSpec::new(string(table, "name")?);
+                if let Some(permissions) = table.get("permissions") {
+                    for table in tables(permissions, "permissions")? {
+                        feature.permissions.push(requirement(table)?);
+                    }
+                }
+                manifest.features.push(feature);
+            }
+            Ok(manifest)
+        }
+    }
+}
@@ -9,7 +9,10 @@ pub mod accessibility;
 pub mod connectivity;
 pub mod productivity;
 
-use tokio::sync::oneshot;
+use std::time::Duration;
+
+use futures::StreamExt;
+use tokio::sync::mpsc;
 
 pub use accessibility::{
     check_accessibility, check_nearby_interaction, check_speech_recognition, request_accessibility,
@@ -19,4 +22,163 @@ pub use accessibility::{
 pub use connectivity::{check_bluetooth, check_wifi, request_bluetooth, request_wifi};
 pub use productivity::{check_calendar, check_contacts, request_calendar, request_contacts};
 
-use crate::types::{PermissionError, PermissionStatus};
+use crate::report::{self, Confidence};
+use crate::trace::Timed;
+use crate::types::{PermissionError, PermissionStatus, PermissionType};
+
+/// How long a single service probe may take before it counts as failed
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Call a method on a session-bus service; a method return means it is usable
+pub(crate) async fn probe_service(
+    destination: &str,
+    path: &str,
+    interface: &str,
+    method: &str,
+) -> Result<PermissionStatus, PermissionError> {
+    probe(false, destination, path, interface, method).await
+}
+
+/// Call a method on a system-bus service, such as BlueZ or NetworkManager
+pub(crate) async fn probe_system_service(
+    destination: &str,
+    path: &str,
+    interface: &str,
+    method: &str,
+) -> Result<PermissionStatus, PermissionError> {
+    probe(true, destination, path, interface, method).await
+}
+
+async fn probe(
+    system: bool,
+    destination: &str,
+    path: &str,
+    interface: &str,
+    method: &str,
+) -> Result<PermissionStatus, PermissionError> {
+    let timed = Timed::bus_call(destination, path, method);
+    let call = async {
+        let connection = if system {
+            zbus::Connection::system().await?
+        } else {
+            zbus::Connection::session().await?
+        };
+        connection
+            .call_method(Some(destination), path, Some(interface), method, &())
+            .await
+    };
+    let reply = timed
+        .instrument(tokio::time::timeout(PROBE_TIMEOUT, call))
+        .await;
+    let target = format!("{} {}.{}", destination, interface, method);
+    match &reply {
+        Ok(result) => report::record(Confidence::Heuristic, "D-Bus call", target, report::outcome(result)),
+        Err(e) => report::record(Confidence::Assumed, "D-Bus call", target, e),
+    }
+    let status = match reply {
+        Ok(Ok(_)) => Ok(PermissionStatus::Authorized),
+        Ok(Err(e)) => Err(PermissionError::from(e).with_probe(destination)),
+        Err(e) => Err(PermissionError::from(e)
+            .with_message(format!("{} timed out after {:?}", method, PROBE_TIMEOUT))
+            .with_probe(destination)),
+    };
+    timed.finish(&status);
+    status
+}
+
+/// Well-known bus names whose presence the `check_*` functions probe for `typ`
+pub fn service_names(typ: PermissionType) -> &'static [&'static str] {
+    match typ {
+        PermissionType::Bluetooth => &["org.bluez"],
+        PermissionType::WiFi => &["org.freedesktop.NetworkManager"],
+        PermissionType::Calendar | PermissionType::Reminders => {
+            &["org.gnome.evolution.dataserver.Calendar7"]
+        },
+        PermissionType::Contacts | PermissionType::AddressBook => {
+            &["org.gnome.evolution.dataserver.AddressBook10"]
+        },
+        PermissionType::SpeechRecognition => &["org.freedesktop.speech-dispatcher"],
+        PermissionType::Accessibility | PermissionType::AccessibilityMouse => &["org.a11y.Bus"],
+        _ => &[],
+    }
+}
+
+/// Forward `NameOwnerChanged` signals for the services behind `types` to `tx`
+///
+/// A service appearing or vanishing changes what its `check_*` function reports.
+/// Both the session and the system bus are watched, since Bluetooth and
+/// NetworkManager normally live on the latter. Runs until `tx` is closed; fails
+/// if the session bus cannot be subscribed to.
+pub async fn watch_service_owners(
+    types: Vec<PermissionType>,
+    tx: mpsc::UnboundedSender<PermissionType>,
+) -> zbus::Result<()> {
+    let session = watch_owners(zbus::Connection::session().await?, &types, &tx).await?;
+    match zbus::Connection::system().await {
+        Ok(system) => {
+            let system = watch_owners(system, &types, &tx);
+            // The system bus is optional; keep the session subscription if it fails
+            tokio::join!(session, async {
+                if let Ok(system) = system.await {
+                    system.await;
+                }
+            });
+        },
+        Err(_) => session.await,
+    }
+    Ok(())
+}
+
+/// Subscribe to `NameOwnerChanged` on `connection`, returning the forwarding loop
+async fn watch_owners(
+    connection: zbus::Connection,
+    types: &[PermissionType],
+    tx: &mpsc::UnboundedSender<PermissionType>,
+) -> zbus::Result<impl Future<Output = ()> + use<>> {
+    // One rule per service, so unrelated clients coming and going stay silent
+    let mut names: Vec<&'static str> = types
+        .iter()
+        .flat_map(|t| service_names(*t))
+        .copied()
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+    let mut streams = Vec::with_capacity(names.len());
+    for name in names {
+        let rule = zbus::MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .sender("org.freedesktop.DBus")?
+            .interface("org.freedesktop.DBus")?
+            .member("NameOwnerChanged")?
+            .arg(0, name)?
+            .build();
+        streams.push(zbus::MessageStream::for_match_rule(rule, &connection, None).await?);
+    }
+    let mut signals = futures::stream::select_all(streams);
+    let types = types.to_vec();
+    let tx = tx.clone();
+
+    Ok(async move {
+        loop {
+            let msg = tokio::select! {
+                _ = tx.closed() => return,
+                msg = signals.next() => msg,
+            };
+            let msg = match msg {
+                Some(Ok(msg)) => msg,
+                Some(Err(_)) => continue,
+                None => return,
+            };
+            // NameOwnerChanged(s name, s old_owner, s new_owner)
+            let Ok((name, _old, _new)) = msg.body().deserialize::<(String, String, String)>()
+            else {
+                continue;
+            };
+            for typ in &types {
+                if service_names(*typ).contains(&name.as_str()) {
+                    tx.send(*typ).ok();
+                }
+            }
+        }
+    })
+}
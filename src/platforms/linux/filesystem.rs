@@ -1,105 +1,225 @@
 //! Filesystem-based permission implementations
+//!
+//! The Desktop, Documents, Downloads, Pictures and Music folders are resolved like
+//! `xdg-user-dir` does, from `user-dirs.dirs` in `$XDG_CONFIG_HOME` (default
+//! `~/.config`), so localized (`~/Dokumente`) and relocated folders are checked
+//! where they really are. Folders the file does not configure, or every folder
+//! when there is no such file, default to `$HOME/<English name>` as in
+//! `xdg-user-dir`.
+
+use std::path::{Path, PathBuf};
 
 use tokio::sync::oneshot;
 
-use crate::types::{PermissionError, PermissionStatus, PermissionType};
+use crate::report::{self, Confidence};
+use crate::trace::Timed;
+use crate::types::{ErrorKind, PermissionError, PermissionStatus, PermissionType};
 
-pub fn check_photos() -> Result<PermissionStatus, PermissionError> {
-    let pictures_path = format!("{}/Pictures", std::env::var("HOME").unwrap_or_default());
-    match std::fs::read_dir(&pictures_path) {
-        Ok(_) => Ok(PermissionStatus::Authorized),
-        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Ok(PermissionStatus::Denied),
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(PermissionStatus::Denied),
-        Err(e) => Err(PermissionError::SystemError(format!(
-            "System operation failed: {}",
-            e
-        ))),
-    }
+/// Folder permissions with their `user-dirs.dirs` key and default folder name
+const USER_DIRS: &[(PermissionType, &str, &str)] = &[
+    (PermissionType::DesktopFolder, "XDG_DESKTOP_DIR", "Desktop"),
+    (PermissionType::DocumentsFolder, "XDG_DOCUMENTS_DIR", "Documents"),
+    (PermissionType::DownloadsFolder, "XDG_DOWNLOAD_DIR", "Downloads"),
+    (PermissionType::Photos, "XDG_PICTURES_DIR", "Pictures"),
+    (PermissionType::MediaLibrary, "XDG_MUSIC_DIR", "Music"),
+];
+
+/// Folder behind a folder permission, or `None` if it is not configured
+///
+/// A folder set to the home directory itself is disabled, as in
+/// `xdg-user-dirs`, and also resolves to `None`.
+pub fn user_dir(typ: PermissionType) -> Option<PathBuf> {
+    let home = dirs::home_dir();
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+    let contents = config.and_then(|dir| std::fs::read_to_string(dir.join("user-dirs.dirs")).ok());
+    resolve_user_dir(typ, contents.as_deref(), home.as_deref())
 }
 
-pub fn check_media_library() -> Result<PermissionStatus, PermissionError> {
-    let music_path = format!("{}/Music", std::env::var("HOME").unwrap_or_default());
-    match std::fs::read_dir(&music_path) {
-        Ok(_) => Ok(PermissionStatus::Authorized),
-        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Ok(PermissionStatus::Denied),
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(PermissionStatus::Denied),
-        Err(e) => Err(PermissionError::SystemError(format!(
-            "System operation failed: {}",
-            e
-        ))),
-    }
+/// Folder behind `typ` given the contents of `user-dirs.dirs`, if there is one
+fn resolve_user_dir(
+    typ: PermissionType,
+    contents: Option<&str>,
+    home: Option<&Path>,
+) -> Option<PathBuf> {
+    let &(_, key, default) = USER_DIRS.iter().find(|(dir, ..)| *dir == typ)?;
+    contents
+        .and_then(|contents| parse_user_dir(contents, key, home))
+        .or_else(|| home.map(|home| home.join(default)))
+        .filter(|dir| home != Some(dir.as_path()))
 }
 
-pub fn check_desktop_folder() -> Result<PermissionStatus, PermissionError> {
-    let desktop_path = format!("{}/Desktop", std::env::var("HOME").unwrap_or_default());
-    match std::fs::read_dir(&desktop_path) {
-        Ok(_) => Ok(PermissionStatus::Authorized),
-        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Ok(PermissionStatus::Denied),
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(PermissionStatus::Denied),
-        Err(e) => Err(PermissionError::SystemError(format!(
-            "System operation failed: {}",
-            e
-        ))),
+/// Value of `key` in the shell-style `user-dirs.dirs` format
+///
+/// Values are `"$HOME/relative"` or `"/absolute"`; anything else is ignored,
+/// as `xdg-user-dirs` does.
+fn parse_user_dir(contents: &str, key: &str, home: Option<&Path>) -> Option<PathBuf> {
+    let value = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .filter(|(name, _)| name.trim() == key)
+        .map(|(_, value)| value.trim())
+        .next_back()?;
+    let value = value.strip_prefix('"')?.strip_suffix('"')?;
+
+    // Undo shell escaping of `"`, `\`, `$` and backquotes
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        unescaped.push(if c == '\\' { chars.next()? } else { c });
     }
-}
 
-pub fn check_documents_folder() -> Result<PermissionStatus, PermissionError> {
-    let documents_path = format!("{}/Documents", std::env::var("HOME").unwrap_or_default());
-    match std::fs::read_dir(&documents_path) {
-        Ok(_) => Ok(PermissionStatus::Authorized),
-        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Ok(PermissionStatus::Denied),
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(PermissionStatus::Denied),
-        Err(e) => Err(PermissionError::SystemError(format!(
-            "System operation failed: {}",
-            e
-        ))),
+    if let Some(relative) = unescaped.strip_prefix("$HOME") {
+        Some(home?.join(relative.trim_start_matches('/')))
+    } else if unescaped.starts_with('/') {
+        Some(PathBuf::from(unescaped))
+    } else {
+        None
     }
 }
 
-pub fn check_downloads_folder() -> Result<PermissionStatus, PermissionError> {
-    let downloads_path = format!("{}/Downloads", std::env::var("HOME").unwrap_or_default());
-    match std::fs::read_dir(&downloads_path) {
+/// Probe the folder behind `typ` by listing it
+///
+/// A folder that is not configured or does not exist fails with
+/// [`ErrorKind::NotFound`], so it is not mistaken for one the user was denied.
+/// The resolved path is recorded as the report's path, as the probe target and
+/// on errors.
+async fn check_folder(typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
+    let Some(path) = user_dir(typ) else {
+        report::record(Confidence::Heuristic, "user-dirs", typ.as_str(), "not configured");
+        return Err(PermissionError::new(ErrorKind::NotFound)
+            .with_message(format!("no folder is configured for {}", typ))
+            .with_probe("user-dirs.dirs"));
+    };
+    report::record_path(&path);
+    let target = path.display().to_string();
+    let timed = Timed::local("read_dir", &target);
+    let listing = timed.instrument(tokio::fs::read_dir(&path)).await;
+    timed.finish_probe(&listing);
+    report::record(Confidence::Heuristic, "read_dir", &target, report::outcome(&listing));
+    match listing {
         Ok(_) => Ok(PermissionStatus::Authorized),
         Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Ok(PermissionStatus::Denied),
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(PermissionStatus::Denied),
-        Err(e) => Err(PermissionError::SystemError(format!(
-            "System operation failed: {}",
-            e
-        ))),
+        Err(e) => Err(PermissionError::from(e).with_probe(target)),
     }
 }
 
+pub async fn check_photos() -> Result<PermissionStatus, PermissionError> {
+    check_folder(PermissionType::Photos).await
+}
+
+pub async fn check_media_library() -> Result<PermissionStatus, PermissionError> {
+    check_folder(PermissionType::MediaLibrary).await
+}
+
+pub async fn check_desktop_folder() -> Result<PermissionStatus, PermissionError> {
+    check_folder(PermissionType::DesktopFolder).await
+}
+
+pub async fn check_documents_folder() -> Result<PermissionStatus, PermissionError> {
+    check_folder(PermissionType::DocumentsFolder).await
+}
+
+pub async fn check_downloads_folder() -> Result<PermissionStatus, PermissionError> {
+    check_folder(PermissionType::DownloadsFolder).await
+}
+
 pub fn request_photos(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
-    std::thread::spawn(move || {
-        let result = check_photos();
+    crate::runtime::spawn(async move {
+        let result = check_photos().await;
         tx.send(result).ok();
     });
 }
 
 pub fn request_media_library(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
-    std::thread::spawn(move || {
-        let result = check_media_library();
+    crate::runtime::spawn(async move {
+        let result = check_media_library().await;
         tx.send(result).ok();
     });
 }
 
 pub fn request_desktop_folder(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
-    std::thread::spawn(move || {
-        let result = check_desktop_folder();
+    crate::runtime::spawn(async move {
+        let result = check_desktop_folder().await;
         tx.send(result).ok();
     });
 }
 
 pub fn request_documents_folder(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
-    std::thread::spawn(move || {
-        let result = check_documents_folder();
+    crate::runtime::spawn(async move {
+        let result = check_documents_folder().await;
         tx.send(result).ok();
     });
 }
 
 pub fn request_downloads_folder(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
-    std::thread::spawn(move || {
-        let result = check_downloads_folder();
+    crate::runtime::spawn(async move {
+        let result = check_downloads_folder().await;
         tx.send(result).ok();
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER_DIRS_FILE: &str = r#"
+# This file is written by xdg-user-dirs-update
+XDG_DESKTOP_DIR="$HOME/Schreibtisch"
+XDG_DOCUMENTS_DIR="$HOME/Dokumente"
+XDG_DOWNLOAD_DIR="/data/downloads"
+XDG_MUSIC_DIR="$HOME/"
+XDG_PICTURES_DIR="$HOME/Bilder \"alt\""
+XDG_VIDEOS_DIR=relative/ignored
+"#;
+
+    #[test]
+    fn test_parse_user_dirs() {
+        let home = Path::new("/home/ana");
+        let parse = |key| parse_user_dir(USER_DIRS_FILE, key, Some(home));
+        assert_eq!(
+            parse("XDG_DOCUMENTS_DIR"),
+            Some(PathBuf::from("/home/ana/Dokumente"))
+        );
+        assert_eq!(
+            parse("XDG_DOWNLOAD_DIR"),
+            Some(PathBuf::from("/data/downloads"))
+        );
+        assert_eq!(parse("XDG_MUSIC_DIR"), Some(PathBuf::from("/home/ana")));
+        assert_eq!(
+            parse("XDG_PICTURES_DIR"),
+            Some(PathBuf::from("/home/ana/Bilder \"alt\""))
+        );
+        assert_eq!(parse("XDG_VIDEOS_DIR"), None);
+        assert_eq!(parse("XDG_TEMPLATES_DIR"), None);
+    }
+
+    #[test]
+    fn test_unconfigured_folders_default_to_english_names() {
+        let home = Some(Path::new("/home/ana"));
+        let resolve = |typ, contents| resolve_user_dir(typ, contents, home);
+        assert_eq!(
+            resolve(PermissionType::DocumentsFolder, None),
+            Some(PathBuf::from("/home/ana/Documents"))
+        );
+        assert_eq!(
+            resolve(PermissionType::DownloadsFolder, Some("XDG_DESKTOP_DIR=\"$HOME/\"\n")),
+            Some(PathBuf::from("/home/ana/Downloads"))
+        );
+        // Set to the home directory means disabled
+        assert_eq!(resolve(PermissionType::MediaLibrary, Some(USER_DIRS_FILE)), None);
+        assert_eq!(resolve_user_dir(PermissionType::DocumentsFolder, None, None), None);
+    }
+
+    #[test]
+    fn test_home_relative_needs_home() {
+        assert_eq!(
+            parse_user_dir(USER_DIRS_FILE, "XDG_DESKTOP_DIR", None),
+            None
+        );
+    }
+}
@@ -0,0 +1,359 @@
+//! Linux permission handler dispatching to portal, D-Bus, filesystem and system probes
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use tokio::sync::{mpsc, oneshot};
+
+use super::settings::{self, Desktop, Launcher, ProcessLauncher};
+use super::{
+    dbus_services, filesystem, notification_permissions, platform_specific, portal, remediation,
+    system,
+};
+use crate::remediation::Remediation;
+use crate::request::RequestOptions;
+use crate::traits::PermissionHandler;
+use crate::types::{PermissionError, PermissionStatus, PermissionType, Support};
+
+/// Linux-specific permission handler
+///
+/// `check_permission` never shows UI on any code path: portal-backed permissions are
+/// read from the portal PermissionStore, and every other probe only inspects D-Bus
+/// services, device nodes or the filesystem. Dialogs are reserved for
+/// `request_permission`, whose portal dialogs for location and screen capture
+/// are attached to the [`RequestOptions::parent_window`].
+///
+/// Every probe is async-native; `check_permission` waits for it on a private
+/// runtime, so the sync API works with or without a caller runtime of any flavor.
+///
+/// `watch_changes` reports PermissionStore edits for camera, microphone and
+/// location, and services appearing or vanishing for the D-Bus backed permissions.
+///
+/// `open_settings` launches the settings panel of the session's desktop; the
+/// desktop and the launcher can be replaced for tests.
+pub struct LinuxHandler {
+    desktop: Option<Desktop>,
+    launcher: Arc<dyn Launcher>,
+}
+
+impl Default for LinuxHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinuxHandler {
+    pub fn new() -> Self {
+        Self {
+            desktop: None,
+            launcher: Arc::new(ProcessLauncher),
+        }
+    }
+
+    /// Open settings for `desktop` instead of the one detected from the environment
+    pub fn with_desktop(mut self, desktop: Desktop) -> Self {
+        self.desktop = Some(desktop);
+        self
+    }
+
+    /// Start settings commands with `launcher` instead of spawning processes
+    pub fn with_launcher(mut self, launcher: Arc<dyn Launcher>) -> Self {
+        self.launcher = launcher;
+        self
+    }
+}
+
+impl PermissionHandler for LinuxHandler {
+    fn name(&self) -> &'static str {
+        "linux"
+    }
+
+    fn check_permission(&self, typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
+        crate::runtime::block_on(check(typ))
+    }
+
+    fn check_permission_async(
+        &self,
+        typ: PermissionType,
+    ) -> BoxFuture<'_, Result<PermissionStatus, PermissionError>> {
+        Box::pin(check(typ))
+    }
+
+    fn request_permission(
+        &self,
+        typ: PermissionType,
+        tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        self.request_permission_with(typ, &RequestOptions::default(), tx)
+    }
+
+    fn request_permission_with(
+        &self,
+        typ: PermissionType,
+        options: &RequestOptions,
+        tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        match typ {
+            // Portal-based permissions
+            PermissionType::Camera => portal::request_camera(tx),
+            PermissionType::Microphone => portal::request_microphone(tx),
+            PermissionType::Location => portal::request_location(options, tx),
+
+            // D-Bus service permissions
+            PermissionType::Bluetooth => dbus_services::request_bluetooth(tx),
+            PermissionType::WiFi => dbus_services::request_wifi(tx),
+            PermissionType::Accessibility | PermissionType::AccessibilityMouse => {
+                dbus_services::request_accessibility(tx)
+            },
+            PermissionType::Calendar | PermissionType::Reminders => {
+                dbus_services::request_calendar(tx)
+            },
+            PermissionType::Contacts | PermissionType::AddressBook => {
+                dbus_services::request_contacts(tx)
+            },
+            PermissionType::SpeechRecognition => dbus_services::request_speech_recognition(tx),
+            PermissionType::NearbyInteraction => dbus_services::request_nearby_interaction(tx),
+
+            // Filesystem-based permissions
+            PermissionType::Photos | PermissionType::PhotosAdd => filesystem::request_photos(tx),
+            PermissionType::MediaLibrary => filesystem::request_media_library(tx),
+            PermissionType::DesktopFolder => filesystem::request_desktop_folder(tx),
+            PermissionType::DocumentsFolder => filesystem::request_documents_folder(tx),
+            PermissionType::DownloadsFolder => filesystem::request_downloads_folder(tx),
+
+            // System-level permissions
+            PermissionType::FullDiskAccess | PermissionType::AdminFiles => {
+                system::request_admin_files(tx)
+            },
+            PermissionType::ScreenCapture | PermissionType::RemoteDesktop => {
+                system::request_screen_capture(options, tx)
+            },
+            PermissionType::InputMonitoring => system::request_input_monitoring(tx),
+            PermissionType::NetworkVolumes => system::request_network_volumes(tx),
+            PermissionType::RemovableVolumes => system::request_removable_volumes(tx),
+            PermissionType::Motion => system::request_motion(tx),
+
+            // Notification permission
+            PermissionType::Notification => notification_permissions::request_permission(tx),
+
+            // Platform-specific permissions
+            _ => platform_specific::request_general_linux_permission(typ, tx),
+        }
+    }
+
+    fn support(&self, typ: PermissionType) -> Support {
+        support(typ)
+    }
+
+    fn remediation(&self, typ: PermissionType) -> Remediation {
+        let desktop = self.desktop.clone().unwrap_or_else(Desktop::detect);
+        remediation::remediation(typ, &desktop)
+    }
+
+    fn open_settings(&self, typ: PermissionType) -> Result<(), PermissionError> {
+        let desktop = self.desktop.clone().unwrap_or_else(Desktop::detect);
+        settings::open_settings(&desktop, typ, self.launcher.as_ref())
+    }
+
+    fn requires_prompt(&self, typ: PermissionType) -> bool {
+        // Only portal requests show a dialog; everything else re-checks state
+        matches!(
+            typ,
+            PermissionType::Camera
+                | PermissionType::Location
+                | PermissionType::ScreenCapture
+                | PermissionType::RemoteDesktop
+        )
+    }
+
+    fn shows_prompt(&self, typ: PermissionType) -> BoxFuture<'_, bool> {
+        Box::pin(portal::shows_dialog(typ))
+    }
+
+    fn watch_changes(
+        &self,
+        types: &[PermissionType],
+        interval: Duration,
+        tx: mpsc::UnboundedSender<PermissionType>,
+    ) -> Vec<PermissionType> {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return Vec::new();
+        };
+
+        let store: Vec<PermissionType> = types
+            .iter()
+            .copied()
+            .filter(|t| portal::STORE_PERMISSIONS.contains(t))
+            .collect();
+        let services: Vec<PermissionType> = types
+            .iter()
+            .copied()
+            .filter(|t| !dbus_services::service_names(*t).is_empty())
+            .collect();
+
+        if !store.is_empty() {
+            let (types, tx) = (store.clone(), tx.clone());
+            handle.spawn(async move {
+                if portal::watch_permission_store(types.clone(), tx.clone())
+                    .await
+                    .is_err()
+                {
+                    poll_fallback(types, interval, tx).await;
+                }
+            });
+        }
+        if !services.is_empty() {
+            let (types, tx) = (services.clone(), tx);
+            handle.spawn(async move {
+                if dbus_services::watch_service_owners(types.clone(), tx.clone())
+                    .await
+                    .is_err()
+                {
+                    poll_fallback(types, interval, tx).await;
+                }
+            });
+        }
+
+        store.into_iter().chain(services).collect()
+    }
+}
+
+/// Dispatch a status check to the async probe for `typ`
+async fn check(typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
+    match typ {
+        // Portal-based permissions
+        PermissionType::Camera => portal::check_camera().await,
+        PermissionType::Microphone => portal::check_microphone().await,
+        PermissionType::Location => portal::check_location().await,
+
+        // D-Bus service permissions
+        PermissionType::Bluetooth => dbus_services::check_bluetooth().await,
+        PermissionType::WiFi => dbus_services::check_wifi().await,
+        PermissionType::Calendar | PermissionType::Reminders => {
+            dbus_services::check_calendar().await
+        },
+        PermissionType::Contacts | PermissionType::AddressBook => {
+            dbus_services::check_contacts().await
+        },
+        PermissionType::SpeechRecognition => dbus_services::check_speech_recognition().await,
+        PermissionType::Accessibility | PermissionType::AccessibilityMouse => {
+            dbus_services::check_accessibility().await
+        },
+        PermissionType::NearbyInteraction => dbus_services::check_nearby_interaction().await,
+
+        // Filesystem-based permissions
+        PermissionType::Photos | PermissionType::PhotosAdd => filesystem::check_photos().await,
+        PermissionType::MediaLibrary => filesystem::check_media_library().await,
+        PermissionType::DesktopFolder => filesystem::check_desktop_folder().await,
+        PermissionType::DocumentsFolder => filesystem::check_documents_folder().await,
+        PermissionType::DownloadsFolder => filesystem::check_downloads_folder().await,
+
+        // System-level permissions
+        PermissionType::FullDiskAccess | PermissionType::AdminFiles => {
+            system::check_admin_files().await
+        },
+        PermissionType::ScreenCapture | PermissionType::RemoteDesktop => {
+            system::check_screen_capture().await
+        },
+        PermissionType::InputMonitoring => system::check_input_monitoring().await,
+        PermissionType::NetworkVolumes => system::check_network_volumes().await,
+        PermissionType::RemovableVolumes => system::check_removable_volumes().await,
+        PermissionType::Motion => system::check_motion().await,
+
+        // Notification permission
+        PermissionType::Notification => notification_permissions::check_permission().await,
+
+        // Platform-specific permissions
+        PermissionType::AppleEvents | PermissionType::PostEvent => {
+            platform_specific::check_apple_events().await
+        },
+        PermissionType::All
+        | PermissionType::DeveloperTools
+        | PermissionType::FileProviderDomain
+        | PermissionType::FileProviderPresence
+        | PermissionType::UbiquitousFileProvider
+        | PermissionType::WillfulWrite => {
+            platform_specific::handle_general_linux_permission(typ).await
+        },
+
+        // iOS-specific permissions not available on Linux
+        PermissionType::Calls
+        | PermissionType::FaceID
+        | PermissionType::FocusStatus
+        | PermissionType::Siri => platform_specific::handle_ios_specific_permission(typ),
+    }
+}
+
+/// Support matrix for Linux
+///
+/// Only the permissions whose grants are read from the portal PermissionStore
+/// exist as such on Linux; the rest are derived from services, device nodes and
+/// the filesystem. Screen capture and remote desktop are requested through the
+/// portal, but their checks only open the framebuffer device.
+pub fn support(typ: PermissionType) -> Support {
+    let via = match typ {
+        PermissionType::Camera | PermissionType::Microphone | PermissionType::Location => {
+            return Support::Native;
+        },
+
+        PermissionType::ScreenCapture | PermissionType::RemoteDesktop => "framebuffer device",
+
+        PermissionType::Bluetooth | PermissionType::NearbyInteraction => "BlueZ service",
+        PermissionType::WiFi => "NetworkManager service",
+        PermissionType::Calendar | PermissionType::Reminders => "Evolution calendar service",
+        PermissionType::Contacts | PermissionType::AddressBook => "Evolution address book service",
+        PermissionType::SpeechRecognition => "speech-dispatcher service",
+        PermissionType::Accessibility | PermissionType::AccessibilityMouse => {
+            "AT-SPI accessibility bus"
+        },
+        PermissionType::Notification => "notification service",
+        PermissionType::AppleEvents | PermissionType::PostEvent => "D-Bus session bus",
+
+        PermissionType::Photos
+        | PermissionType::PhotosAdd
+        | PermissionType::MediaLibrary
+        | PermissionType::DesktopFolder
+        | PermissionType::DocumentsFolder
+        | PermissionType::DownloadsFolder => "user folder access",
+        PermissionType::FullDiskAccess | PermissionType::AdminFiles => "effective user id",
+        PermissionType::InputMonitoring => "/dev/input access",
+        PermissionType::NetworkVolumes => "/mnt access",
+        PermissionType::RemovableVolumes => "/media access",
+        PermissionType::Motion => "/sys/class/input access",
+
+        PermissionType::All
+        | PermissionType::DeveloperTools
+        | PermissionType::FileProviderDomain
+        | PermissionType::FileProviderPresence
+        | PermissionType::UbiquitousFileProvider
+        | PermissionType::WillfulWrite
+        | PermissionType::Calls
+        | PermissionType::FaceID
+        | PermissionType::FocusStatus
+        | PermissionType::Siri => return Support::NotApplicable,
+    };
+    Support::Emulated { via }
+}
+
+/// Signal `types` every `interval` when the bus cannot be subscribed to
+async fn poll_fallback(
+    types: Vec<PermissionType>,
+    interval: Duration,
+    tx: mpsc::UnboundedSender<PermissionType>,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.tick().await;
+    loop {
+        tokio::select! {
+            _ = tx.closed() => return,
+            _ = ticker.tick() => {
+                for typ in &types {
+                    if tx.send(*typ).is_err() {
+                        return;
+                    }
+                }
+            },
+        }
+    }
+}
@@ -1,63 +1,26 @@
 //! Linux notification permission handling using D-Bus
 
+use crate::report::{self, Confidence};
+use crate::trace::Timed;
 use crate::types::{PermissionError, PermissionStatus};
 use tokio::sync::oneshot;
-use std::sync::OnceLock;
 
-// Global cached runtime - created once on first use, reused forever
-// Eliminates expensive thread creation/destruction overhead (100ms + 150ms per call)
-static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
-
-/// Get or create the cached tokio runtime for D-Bus operations
-/// 
-/// The runtime is created once on first call and reused for all subsequent calls.
-/// It lives for the entire program lifetime to avoid the expensive overhead of
-/// spawning and joining worker threads (8 threads × ~20ms join time = 160ms overhead).
-/// 
-/// # Panics
-/// 
-/// Panics if runtime creation fails, which indicates a fundamental system problem
-/// that would prevent all permission checking from working anyway.
-fn get_or_create_runtime() -> &'static tokio::runtime::Runtime {
-    RUNTIME.get_or_init(|| {
-        tokio::runtime::Runtime::new()
-            .expect("Failed to create tokio runtime for Linux notification permissions")
-    })
-}
+/// Well-known name of the desktop notification service
+const NOTIFICATIONS: &str = "org.freedesktop.Notifications";
 
 /// Check if D-Bus notification service is available
-pub fn check_permission() -> Result<PermissionStatus, PermissionError> {
-    #[cfg(target_os = "linux")]
-    {
-        // Check if we can connect to D-Bus session bus and if notification service exists
-        let rt = tokio::runtime::Handle::try_current();
-
-        let result = match rt {
-            Ok(handle) => tokio::task::block_in_place(|| {
-                handle.block_on(async { check_dbus_notification_service().await })
-            }),
-            Err(_) => {
-                // No tokio runtime available - use cached runtime
-                // First call: creates runtime (~100ms), caches it
-                // Subsequent calls: reuses cached runtime (zero overhead)
-                get_or_create_runtime()
-                    .block_on(async { check_dbus_notification_service().await })
-            }
-        };
-
-        result
-    }
-
-    #[cfg(not(target_os = "linux"))]
-    Ok(PermissionStatus::Authorized)
+pub async fn check_permission() -> Result<PermissionStatus, PermissionError> {
+    let timed = Timed::bus_call("org.freedesktop.DBus", "/org/freedesktop/DBus", "ListNames");
+    let status = timed.instrument(check_dbus_notification_service()).await;
+    timed.finish(&status);
+    status
 }
 
-#[cfg(target_os = "linux")]
 async fn check_dbus_notification_service() -> Result<PermissionStatus, PermissionError> {
     // Try to connect to D-Bus session bus
     let connection = zbus::Connection::session()
         .await
-        .map_err(|e| PermissionError::SystemError(format!("D-Bus connection failed: {}", e)))?;
+        .map_err(|e| PermissionError::from(e).with_message("D-Bus connection failed"))?;
 
     // Check if org.freedesktop.Notifications service is available
     let reply = connection
@@ -76,16 +39,19 @@ async fn check_dbus_notification_service() -> Result<PermissionStatus, Permissio
                 .body()
                 .deserialize()
                 .map_err(|e| {
-                    PermissionError::SystemError(format!(
-                        "Failed to deserialize D-Bus ListNames response: {}",
-                        e
-                    ))
+                    PermissionError::from(e)
+                        .with_message("Failed to deserialize D-Bus ListNames response")
+                        .with_probe("org.freedesktop.DBus")
                 })?;
 
-            if names
-                .iter()
-                .any(|n| n == "org.freedesktop.Notifications")
-            {
+            let running = names.iter().any(|n| n == NOTIFICATIONS);
+            report::record(
+                Confidence::Heuristic,
+                "D-Bus ListNames",
+                NOTIFICATIONS,
+                if running { "running" } else { "not running" },
+            );
+            if running {
                 Ok(PermissionStatus::Authorized)
             } else {
                 // Service not running but might be activatable - check activatable names
@@ -105,49 +71,40 @@ async fn check_dbus_notification_service() -> Result<PermissionStatus, Permissio
                             .body()
                             .deserialize()
                             .map_err(|e| {
-                                PermissionError::SystemError(format!(
-                                    "Failed to deserialize D-Bus ListActivatableNames response: {}",
-                                    e
-                                ))
+                                PermissionError::from(e)
+                                    .with_message("Failed to deserialize D-Bus ListActivatableNames response")
+                                    .with_probe("org.freedesktop.DBus")
                             })?;
-                        if activatable
-                            .iter()
-                            .any(|n| n == "org.freedesktop.Notifications")
-                        {
+                        let activatable = activatable.iter().any(|n| n == NOTIFICATIONS);
+                        report::record(
+                            Confidence::Heuristic,
+                            "D-Bus ListActivatableNames",
+                            NOTIFICATIONS,
+                            if activatable { "activatable" } else { "not activatable" },
+                        );
+                        if activatable {
                             Ok(PermissionStatus::Authorized)
                         } else {
                             Ok(PermissionStatus::Denied)
                         }
                     }
-                    Err(e) => Err(PermissionError::SystemError(format!(
-                        "D-Bus ListActivatableNames failed: {}. Cannot verify notification service availability.",
-                        e
-                    ))),
+                    Err(e) => Err(PermissionError::from(e)
+                        .with_message("D-Bus ListActivatableNames failed. Cannot verify notification service availability.")
+                        .with_probe("org.freedesktop.DBus")),
                 }
             }
         }
-        Err(_) => Ok(PermissionStatus::Denied),
+        Err(e) => {
+            report::record(Confidence::Assumed, "D-Bus ListNames", NOTIFICATIONS, e);
+            Ok(PermissionStatus::Denied)
+        },
     }
 }
 
 /// Request notification permission (Linux doesn't require user permission)
 pub fn request_permission(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
-    #[cfg(target_os = "linux")]
-    {
-        // Spawn async task in existing runtime (zero overhead)
-        // This avoids creating a new runtime or using block_in_place
-        tokio::spawn(async move {
-            // Directly call the async D-Bus check function
-            let result = check_dbus_notification_service().await;
-            
-            // Send result back through the oneshot channel
-            tx.send(result).ok();
-        });
-    }
-    
-    #[cfg(not(target_os = "linux"))]
-    {
-        // Non-Linux platforms: notifications always authorized
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
-    }
+    crate::runtime::spawn(async move {
+        let result = check_dbus_notification_service().await;
+        tx.send(result).ok();
+    });
 }
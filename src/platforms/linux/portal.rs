@@ -1,312 +1,557 @@
 //! Portal-based permission implementations for Camera, Microphone, and Location
+//!
+//! Checks never talk to the portal frontend: they read the grants recorded in the
+//! xdg-desktop-portal PermissionStore, so `check_*` can never open a dialog. Only the
+//! `request_*` functions go through `org.freedesktop.portal.*` and may prompt the user.
 
-use tokio::sync::oneshot;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, Ordering};
 
-#[cfg(target_os = "linux")]
-use {
-    ashpd::Request, ashpd::desktop::camera::Camera, ashpd::desktop::location::Location,
-    ashpd::desktop::microphone::Microphone,
-};
+use futures::StreamExt;
+use tokio::sync::{mpsc, oneshot};
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
 
-use crate::types::{PermissionError, PermissionStatus, PermissionType};
+use crate::report::{self, Confidence};
+use crate::request::{LocationAccuracy, RequestOptions};
+use crate::trace::Timed;
+use crate::types::{ErrorKind, PermissionError, PermissionStatus, PermissionType};
 
-pub fn check_camera() -> Result<PermissionStatus, PermissionError> {
-    #[cfg(target_os = "linux")]
+/// Well-known name, object path and interface of the portal PermissionStore
+const PERMISSION_STORE: &str = "org.freedesktop.impl.portal.PermissionStore";
+const PERMISSION_STORE_PATH: &str = "/org/freedesktop/impl/portal/PermissionStore";
+
+/// D-Bus error returned by `Lookup` when the table or entry does not exist
+const NOT_FOUND_ERROR: &str = "org.freedesktop.portal.Error.NotFound";
+
+/// PermissionStore table and entry id holding camera/microphone grants
+const DEVICES_TABLE: &str = "devices";
+/// PermissionStore table and entry id holding location grants
+const LOCATION_TABLE: &str = "location";
+
+/// Application id under which the portal records our grants
+///
+/// Priority order:
+/// 1. Environment variable `KODEGEN_LINUX_APP_ID`
+/// 2. `FLATPAK_ID` set by the Flatpak runtime
+/// 3. `name=` in the `[Application]` group of `/.flatpak-info`
+/// 4. The empty id the portal uses for unsandboxed host applications
+pub fn app_id() -> String {
+    if let Ok(app_id) = std::env::var("KODEGEN_LINUX_APP_ID")
+        && !app_id.is_empty()
     {
-        tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(async {
-                match Camera::request().await {
-                    Ok(request) => {
-                        match request.response().await {
-                            Ok(granted) => {
-                                if granted {
-                                    Ok(PermissionStatus::Authorized)
-                                } else {
-                                    Ok(PermissionStatus::Denied)
-                                }
-                            },
-                            Err(_) => {
-                                // Fallback to device file access if portal fails
-                                match std::fs::File::open("/dev/video0") {
-                                    Ok(_) => Ok(PermissionStatus::Authorized),
-                                    Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
-                                        Ok(PermissionStatus::Denied)
-                                    },
-                                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                                        Ok(PermissionStatus::Denied)
-                                    },
-                                    Err(e) => Err(PermissionError::SystemError(format!(
-                                        "Portal method call failed: {}",
-                                        e
-                                    ))),
-                                }
-                            },
-                        }
-                    },
-                    Err(_) => {
-                        // Fallback to device file access if portal unavailable
-                        match std::fs::File::open("/dev/video0") {
-                            Ok(_) => Ok(PermissionStatus::Authorized),
-                            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
-                                Ok(PermissionStatus::Denied)
-                            },
-                            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                                Ok(PermissionStatus::Denied)
-                            },
-                            Err(e) => Err(PermissionError::SystemError(format!(
-                                "Video device access failed: {}",
-                                e
-                            ))),
-                        }
-                    },
-                }
-            })
-        })
+        return app_id;
     }
-    #[cfg(not(target_os = "linux"))]
-    Ok(PermissionStatus::Authorized)
-}
 
-pub fn check_microphone() -> Result<PermissionStatus, PermissionError> {
-    #[cfg(target_os = "linux")]
+    if let Ok(app_id) = std::env::var("FLATPAK_ID")
+        && !app_id.is_empty()
     {
-        tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(async {
-                match Microphone::request().await {
-                    Ok(request) => {
-                        match request.response().await {
-                            Ok(granted) => {
-                                if granted {
-                                    Ok(PermissionStatus::Authorized)
-                                } else {
-                                    Ok(PermissionStatus::Denied)
-                                }
-                            },
-                            Err(_) => {
-                                // Fallback to device file access if portal fails
-                                match std::fs::File::open("/dev/snd/controlC0") {
-                                    Ok(_) => Ok(PermissionStatus::Authorized),
-                                    Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
-                                        Ok(PermissionStatus::Denied)
-                                    },
-                                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                                        Ok(PermissionStatus::Denied)
-                                    },
-                                    Err(e) => Err(PermissionError::SystemError(format!(
-                                        "System operation failed: {}",
-                                        e
-                                    ))),
-                                }
-                            },
-                        }
-                    },
-                    Err(_) => {
-                        // Fallback to device file access if portal unavailable
-                        match std::fs::File::open("/dev/snd/controlC0") {
-                            Ok(_) => Ok(PermissionStatus::Authorized),
-                            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
-                                Ok(PermissionStatus::Denied)
-                            },
-                            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                                Ok(PermissionStatus::Denied)
-                            },
-                            Err(e) => Err(PermissionError::SystemError(format!(
-                                "System operation failed: {}",
-                                e
-                            ))),
-                        }
-                    },
-                }
-            })
-        })
+        return app_id;
     }
-    #[cfg(not(target_os = "linux"))]
-    Ok(PermissionStatus::Authorized)
-}
 
-pub fn check_location() -> Result<PermissionStatus, PermissionError> {
-    #[cfg(target_os = "linux")]
-    {
-        use std::time::Duration;
-
-        use dbus::Message;
-        use dbus::blocking::Connection;
-
-        tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(async {
-            match Location::request().await {
-                Ok(request) => {
-                    match request.response().await {
-                        Ok(granted) => {
-                            if granted {
-                                Ok(PermissionStatus::Authorized)
-                            } else {
-                                Ok(PermissionStatus::Denied)
-                            }
-                        },
-                        Err(_) => {
-                            // Fallback to D-Bus GeoClue2 check if portal fails
-                            match Connection::new_session() {
-                                Ok(conn) => {
-                                    let msg = match Message::new_method_call(
-                                        "org.freedesktop.GeoClue2",
-                                        "/org/freedesktop/GeoClue2/Manager",
-                                        "org.freedesktop.GeoClue2.Manager",
-                                        "GetClient",
-                                    ) {
-                                        Ok(msg) => msg,
-                                        Err(e) => {
-                                            return Err(PermissionError::SystemError(format!(
-                                                "D-Bus message creation failed for GeoClue2 \
-                                                 GetClient: {}",
-                                                e
-                                            )));
-                                        },
-                                    };
-                                    match conn
-                                        .send_with_reply_and_block(msg, Duration::from_secs(2))
-                                    {
-                                        Ok(reply) => {
-                                            if reply.msg_type() == dbus::MessageType::MethodReturn {
-                                                Ok(PermissionStatus::Authorized)
-                                            } else {
-                                                Ok(PermissionStatus::Denied)
-                                            }
-                                        },
-                                        Err(e) => Err(PermissionError::SystemError(format!(
-                                            "System operation failed: {}",
-                                            e
-                                        ))),
-                                    }
-                                },
-                                Err(e) => Err(PermissionError::SystemError(format!(
-                                    "System operation failed: {}",
-                                    e
-                                ))),
-                            }
-                        },
-                    }
-                },
-                Err(_) => {
-                    // Fallback to D-Bus GeoClue2 check if portal unavailable
-                    match Connection::new_session() {
-                        Ok(conn) => {
-                            let msg = match Message::new_method_call(
-                                "org.freedesktop.GeoClue2",
-                                "/org/freedesktop/GeoClue2/Manager",
-                                "org.freedesktop.GeoClue2.Manager",
-                                "GetClient",
-                            ) {
-                                Ok(msg) => msg,
-                                Err(e) => {
-                                    return Err(PermissionError::SystemError(format!(
-                                        "D-Bus message creation failed for GeoClue2 GetClient: {}",
-                                        e
-                                    )));
-                                },
-                            };
-                            match conn.send_with_reply_and_block(msg, Duration::from_secs(2)) {
-                                Ok(reply) => {
-                                    if reply.msg_type() == dbus::MessageType::MethodReturn {
-                                        Ok(PermissionStatus::Authorized)
-                                    } else {
-                                        Ok(PermissionStatus::Denied)
-                                    }
-                                },
-                                Err(e) => Err(PermissionError::SystemError(format!(
-                                    "System operation failed: {}",
-                                    e
-                                ))),
-                            }
-                        },
-                        Err(e) => Err(PermissionError::SystemError(format!(
-                            "System operation failed: {}",
-                            e
-                        ))),
-                    }
-                },
+    if let Ok(info) = std::fs::read_to_string("/.flatpak-info") {
+        let mut in_application = false;
+        for line in info.lines().map(str::trim) {
+            if line.starts_with('[') {
+                in_application = line == "[Application]";
+            } else if in_application && let Some(name) = line.strip_prefix("name=") {
+                return name.to_string();
             }
-            })
-        })
+        }
     }
-    #[cfg(not(target_os = "linux"))]
-    Ok(PermissionStatus::Authorized)
+
+    String::new()
 }
 
-pub fn request_camera(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
-    #[cfg(target_os = "linux")]
-    {
-        tokio::spawn(async move {
-            let result = match Camera::request().await {
-                Ok(request) => match request.response().await {
-                    Ok(granted) => {
-                        if granted {
-                            Ok(PermissionStatus::Authorized)
-                        } else {
-                            Ok(PermissionStatus::Denied)
-                        }
-                    },
-                    Err(e) => Err(PermissionError::SystemError(e.to_string())),
-                },
-                Err(e) => Err(PermissionError::SystemError(e.to_string())),
-            };
-            tx.send(result).ok();
-        });
+/// Look up the permissions recorded for our app id in a PermissionStore table
+///
+/// Returns `Ok(None)` when the table, the entry or our app's row does not exist,
+/// and `Err` when the PermissionStore itself cannot be reached.
+async fn lookup_permission(table: &str, id: &str) -> zbus::Result<Option<Vec<String>>> {
+    let timed = Timed::bus_call(PERMISSION_STORE, PERMISSION_STORE_PATH, "Lookup");
+    let lookup = timed.instrument(async {
+        let connection = zbus::Connection::session().await?;
+        let reply = connection
+            .call_method(
+                Some(PERMISSION_STORE),
+                PERMISSION_STORE_PATH,
+                Some(PERMISSION_STORE),
+                "Lookup",
+                &(table, id),
+            )
+            .await;
+
+        match reply {
+            Ok(msg) => {
+                let (mut permissions, _data): (
+                    HashMap<String, Vec<String>>,
+                    zbus::zvariant::OwnedValue,
+                ) = msg.body().deserialize()?;
+                Ok(permissions.remove(&app_id()))
+            },
+            Err(zbus::Error::MethodError(name, _, _)) if name.as_str() == NOT_FOUND_ERROR => {
+                Ok(None)
+            },
+            Err(e) => Err(e),
+        }
+    });
+    let lookup = lookup.await;
+    timed.finish_probe(&lookup);
+    lookup
+}
+
+/// Record a PermissionStore lookup; only a reachable store is authoritative
+fn record_lookup(table: &str, id: &str, lookup: &zbus::Result<Option<Vec<String>>>) {
+    let target = format!("{}/{}", table, id);
+    match lookup {
+        Ok(Some(permissions)) => {
+            report::record(Confidence::Verified, "PermissionStore.Lookup", target, permissions.join(","))
+        },
+        Ok(None) => report::record(Confidence::Verified, "PermissionStore.Lookup", target, "no entry"),
+        Err(e) => report::record(Confidence::Assumed, "PermissionStore.Lookup", target, e),
     }
-    #[cfg(not(target_os = "linux"))]
-    {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+}
+
+/// Map a `devices` table entry (`["yes"]`, `["no"]` or `["ask"]`) to a status
+fn device_status(permissions: &[String]) -> PermissionStatus {
+    match permissions.first().map(String::as_str) {
+        Some("yes") => PermissionStatus::Authorized,
+        Some("no") => PermissionStatus::Denied,
+        _ => PermissionStatus::NotDetermined,
     }
 }
 
-pub fn request_microphone(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
-    #[cfg(target_os = "linux")]
-    {
-        tokio::spawn(async move {
-            let result = match Microphone::request().await {
-                Ok(request) => match request.response().await {
-                    Ok(granted) => {
-                        if granted {
-                            Ok(PermissionStatus::Authorized)
-                        } else {
-                            Ok(PermissionStatus::Denied)
-                        }
-                    },
-                    Err(e) => Err(PermissionError::SystemError(e.to_string())),
-                },
-                Err(e) => Err(PermissionError::SystemError(e.to_string())),
-            };
-            tx.send(result).ok();
-        });
+/// Map a `location` table entry (`[accuracy, timestamp]`) to a status
+fn location_status(permissions: &[String]) -> PermissionStatus {
+    match permissions.first().map(String::as_str) {
+        Some("NONE") => PermissionStatus::Denied,
+        Some(_) => PermissionStatus::Authorized,
+        None => PermissionStatus::NotDetermined,
     }
-    #[cfg(not(target_os = "linux"))]
-    {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+}
+
+/// Probe a device node by opening it
+///
+/// Also the fallback used when no PermissionStore is reachable.
+pub(super) async fn check_device_node(path: &str) -> Result<PermissionStatus, PermissionError> {
+    let timed = Timed::local("open", path);
+    let opened = timed.instrument(tokio::fs::File::open(path)).await;
+    timed.finish_probe(&opened);
+    report::record(Confidence::Heuristic, "open", path, report::outcome(&opened));
+    match opened {
+        Ok(_) => Ok(PermissionStatus::Authorized),
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Ok(PermissionStatus::Denied),
+        // Including no such device, which is not a denial
+        Err(e) => Err(PermissionError::from(e)
+            .with_message("Device access failed")
+            .with_probe(path)),
     }
 }
 
-pub fn request_location(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
-    #[cfg(target_os = "linux")]
-    {
-        tokio::spawn(async move {
-            let result = match Location::request().await {
-                Ok(request) => match request.response().await {
-                    Ok(granted) => {
-                        if granted {
-                            Ok(PermissionStatus::Authorized)
-                        } else {
-                            Ok(PermissionStatus::Denied)
-                        }
-                    },
-                    Err(e) => Err(PermissionError::SystemError(e.to_string())),
-                },
-                Err(e) => Err(PermissionError::SystemError(e.to_string())),
-            };
+async fn check_device(device: &str, node: &str) -> Result<PermissionStatus, PermissionError> {
+    let lookup = lookup_permission(DEVICES_TABLE, device).await;
+    record_lookup(DEVICES_TABLE, device, &lookup);
+    match lookup {
+        Ok(Some(permissions)) => Ok(device_status(&permissions)),
+        Ok(None) => Ok(PermissionStatus::NotDetermined),
+        Err(_) => check_device_node(node).await,
+    }
+}
+
+pub async fn check_camera() -> Result<PermissionStatus, PermissionError> {
+    check_device("camera", "/dev/video0").await
+}
+
+pub async fn check_microphone() -> Result<PermissionStatus, PermissionError> {
+    check_device("microphone", "/dev/snd/controlC0").await
+}
+
+pub async fn check_location() -> Result<PermissionStatus, PermissionError> {
+    let lookup = lookup_permission(LOCATION_TABLE, LOCATION_TABLE).await;
+    record_lookup(LOCATION_TABLE, LOCATION_TABLE, &lookup);
+    match lookup {
+        Ok(Some(permissions)) => Ok(location_status(&permissions)),
+        Ok(None) => Ok(PermissionStatus::NotDetermined),
+        // Without a PermissionStore there is no record of a location grant
+        Err(_) => Ok(PermissionStatus::NotDetermined),
+    }
+}
+
+/// Whether the portal asks the user about `typ`
+///
+/// The Camera and Location portals answer from the PermissionStore once a
+/// decision is recorded there; the ScreenCast portal always asks.
+pub async fn shows_dialog(typ: PermissionType) -> bool {
+    let recorded = match typ {
+        PermissionType::Camera => lookup_permission(DEVICES_TABLE, "camera")
+            .await
+            .map(|entry| entry.map(|permissions| device_status(&permissions))),
+        PermissionType::Location => lookup_permission(LOCATION_TABLE, LOCATION_TABLE)
+            .await
+            .map(|entry| entry.map(|permissions| location_status(&permissions))),
+        _ => return true,
+    };
+    !matches!(recorded, Ok(Some(status)) if status != PermissionStatus::NotDetermined)
+}
+
+/// Permissions recorded in the PermissionStore and reported by [`watch_permission_store`]
+pub const STORE_PERMISSIONS: &[PermissionType] = &[
+    PermissionType::Camera,
+    PermissionType::Microphone,
+    PermissionType::Location,
+];
+
+/// Permission recorded under a PermissionStore table and entry id
+fn store_permission(table: &str, id: &str) -> Option<PermissionType> {
+    match (table, id) {
+        (DEVICES_TABLE, "camera") => Some(PermissionType::Camera),
+        (DEVICES_TABLE, "microphone") => Some(PermissionType::Microphone),
+        (LOCATION_TABLE, LOCATION_TABLE) => Some(PermissionType::Location),
+        _ => None,
+    }
+}
+
+/// Forward PermissionStore `Changed` signals for `types` to `tx`
+///
+/// The store emits `Changed` whenever an entry is edited, e.g. by GNOME Settings
+/// or `flatpak permission-set`. Runs until `tx` is closed or the bus connection
+/// ends; fails if the session bus cannot be subscribed to.
+pub async fn watch_permission_store(
+    types: Vec<PermissionType>,
+    tx: mpsc::UnboundedSender<PermissionType>,
+) -> zbus::Result<()> {
+    let connection = zbus::Connection::session().await?;
+    let rule = zbus::MatchRule::builder()
+        .msg_type(zbus::message::Type::Signal)
+        .sender(PERMISSION_STORE)?
+        .path(PERMISSION_STORE_PATH)?
+        .interface(PERMISSION_STORE)?
+        .member("Changed")?
+        .build();
+    let mut signals = zbus::MessageStream::for_match_rule(rule, &connection, None).await?;
+
+    loop {
+        let msg = tokio::select! {
+            _ = tx.closed() => return Ok(()),
+            msg = signals.next() => msg,
+        };
+        let Some(msg) = msg else {
+            return Ok(());
+        };
+        // Changed(s table, s id, b deleted, v data, a{sas} permissions)
+        let (table, id, _deleted, _data, _permissions): (
+            String,
+            String,
+            bool,
+            zbus::zvariant::OwnedValue,
+            HashMap<String, Vec<String>>,
+        ) = msg?.body().deserialize()?;
+        if let Some(typ) = store_permission(&table, &id)
+            && types.contains(&typ)
+        {
+            tx.send(typ).ok();
+        }
+    }
+}
+
+/// Well-known name, object path and request interface of the portal frontend
+pub(crate) const PORTAL_DESKTOP: &str = "org.freedesktop.portal.Desktop";
+pub(crate) const PORTAL_DESKTOP_PATH: &str = "/org/freedesktop/portal/desktop";
+const PORTAL_REQUEST: &str = "org.freedesktop.portal.Request";
+
+/// Outcome of a portal request
+pub(crate) enum PortalResponse {
+    /// The user granted the request
+    Granted,
+    /// The user dismissed the dialog without answering
+    Dismissed,
+    /// The portal refused the request
+    Refused,
+    /// The caller stopped waiting and the request was closed
+    Closed,
+}
+
+/// Fresh `handle_token` for a portal request or session
+pub(crate) fn handle_token() -> String {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    format!(
+        "kodegen_{}_{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// Object path the portal will use for the request created with `token`
+fn request_path(connection: &zbus::Connection, token: &str) -> zbus::Result<String> {
+    handle_path(connection, "request", token)
+}
+
+/// Object path the portal will use for the session created with `token`
+pub(crate) fn session_path(
+    connection: &zbus::Connection,
+    token: &str,
+) -> zbus::Result<OwnedObjectPath> {
+    Ok(OwnedObjectPath::try_from(handle_path(connection, "session", token)?)?)
+}
+
+fn handle_path(connection: &zbus::Connection, kind: &str, token: &str) -> zbus::Result<String> {
+    let sender = connection
+        .unique_name()
+        .ok_or_else(|| zbus::Error::Failure("connection has no unique name".into()))?;
+    let sender = sender.trim_start_matches(':').replace('.', "_");
+    Ok(format!("{PORTAL_DESKTOP_PATH}/{kind}/{sender}/{token}"))
+}
+
+/// `parent_window` argument for portal methods; empty leaves the dialog unparented
+pub(crate) fn parent_window(options: &RequestOptions) -> String {
+    options
+        .parent_window
+        .as_ref()
+        .map(ToString::to_string)
+        .unwrap_or_default()
+}
+
+/// Options of the Location portal's `CreateSession`
+fn location_session_options<'a>(
+    token: &'a str,
+    options: &RequestOptions,
+) -> HashMap<&'static str, Value<'a>> {
+    let mut session_options = HashMap::from([("session_handle_token", Value::from(token))]);
+    if let Some(accuracy) = options.accuracy {
+        let level: u32 = match accuracy {
+            LocationAccuracy::Country => 1,
+            LocationAccuracy::City => 2,
+            LocationAccuracy::Neighborhood => 3,
+            LocationAccuracy::Street => 4,
+            LocationAccuracy::Exact => 5,
+        };
+        session_options.insert("accuracy", Value::from(level));
+    }
+    if let Some(meters) = options.distance_threshold {
+        session_options.insert("distance-threshold", Value::from(meters));
+    }
+    session_options
+}
+
+/// Issue a portal request and wait for its `Response` signal
+///
+/// `call` is the request-returning portal method call, whose options carry
+/// `handle_token` = `token`. It is only awaited after subscribing to the response,
+/// so a fast answer cannot be missed. If `closed` resolves before the user answers,
+/// the portal `Request` is closed so the dialog goes away.
+pub(crate) async fn portal_request(
+    connection: &zbus::Connection,
+    token: &str,
+    call: impl Future<Output = zbus::Result<zbus::Message>>,
+    closed: impl Future<Output = ()>,
+) -> zbus::Result<PortalResponse> {
+    let path = request_path(connection, token)?;
+    let timed = Timed::bus_call(PORTAL_DESKTOP, &path, "Response");
+    let response = timed.instrument(await_response(connection, path, call, closed));
+    let response = response.await;
+    timed.finish_probe(&response);
+    response
+}
+
+/// Body of [`portal_request`], once the request path is known
+async fn await_response(
+    connection: &zbus::Connection,
+    path: String,
+    call: impl Future<Output = zbus::Result<zbus::Message>>,
+    closed: impl Future<Output = ()>,
+) -> zbus::Result<PortalResponse> {
+    let rule = zbus::MatchRule::builder()
+        .msg_type(zbus::message::Type::Signal)
+        .sender(PORTAL_DESKTOP)?
+        .path(path)?
+        .interface(PORTAL_REQUEST)?
+        .member("Response")?
+        .build();
+    let mut responses = zbus::MessageStream::for_match_rule(rule, connection, Some(1)).await?;
+
+    let handle: OwnedObjectPath = call.await?.body().deserialize()?;
+
+    tokio::pin!(closed);
+    tokio::select! {
+        msg = responses.next() => {
+            let msg = msg.ok_or_else(|| {
+                zbus::Error::Failure("portal connection closed before Response".into())
+            })??;
+            // Response(u response, a{sv} results): 0 success, 1 cancelled, 2 other
+            let (response, _results): (u32, HashMap<String, OwnedValue>) =
+                msg.body().deserialize()?;
+            Ok(match response {
+                0 => PortalResponse::Granted,
+                1 => PortalResponse::Dismissed,
+                _ => PortalResponse::Refused,
+            })
+        },
+        _ = &mut closed => {
+            connection
+                .call_method(Some(PORTAL_DESKTOP), handle.as_str(), Some(PORTAL_REQUEST), "Close", &())
+                .await
+                .ok();
+            Ok(PortalResponse::Closed)
+        },
+    }
+}
+
+/// Map the outcome of a portal request to a status, `None` if nobody is waiting
+pub(crate) fn portal_status(
+    result: zbus::Result<PortalResponse>,
+    portal: &str,
+) -> Option<Result<PermissionStatus, PermissionError>> {
+    match result {
+        Ok(PortalResponse::Granted) => Some(Ok(PermissionStatus::Authorized)),
+        Ok(PortalResponse::Dismissed) => Some(Err(PermissionError::new(ErrorKind::UserCancelled)
+            .with_probe(portal))),
+        Ok(PortalResponse::Refused) => Some(Ok(PermissionStatus::Denied)),
+        Ok(PortalResponse::Closed) => None,
+        Err(e) => Some(Err(PermissionError::from(e).with_probe(portal))),
+    }
+}
+
+/// Ask the Camera portal for access
+///
+/// `AccessCamera` takes neither a parent window nor a reason, so there are no
+/// [`RequestOptions`] to apply.
+pub fn request_camera(mut tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
+    crate::runtime::spawn(async move {
+        let result = async {
+            let connection = zbus::Connection::session().await?;
+            let token = handle_token();
+            let options = HashMap::from([("handle_token", Value::from(token.as_str()))]);
+            let body = (options,);
+            let call = connection.call_method(
+                Some(PORTAL_DESKTOP),
+                PORTAL_DESKTOP_PATH,
+                Some("org.freedesktop.portal.Camera"),
+                "AccessCamera",
+                &body,
+            );
+            portal_request(&connection, &token, call, tx.closed()).await
+        };
+        if let Some(result) = portal_status(result.await, "org.freedesktop.portal.Camera") {
+            tx.send(result).ok();
+        }
+    });
+}
+
+pub fn request_microphone(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
+    // No portal prompts for microphone access (the Device portal is deprecated);
+    // report the grant recorded in the PermissionStore instead
+    crate::runtime::spawn(async move {
+        tx.send(check_microphone().await).ok();
+    });
+}
+
+/// Ask the Location portal for access, parented and tuned by `options`
+pub fn request_location(
+    options: &RequestOptions,
+    mut tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+) {
+    let options = options.clone();
+    crate::runtime::spawn(async move {
+        let result = async {
+            let connection = zbus::Connection::session().await?;
+            let session_token = handle_token();
+            let session_options = location_session_options(&session_token, &options);
+            let session: OwnedObjectPath = connection
+                .call_method(
+                    Some(PORTAL_DESKTOP),
+                    PORTAL_DESKTOP_PATH,
+                    Some("org.freedesktop.portal.Location"),
+                    "CreateSession",
+                    &(session_options,),
+                )
+                .await?
+                .body()
+                .deserialize()?;
+
+            let token = handle_token();
+            let start_options = HashMap::from([("handle_token", Value::from(token.as_str()))]);
+            let body = (&session, parent_window(&options), start_options);
+            let call = connection.call_method(
+                Some(PORTAL_DESKTOP),
+                PORTAL_DESKTOP_PATH,
+                Some("org.freedesktop.portal.Location"),
+                "Start",
+                &body,
+            );
+            let response = portal_request(&connection, &token, call, tx.closed()).await;
+
+            // Only the grant matters; stop the location session right away
+            connection
+                .call_method(
+                    Some(PORTAL_DESKTOP),
+                    session.as_str(),
+                    Some("org.freedesktop.portal.Session"),
+                    "Close",
+                    &(),
+                )
+                .await
+                .ok();
+            response
+        };
+        if let Some(result) = portal_status(result.await, "org.freedesktop.portal.Location") {
             tx.send(result).ok();
-        });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
     }
-    #[cfg(not(target_os = "linux"))]
-    {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+
+    #[test]
+    fn test_device_status_mapping() {
+        assert_eq!(device_status(&entry(&["yes"])), PermissionStatus::Authorized);
+        assert_eq!(device_status(&entry(&["no"])), PermissionStatus::Denied);
+        assert_eq!(device_status(&entry(&["ask"])), PermissionStatus::NotDetermined);
+        assert_eq!(device_status(&[]), PermissionStatus::NotDetermined);
+    }
+
+    #[tokio::test]
+    async fn test_missing_device_node_is_not_found() {
+        let error = check_device_node("/dev/kodegen-no-such-device").await.unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+        assert_eq!(error.probe(), Some("/dev/kodegen-no-such-device"));
+    }
+
+    #[test]
+    fn test_location_status_mapping() {
+        assert_eq!(location_status(&entry(&["EXACT", "0"])), PermissionStatus::Authorized);
+        assert_eq!(location_status(&entry(&["CITY", "0"])), PermissionStatus::Authorized);
+        assert_eq!(location_status(&entry(&["NONE", "0"])), PermissionStatus::Denied);
+        assert_eq!(location_status(&[]), PermissionStatus::NotDetermined);
+    }
+
+    #[test]
+    fn test_location_request_options() {
+        let options = RequestOptions::new()
+            .with_parent_window(crate::WindowIdentifier::X11(0x3a00007))
+            .with_accuracy(LocationAccuracy::City)
+            .with_distance_threshold(500);
+        let session = location_session_options("t", &options);
+        assert_eq!(session["accuracy"], Value::from(2u32));
+        assert_eq!(session["distance-threshold"], Value::from(500u32));
+        assert_eq!(parent_window(&options), "x11:3a00007");
+
+        let defaults = location_session_options("t", &RequestOptions::new());
+        assert!(!defaults.contains_key("accuracy"));
+        assert_eq!(parent_window(&RequestOptions::new()), "");
+    }
+
+    #[test]
+    fn test_store_permission_mapping() {
+        assert_eq!(store_permission("devices", "camera"), Some(PermissionType::Camera));
+        assert_eq!(store_permission("devices", "microphone"), Some(PermissionType::Microphone));
+        assert_eq!(store_permission("location", "location"), Some(PermissionType::Location));
+        assert_eq!(store_permission("devices", "speakers"), None);
+        assert_eq!(store_permission("notifications", "notification"), None);
     }
 }
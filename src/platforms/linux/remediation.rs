@@ -0,0 +1,293 @@
+//! Remediation guidance for Linux hosts and Flatpak sandboxes
+//!
+//! On the host, camera, microphone and screen sharing are mediated by the portal
+//! and other denials come from Unix group membership or a service that is not
+//! running. Inside Flatpak the sandbox itself is usually what blocks access, and
+//! `flatpak override` lifts it for the application id the portal knows.
+//!
+//! Linux desktops open their settings panels by command rather than by URI, so
+//! `settings_uris` stays empty; the panel for the session's desktop is named in a
+//! step instead, and [`super::settings::open_settings`] launches it.
+
+use std::path::Path;
+
+use super::settings::{self, Desktop};
+use super::{filesystem, portal};
+use crate::remediation::Remediation;
+use crate::types::PermissionType;
+
+/// How the user grants `typ` on this system, running `desktop`
+pub fn remediation(typ: PermissionType, desktop: &Desktop) -> Remediation {
+    let flatpak = Path::new("/.flatpak-info")
+        .exists()
+        .then(portal::app_id)
+        .filter(|app_id| !app_id.is_empty());
+    let remediation = match flatpak {
+        Some(app_id) => flatpak_remediation(typ, &app_id),
+        None => host_remediation(typ, &portal::app_id()),
+    };
+    with_settings_panel(remediation, desktop)
+}
+
+/// Name the settings panel for the permission, if `desktop` has one
+fn with_settings_panel(remediation: Remediation, desktop: &Desktop) -> Remediation {
+    // Unrecognized desktops get every desktop's candidates, so none can be named
+    if matches!(desktop, Desktop::Other(_) | Desktop::Unknown) {
+        return remediation;
+    }
+    match settings::settings_commands(desktop, remediation.permission).first() {
+        Some(command) => remediation.with_step(format!("Open the settings with `{}`", command)),
+        None => remediation,
+    }
+}
+
+/// Guidance for an unsandboxed application, known to the portal as `app_id`
+fn host_remediation(typ: PermissionType, app_id: &str) -> Remediation {
+    let remediation = Remediation::new(typ);
+    match typ {
+        // The portal PermissionStore records the denial; clearing it makes the
+        // camera portal ask again
+        PermissionType::Camera => {
+            let remediation = remediation
+                .with_step("Allow camera access for this application in the privacy settings");
+            if app_id.is_empty() {
+                remediation
+            } else {
+                remediation
+                    .with_step("Or clear the stored decision and request access again")
+                    .with_command(format!(
+                        "flatpak permission-remove devices camera {}",
+                        shell_quote(app_id)
+                    ))
+            }
+        },
+        // No portal prompts for the microphone, so the grant is set directly
+        PermissionType::Microphone => {
+            let remediation = remediation
+                .with_step("Allow microphone access for this application in the privacy settings");
+            if app_id.is_empty() {
+                remediation
+            } else {
+                remediation.with_command(format!(
+                    "flatpak permission-set devices microphone {} yes",
+                    shell_quote(app_id)
+                ))
+            }
+        },
+        // Screen sharing goes through the portal, which asks every session
+        PermissionType::ScreenCapture | PermissionType::RemoteDesktop => remediation
+            .with_step("Approve the screen sharing dialog when it appears")
+            .with_step(
+                "If no dialog appears, install xdg-desktop-portal and the backend for your \
+                 desktop (xdg-desktop-portal-gnome, -kde or -wlr)",
+            ),
+        PermissionType::InputMonitoring => join_group(remediation, "input"),
+        PermissionType::Location => remediation
+            .with_step("Turn on Location Services in the privacy settings")
+            .with_command("gsettings set org.gnome.system.location enabled true"),
+        PermissionType::Bluetooth | PermissionType::NearbyInteraction => remediation
+            .with_step("Start the Bluetooth service and turn Bluetooth on")
+            .with_command("sudo systemctl enable --now bluetooth.service"),
+        PermissionType::WiFi => remediation
+            .with_step("Start NetworkManager")
+            .with_command("sudo systemctl enable --now NetworkManager.service"),
+        PermissionType::Calendar
+        | PermissionType::Reminders
+        | PermissionType::Contacts
+        | PermissionType::AddressBook => remediation
+            .with_step("Install Evolution Data Server (`evolution-data-server`) and log in again")
+            .with_relogin_required(true),
+        PermissionType::SpeechRecognition => {
+            remediation.with_step("Install and start speech-dispatcher")
+        },
+        PermissionType::Accessibility | PermissionType::AccessibilityMouse => remediation
+            .with_step("Turn on assistive technologies and log in again")
+            .with_command("gsettings set org.gnome.desktop.interface toolkit-accessibility true")
+            .with_relogin_required(true),
+        PermissionType::Notification => {
+            remediation.with_step("Allow notifications for this application in the system settings")
+        },
+        PermissionType::Photos
+        | PermissionType::PhotosAdd
+        | PermissionType::MediaLibrary
+        | PermissionType::DesktopFolder
+        | PermissionType::DocumentsFolder
+        | PermissionType::DownloadsFolder => folder_remediation(remediation),
+        PermissionType::FullDiskAccess | PermissionType::AdminFiles => {
+            remediation.with_step("Run the application as root, for example with sudo")
+        },
+        PermissionType::NetworkVolumes => {
+            remediation.with_step("Ask an administrator to give your user access to /mnt")
+        },
+        PermissionType::RemovableVolumes => {
+            remediation.with_step("Ask an administrator to give your user access to /media")
+        },
+        _ => remediation,
+    }
+}
+
+/// Guidance for an application sandboxed by Flatpak as `app_id`
+fn flatpak_remediation(typ: PermissionType, app_id: &str) -> Remediation {
+    let remediation = Remediation::new(typ);
+    let grant = |remediation: Remediation, args: &str| {
+        remediation
+            .with_step("Grant the sandbox access, then restart the application")
+            .with_command(format!("flatpak override --user {} {}", args, shell_quote(app_id)))
+            .with_restart_required(true)
+    };
+    match typ {
+        PermissionType::Camera => grant(
+            remediation.with_command(format!(
+                "flatpak permission-set devices camera {} yes",
+                shell_quote(app_id)
+            )),
+            "--device=all",
+        ),
+        PermissionType::Microphone => grant(
+            remediation.with_command(format!(
+                "flatpak permission-set devices microphone {} yes",
+                shell_quote(app_id)
+            )),
+            "--socket=pulseaudio",
+        ),
+        PermissionType::Location => remediation
+            .with_step("Allow this application in the location settings")
+            .with_command(format!(
+                "flatpak permission-set location location {} EXACT 0",
+                shell_quote(app_id)
+            )),
+        PermissionType::InputMonitoring | PermissionType::Motion => {
+            grant(remediation, "--device=all")
+        },
+        PermissionType::Bluetooth | PermissionType::NearbyInteraction => grant(
+            remediation,
+            "--allow=bluetooth --system-talk-name=org.bluez",
+        ),
+        PermissionType::WiFi => grant(
+            remediation,
+            "--system-talk-name=org.freedesktop.NetworkManager",
+        ),
+        PermissionType::Calendar
+        | PermissionType::Reminders
+        | PermissionType::Contacts
+        | PermissionType::AddressBook => {
+            grant(remediation, "--talk-name=org.gnome.evolution.dataserver.*")
+        },
+        PermissionType::Accessibility | PermissionType::AccessibilityMouse => {
+            grant(remediation, "--talk-name=org.a11y.Bus")
+        },
+        PermissionType::Notification => {
+            grant(remediation, "--talk-name=org.freedesktop.Notifications")
+        },
+        PermissionType::AppleEvents | PermissionType::PostEvent => {
+            grant(remediation, "--socket=session-bus")
+        },
+        PermissionType::Photos | PermissionType::PhotosAdd => {
+            grant(remediation, "--filesystem=xdg-pictures")
+        },
+        PermissionType::MediaLibrary => grant(remediation, "--filesystem=xdg-music"),
+        PermissionType::DesktopFolder => grant(remediation, "--filesystem=xdg-desktop"),
+        PermissionType::DocumentsFolder => grant(remediation, "--filesystem=xdg-documents"),
+        PermissionType::DownloadsFolder => grant(remediation, "--filesystem=xdg-download"),
+        PermissionType::FullDiskAccess => grant(remediation, "--filesystem=host"),
+        PermissionType::NetworkVolumes => grant(remediation, "--filesystem=/mnt"),
+        PermissionType::RemovableVolumes => {
+            grant(remediation, "--filesystem=/media --filesystem=/run/media")
+        },
+        // The portal asks again for every screen sharing session
+        PermissionType::ScreenCapture | PermissionType::RemoteDesktop => {
+            remediation.with_step("Approve the screen sharing dialog when it appears")
+        },
+        _ => host_remediation(typ, app_id),
+    }
+}
+
+/// Add the user to `group`, which takes effect at the next login
+fn join_group(remediation: Remediation, group: &str) -> Remediation {
+    remediation
+        .with_step(format!(
+            "Add your user to the `{}` group, then log out and back in",
+            group
+        ))
+        .with_command(format!("sudo usermod -aG {} \"$USER\"", group))
+        .with_relogin_required(true)
+}
+
+/// Make the folder behind a folder permission accessible, or configure one
+fn folder_remediation(remediation: Remediation) -> Remediation {
+    let typ = remediation.permission;
+    match filesystem::user_dir(typ) {
+        Some(dir) => remediation
+            .with_step(format!("Give your user access to {}", dir.display()))
+            .with_command(format!(
+                "chmod u+rwx {}",
+                shell_quote(&dir.display().to_string())
+            )),
+        None => {
+            let (key, default) = match typ {
+                PermissionType::DesktopFolder => ("DESKTOP", "Desktop"),
+                PermissionType::DocumentsFolder => ("DOCUMENTS", "Documents"),
+                PermissionType::DownloadsFolder => ("DOWNLOAD", "Downloads"),
+                PermissionType::MediaLibrary => ("MUSIC", "Music"),
+                _ => ("PICTURES", "Pictures"),
+            };
+            remediation
+                .with_step("Create the folder and register it in user-dirs.dirs")
+                .with_command(format!("mkdir -p \"$HOME/{}\"", default))
+                .with_command(format!(
+                    "xdg-user-dirs-update --set {} \"$HOME/{}\"",
+                    key, default
+                ))
+        },
+    }
+}
+
+/// Quote `value` for a POSIX shell
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flatpak_camera_override() {
+        let remediation = flatpak_remediation(PermissionType::Camera, "org.example.App");
+        assert_eq!(
+            remediation.commands,
+            [
+                "flatpak permission-set devices camera 'org.example.App' yes",
+                "flatpak override --user --device=all 'org.example.App'",
+            ]
+        );
+        assert!(remediation.restart_required);
+        assert!(!remediation.relogin_required);
+    }
+
+    #[test]
+    fn test_host_screen_capture_uses_portal() {
+        for typ in [PermissionType::ScreenCapture, PermissionType::RemoteDesktop] {
+            let remediation = host_remediation(typ, "");
+            assert!(remediation.steps[0].contains("screen sharing dialog"));
+            assert!(remediation.commands.is_empty());
+            assert!(!remediation.relogin_required);
+        }
+    }
+
+    #[test]
+    fn test_host_camera_removes_portal_permission() {
+        let remediation = host_remediation(PermissionType::Camera, "org.example.App");
+        assert_eq!(
+            remediation.commands,
+            ["flatpak permission-remove devices camera 'org.example.App'"]
+        );
+        assert!(!remediation.relogin_required);
+
+        let remediation = with_settings_panel(remediation, &Desktop::Gnome);
+        assert_eq!(
+            remediation.steps.last().unwrap(),
+            "Open the settings with `gnome-control-center camera`"
+        );
+    }
+}
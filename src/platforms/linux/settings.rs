@@ -0,0 +1,302 @@
+//! Opening the desktop's settings panel for a permission
+//!
+//! Every desktop environment ships its own settings application, so
+//! [`open_settings`] picks commands for the [`Desktop`] the session runs, then
+//! tries them in order until one starts. GNOME and Cinnamon open a panel by id,
+//! KDE Plasma a KCM module; XFCE and MATE have separate tools per area. Portal
+//! permissions (camera, microphone, location) fall back to Flatseal, which edits
+//! the portal PermissionStore on any desktop.
+//!
+//! Both the desktop and the way commands are started can be replaced through
+//! [`LinuxHandler::with_desktop`](super::LinuxHandler::with_desktop) and
+//! [`LinuxHandler::with_launcher`](super::LinuxHandler::with_launcher).
+
+use std::fmt;
+use std::io;
+use std::process::{Command, Stdio};
+
+use crate::trace::Timed;
+use crate::types::{ErrorKind, PermissionError, PermissionType};
+
+/// Environment variables naming the desktop, most specific first
+const DESKTOP_VARS: [&str; 3] = [
+    "XDG_CURRENT_DESKTOP",
+    "XDG_SESSION_DESKTOP",
+    "DESKTOP_SESSION",
+];
+
+/// Desktop environment of the graphical session
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Desktop {
+    /// GNOME, including Ubuntu's and Pop!_OS's sessions
+    Gnome,
+    /// KDE Plasma
+    Kde,
+    /// XFCE
+    Xfce,
+    /// Cinnamon
+    Cinnamon,
+    /// MATE
+    Mate,
+    /// Another desktop, by the name it reports
+    Other(String),
+    /// No desktop could be detected
+    Unknown,
+}
+
+impl Desktop {
+    /// Desktop of the current session, from `XDG_CURRENT_DESKTOP`,
+    /// `XDG_SESSION_DESKTOP` or `DESKTOP_SESSION`
+    pub fn detect() -> Self {
+        DESKTOP_VARS
+            .iter()
+            .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()))
+            .map_or(Self::Unknown, |names| Self::from_names(&names))
+    }
+
+    /// Desktop named by a colon-separated `XDG_CURRENT_DESKTOP` value
+    ///
+    /// The first name that is a known desktop wins, so `Budgie:GNOME` is GNOME.
+    pub fn from_names(names: &str) -> Self {
+        if let Some(desktop) = names.split(':').find_map(Self::known) {
+            return desktop;
+        }
+        match names.split(':').next().map(str::trim) {
+            Some(name) if !name.is_empty() => Self::Other(name.to_string()),
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Desktop for one name in `XDG_CURRENT_DESKTOP`, if it is one we know
+    fn known(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "gnome" | "gnome-classic" | "gnome-flashback" | "ubuntu" | "pop" => Some(Self::Gnome),
+            "kde" | "plasma" | "plasmawayland" => Some(Self::Kde),
+            "xfce" | "xfce4" => Some(Self::Xfce),
+            "cinnamon" | "x-cinnamon" => Some(Self::Cinnamon),
+            "mate" => Some(Self::Mate),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Desktop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Gnome => write!(f, "GNOME"),
+            Self::Kde => write!(f, "KDE Plasma"),
+            Self::Xfce => write!(f, "XFCE"),
+            Self::Cinnamon => write!(f, "Cinnamon"),
+            Self::Mate => write!(f, "MATE"),
+            Self::Other(name) => write!(f, "{}", name),
+            Self::Unknown => write!(f, "an unknown desktop"),
+        }
+    }
+}
+
+/// A program to start, with its arguments
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SettingsCommand {
+    /// Program name, looked up in `PATH`
+    pub program: String,
+    /// Arguments passed to the program
+    pub args: Vec<String>,
+}
+
+impl SettingsCommand {
+    fn new(program: &str, args: &[&str]) -> Self {
+        Self {
+            program: program.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+}
+
+impl fmt::Display for SettingsCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.program)?;
+        for arg in &self.args {
+            write!(f, " {}", arg)?;
+        }
+        Ok(())
+    }
+}
+
+/// Starts settings commands
+pub trait Launcher: Send + Sync {
+    /// Start `command` without waiting for it to exit
+    ///
+    /// Fail with [`io::ErrorKind::NotFound`] when the program is not installed,
+    /// so the next candidate is tried.
+    fn launch(&self, command: &SettingsCommand) -> io::Result<()>;
+}
+
+/// [`Launcher`] spawning each command as a detached child process
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProcessLauncher;
+
+impl Launcher for ProcessLauncher {
+    fn launch(&self, command: &SettingsCommand) -> io::Result<()> {
+        let mut child = Command::new(&command.program)
+            .args(&command.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        // Reap the settings application whenever the user closes it
+        std::thread::spawn(move || child.wait());
+        Ok(())
+    }
+}
+
+/// Open the settings panel for `typ` on `desktop`
+///
+/// Fails with [`ErrorKind::NotSupportedOnPlatform`] when no desktop has a panel
+/// for `typ`, and with [`ErrorKind::NotFound`] when none of the candidate
+/// programs is installed.
+pub fn open_settings(
+    desktop: &Desktop,
+    typ: PermissionType,
+    launcher: &dyn Launcher,
+) -> Result<(), PermissionError> {
+    let commands = settings_commands(desktop, typ);
+    if commands.is_empty() {
+        return Err(PermissionError::new(ErrorKind::NotSupportedOnPlatform)
+            .with_message(format!("{} has no settings panel for {}", desktop, typ)));
+    }
+
+    for command in &commands {
+        let program = command.to_string();
+        let timed = Timed::local("spawn", &program);
+        let launched = launcher.launch(command);
+        timed.finish_probe(&launched);
+        match launched {
+            Ok(()) => return Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(PermissionError::from(e).with_probe(program)),
+        }
+    }
+
+    let tried: Vec<&str> = commands
+        .iter()
+        .map(|command| command.program.as_str())
+        .collect();
+    Err(
+        PermissionError::new(ErrorKind::NotFound).with_message(format!(
+            "no settings application for {} is installed (tried {})",
+            typ,
+            tried.join(", ")
+        )),
+    )
+}
+
+/// Commands opening the panel for `typ`, in the order to try them
+///
+/// An unrecognized desktop tries every desktop's commands.
+pub fn settings_commands(desktop: &Desktop, typ: PermissionType) -> Vec<SettingsCommand> {
+    let mut commands = match desktop {
+        Desktop::Gnome => gnome(typ),
+        Desktop::Kde => kde(typ),
+        Desktop::Xfce => xfce(typ),
+        Desktop::Cinnamon => cinnamon(typ),
+        Desktop::Mate => mate(typ),
+        Desktop::Other(_) | Desktop::Unknown => [gnome, kde, cinnamon, xfce]
+            .iter()
+            .flat_map(|commands| commands(typ))
+            .collect(),
+    };
+
+    // Desktop-independent fallbacks
+    match typ {
+        PermissionType::Camera | PermissionType::Microphone | PermissionType::Location => {
+            commands.push(SettingsCommand::new("flatseal", &[]));
+            commands.push(SettingsCommand::new(
+                "flatpak",
+                &["run", "com.github.tchx84.Flatseal"],
+            ));
+        },
+        PermissionType::Bluetooth => {
+            commands.push(SettingsCommand::new("blueman-manager", &[]));
+        },
+        PermissionType::WiFi => {
+            commands.push(SettingsCommand::new("nm-connection-editor", &[]));
+        },
+        _ => {},
+    }
+    commands
+}
+
+fn gnome(typ: PermissionType) -> Vec<SettingsCommand> {
+    let panel = match typ {
+        PermissionType::Camera => "camera",
+        PermissionType::Microphone => "microphone",
+        PermissionType::Location => "location",
+        PermissionType::RemoteDesktop => "sharing",
+        PermissionType::Bluetooth => "bluetooth",
+        PermissionType::WiFi => "wifi",
+        PermissionType::Notification => "notifications",
+        PermissionType::Accessibility | PermissionType::AccessibilityMouse => "universal-access",
+        _ => return Vec::new(),
+    };
+    vec![SettingsCommand::new("gnome-control-center", &[panel])]
+}
+
+fn kde(typ: PermissionType) -> Vec<SettingsCommand> {
+    // Plasma 6 names the portal permissions module kcm_app-permissions; Plasma 5
+    // ships it as kcm_flatpak
+    let (plasma6, plasma5) = match typ {
+        PermissionType::Camera
+        | PermissionType::Microphone
+        | PermissionType::Location
+        | PermissionType::ScreenCapture
+        | PermissionType::RemoteDesktop => ("kcm_app-permissions", "kcm_flatpak"),
+        PermissionType::Bluetooth => ("kcm_bluetooth", "kcm_bluetooth"),
+        PermissionType::WiFi => ("kcm_networkmanagement", "kcm_networkmanagement"),
+        PermissionType::Notification => ("kcm_notifications", "kcm_notifications"),
+        PermissionType::Accessibility | PermissionType::AccessibilityMouse => {
+            ("kcm_access", "kcm_access")
+        },
+        _ => return Vec::new(),
+    };
+    vec![
+        SettingsCommand::new("systemsettings", &[plasma6]),
+        SettingsCommand::new("kcmshell6", &[plasma6]),
+        SettingsCommand::new("systemsettings5", &[plasma5]),
+        SettingsCommand::new("kcmshell5", &[plasma5]),
+    ]
+}
+
+fn xfce(typ: PermissionType) -> Vec<SettingsCommand> {
+    match typ {
+        PermissionType::Notification => vec![SettingsCommand::new("xfce4-notifyd-config", &[])],
+        PermissionType::Accessibility | PermissionType::AccessibilityMouse => {
+            vec![SettingsCommand::new("xfce4-accessibility-settings", &[])]
+        },
+        _ => Vec::new(),
+    }
+}
+
+// Cinnamon's privacy module only covers recent files, so portal permissions
+// are left to the Flatseal fallback
+fn cinnamon(typ: PermissionType) -> Vec<SettingsCommand> {
+    let module = match typ {
+        PermissionType::Bluetooth => "bluetooth",
+        PermissionType::WiFi => "network",
+        PermissionType::Notification => "notifications",
+        PermissionType::Accessibility | PermissionType::AccessibilityMouse => "accessibility",
+        _ => return Vec::new(),
+    };
+    vec![SettingsCommand::new("cinnamon-settings", &[module])]
+}
+
+fn mate(typ: PermissionType) -> Vec<SettingsCommand> {
+    match typ {
+        PermissionType::Notification => {
+            vec![SettingsCommand::new("mate-notification-properties", &[])]
+        },
+        PermissionType::Accessibility | PermissionType::AccessibilityMouse => {
+            vec![SettingsCommand::new("mate-at-properties", &[])]
+        },
+        _ => Vec::new(),
+    }
+}
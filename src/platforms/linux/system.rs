@@ -1,172 +1,201 @@
 //! System-level permission implementations
 
-use tokio::sync::oneshot;
-
-use crate::types::{PermissionError, PermissionStatus, PermissionType};
+use std::collections::HashMap;
 
-pub fn check_admin_files() -> Result<PermissionStatus, PermissionError> {
-    match std::process::Command::new("id").arg("-u").output() {
+use futures::FutureExt;
+use tokio::sync::oneshot;
+use zbus::zvariant::Value;
+
+use super::portal::{self, PORTAL_DESKTOP, PORTAL_DESKTOP_PATH, PortalResponse};
+use crate::report::{self, Confidence};
+use crate::request::RequestOptions;
+use crate::trace::Timed;
+use crate::types::{PermissionError, PermissionStatus};
+
+const SCREENCAST: &str = "org.freedesktop.portal.ScreenCast";
+
+pub async fn check_admin_files() -> Result<PermissionStatus, PermissionError> {
+    let timed = Timed::local("exec", "id -u");
+    let output = timed
+        .instrument(tokio::process::Command::new("id").arg("-u").output())
+        .await;
+    timed.finish_probe(&output);
+    match output {
         Ok(output) => {
-            let uid = String::from_utf8_lossy(&output.stdout)
-                .trim()
-                .parse::<u32>()
-                .unwrap_or(1000);
-            if uid == 0 {
+            let uid = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            report::record(Confidence::Heuristic, "exec", "id -u", format!("uid {}", uid));
+            if uid.parse::<u32>().unwrap_or(1000) == 0 {
                 Ok(PermissionStatus::Authorized)
             } else {
                 Ok(PermissionStatus::Denied)
             }
         },
-        Err(e) => Err(PermissionError::SystemError(format!(
-            "System operation failed: {}",
-            e
-        ))),
+        Err(e) => Err(PermissionError::from(e).with_probe("id -u")),
     }
 }
 
-pub fn check_screen_capture() -> Result<PermissionStatus, PermissionError> {
-    // Test actual screen capture device access
-    match std::fs::File::open("/dev/fb0") {
+/// Probe a system path by reading its metadata
+async fn check_path(path: &str) -> Result<PermissionStatus, PermissionError> {
+    let timed = Timed::local("stat", path);
+    let metadata = timed.instrument(tokio::fs::metadata(path)).await;
+    timed.finish_probe(&metadata);
+    report::record(Confidence::Heuristic, "stat", path, report::outcome(&metadata));
+    match metadata {
         Ok(_) => Ok(PermissionStatus::Authorized),
-        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Ok(PermissionStatus::Denied),
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(PermissionStatus::Denied),
-        Err(e) => Err(PermissionError::SystemError(format!(
-            "System operation failed: {}",
-            e
-        ))),
+        Err(e) => Err(PermissionError::from(e).with_probe(path)),
     }
 }
 
-pub fn check_input_monitoring() -> Result<PermissionStatus, PermissionError> {
-    match std::fs::File::open("/dev/input/event0") {
-        Ok(_) => Ok(PermissionStatus::Authorized),
-        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Ok(PermissionStatus::Denied),
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(PermissionStatus::Denied),
-        Err(e) => Err(PermissionError::SystemError(format!(
-            "System operation failed: {}",
-            e
-        ))),
-    }
+pub async fn check_screen_capture() -> Result<PermissionStatus, PermissionError> {
+    // Test actual screen capture device access
+    portal::check_device_node("/dev/fb0").await
 }
 
-pub fn check_network_volumes() -> Result<PermissionStatus, PermissionError> {
-    match std::fs::metadata("/mnt") {
-        Ok(_) => Ok(PermissionStatus::Authorized),
-        Err(e) => Err(PermissionError::SystemError(format!(
-            "System operation failed: {}",
-            e
-        ))),
-    }
+pub async fn check_input_monitoring() -> Result<PermissionStatus, PermissionError> {
+    portal::check_device_node("/dev/input/event0").await
 }
 
-pub fn check_removable_volumes() -> Result<PermissionStatus, PermissionError> {
-    match std::fs::metadata("/media") {
-        Ok(_) => Ok(PermissionStatus::Authorized),
-        Err(e) => Err(PermissionError::SystemError(format!(
-            "System operation failed: {}",
-            e
-        ))),
-    }
+pub async fn check_network_volumes() -> Result<PermissionStatus, PermissionError> {
+    check_path("/mnt").await
 }
 
-pub fn check_motion() -> Result<PermissionStatus, PermissionError> {
-    match std::fs::metadata("/sys/class/input") {
-        Ok(_) => Ok(PermissionStatus::Authorized),
-        Err(e) => Err(PermissionError::SystemError(format!(
-            "System operation failed: {}",
-            e
-        ))),
-    }
+pub async fn check_removable_volumes() -> Result<PermissionStatus, PermissionError> {
+    check_path("/media").await
+}
+
+pub async fn check_motion() -> Result<PermissionStatus, PermissionError> {
+    check_path("/sys/class/input").await
 }
 
 pub fn request_admin_files(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
-    std::thread::spawn(move || {
-        let result = check_admin_files();
+    crate::runtime::spawn(async move {
+        let result = check_admin_files().await;
         tx.send(result).ok();
     });
 }
 
-pub fn request_screen_capture(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
-    #[cfg(target_os = "linux")]
-    {
-        std::thread::spawn(move || {
-            use std::time::Duration;
-
-            use dbus::Message;
-            use dbus::blocking::Connection;
-
-            // Use XDG Desktop Portal for screen capture
-            let result = match Connection::new_session() {
-                Ok(conn) => {
-                    let msg = match Message::new_method_call(
-                        "org.freedesktop.portal.Desktop",
-                        "/org/freedesktop/portal/desktop",
-                        "org.freedesktop.portal.ScreenCast",
-                        "CreateSession",
-                    ) {
-                        Ok(msg) => msg,
-                        Err(e) => {
-                            tx.send(Err(PermissionError::SystemError(format!(
-                                "D-Bus message creation failed for Portal ScreenCast \
-                                 CreateSession: {}",
-                                e
-                            )))).ok();
-                            return;
-                        },
-                    };
-                    match conn.send_with_reply_and_block(msg, Duration::from_secs(2)) {
-                        Ok(reply) => {
-                            if reply.msg_type() == dbus::MessageType::MethodReturn {
-                                Ok(PermissionStatus::Authorized)
-                            } else {
-                                Ok(PermissionStatus::Denied)
-                            }
-                        },
-                        Err(e) => Err(PermissionError::SystemError(format!(
-                            "Portal ScreenCast request method call failed: {}",
-                            e
-                        ))),
-                    }
-                },
-                Err(e) => Err(PermissionError::SystemError(format!(
-                    "Portal ScreenCast request connection failed: {}",
-                    e
-                ))),
-            };
+/// Ask the ScreenCast portal to pick a screen or window, parented by `options`
+pub fn request_screen_capture(
+    options: &RequestOptions,
+    mut tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+) {
+    let parent_window = portal::parent_window(options);
+    crate::runtime::spawn(async move {
+        let result = create_screencast_session(&parent_window, tx.closed()).await;
+        if let Some(result) = result {
             tx.send(result).ok();
-        });
+        }
+    });
+}
+
+/// Run the XDG Desktop Portal ScreenCast flow up to the user's choice
+///
+/// `CreateSession` and `SelectSources` are set-up steps; the dialog appears on
+/// `Start`, attached to `parent_window`. The session is closed afterwards since
+/// only the grant matters. Returns `None` if `closed` resolved first and the
+/// portal request was closed.
+async fn create_screencast_session(
+    parent_window: &str,
+    closed: impl Future<Output = ()>,
+) -> Option<Result<PermissionStatus, PermissionError>> {
+    let connection = match zbus::Connection::session().await {
+        Ok(connection) => connection,
+        Err(e) => {
+            return Some(Err(PermissionError::from(e)
+                .with_message("Portal ScreenCast request connection failed")
+                .with_probe(SCREENCAST)));
+        },
+    };
+    let closed = closed.shared();
+
+    let session_token = portal::handle_token();
+    let session = match portal::session_path(&connection, &session_token) {
+        Ok(session) => session,
+        Err(e) => return Some(Err(PermissionError::from(e).with_probe(SCREENCAST))),
+    };
+    let token = portal::handle_token();
+    let body = (HashMap::from([
+        ("handle_token", Value::from(token.as_str())),
+        ("session_handle_token", Value::from(session_token.as_str())),
+    ]),);
+    let call = connection.call_method(
+        Some(PORTAL_DESKTOP),
+        PORTAL_DESKTOP_PATH,
+        Some(SCREENCAST),
+        "CreateSession",
+        &body,
+    );
+    let mut response = portal::portal_request(&connection, &token, call, closed.clone()).await;
+
+    if matches!(response, Ok(PortalResponse::Granted)) {
+        // Monitors and windows
+        let token = portal::handle_token();
+        let select = HashMap::from([
+            ("handle_token", Value::from(token.as_str())),
+            ("types", Value::from(1u32 | 2)),
+        ]);
+        let body = (&session, select);
+        let call = connection.call_method(
+            Some(PORTAL_DESKTOP),
+            PORTAL_DESKTOP_PATH,
+            Some(SCREENCAST),
+            "SelectSources",
+            &body,
+        );
+        response = portal::portal_request(&connection, &token, call, closed.clone()).await;
     }
 
-    #[cfg(not(target_os = "linux"))]
-    {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+    if matches!(response, Ok(PortalResponse::Granted)) {
+        let token = portal::handle_token();
+        let start = HashMap::from([("handle_token", Value::from(token.as_str()))]);
+        let body = (&session, parent_window, start);
+        let call = connection.call_method(
+            Some(PORTAL_DESKTOP),
+            PORTAL_DESKTOP_PATH,
+            Some(SCREENCAST),
+            "Start",
+            &body,
+        );
+        response = portal::portal_request(&connection, &token, call, closed).await;
     }
+
+    connection
+        .call_method(
+            Some(PORTAL_DESKTOP),
+            session.as_str(),
+            Some("org.freedesktop.portal.Session"),
+            "Close",
+            &(),
+        )
+        .await
+        .ok();
+    portal::portal_status(response, SCREENCAST)
 }
 
 pub fn request_input_monitoring(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
-    std::thread::spawn(move || {
-        let result = check_input_monitoring();
+    crate::runtime::spawn(async move {
+        let result = check_input_monitoring().await;
         tx.send(result).ok();
     });
 }
 
 pub fn request_network_volumes(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
-    std::thread::spawn(move || {
-        let result = check_network_volumes();
+    crate::runtime::spawn(async move {
+        let result = check_network_volumes().await;
         tx.send(result).ok();
     });
 }
 
 pub fn request_removable_volumes(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
-    std::thread::spawn(move || {
-        let result = check_removable_volumes();
+    crate::runtime::spawn(async move {
+        let result = check_removable_volumes().await;
         tx.send(result).ok();
     });
 }
 
 pub fn request_motion(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
-    std::thread::spawn(move || {
-        let result = check_motion();
+    crate::runtime::spawn(async move {
+        let result = check_motion().await;
         tx.send(result).ok();
     });
 }
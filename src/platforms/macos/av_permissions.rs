@@ -9,7 +9,8 @@ use objc2_av_foundation::{
     AVAuthorizationStatus, AVCaptureDevice, AVMediaTypeAudio, AVMediaTypeVideo,
 };
 
-use crate::types::{PermissionError, PermissionStatus, PermissionType};
+use crate::report::{self, Confidence};
+use crate::types::{ErrorKind, PermissionError, PermissionStatus, PermissionType};
 
 impl From<AVAuthorizationStatus> for PermissionStatus {
     fn from(status: AVAuthorizationStatus) -> Self {
@@ -23,13 +24,14 @@ impl From<AVAuthorizationStatus> for PermissionStatus {
 }
 
 pub fn check_permission(typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
-    let media_type = match typ {
-        PermissionType::Camera => unsafe { AVMediaTypeVideo.ok_or(PermissionError::Unknown)? },
-        PermissionType::Microphone => unsafe { AVMediaTypeAudio.ok_or(PermissionError::Unknown)? },
-        _ => return Err(PermissionError::Unknown),
+    let (media_type, target) = match typ {
+        PermissionType::Camera => unsafe { (AVMediaTypeVideo.ok_or(PermissionError::new(ErrorKind::Unknown))?, "AVMediaTypeVideo") },
+        PermissionType::Microphone => unsafe { (AVMediaTypeAudio.ok_or(PermissionError::new(ErrorKind::Unknown))?, "AVMediaTypeAudio") },
+        _ => return Err(PermissionError::new(ErrorKind::Unknown)),
     };
-    let status = unsafe { AVCaptureDevice::authorizationStatusForMediaType(media_type) };
-    Ok(status.into())
+    let status = unsafe { AVCaptureDevice::authorizationStatusForMediaType(media_type) }.into();
+    report::record(Confidence::Verified, "AVCaptureDevice.authorizationStatusForMediaType", target, status);
+    Ok(status)
 }
 
 pub fn request_permission(
@@ -41,7 +43,7 @@ pub fn request_permission(
             match AVMediaTypeVideo {
                 Some(media_type) => media_type,
                 None => {
-                    tx.send(Err(PermissionError::Unknown)).ok();
+                    tx.send(Err(PermissionError::new(ErrorKind::Unknown))).ok();
                     return;
                 },
             }
@@ -50,13 +52,13 @@ pub fn request_permission(
             match AVMediaTypeAudio {
                 Some(media_type) => media_type,
                 None => {
-                    tx.send(Err(PermissionError::Unknown)).ok();
+                    tx.send(Err(PermissionError::new(ErrorKind::Unknown))).ok();
                     return;
                 },
             }
         },
         _ => {
-            tx.send(Err(PermissionError::Unknown)).ok();
+            tx.send(Err(PermissionError::new(ErrorKind::Unknown))).ok();
             return;
         },
     };
@@ -10,6 +10,7 @@ use objc2_core_bluetooth::{
     CBCentralManager, CBCentralManagerDelegate, CBManager, CBManagerAuthorization,
 };
 
+use crate::report::{self, Confidence};
 use crate::types::{PermissionError, PermissionStatus};
 
 type BluetoothTxType = Arc<Mutex<Option<oneshot::Sender<Result<PermissionStatus, PermissionError>>>>>;
@@ -56,8 +57,9 @@ impl From<CBManagerAuthorization> for PermissionStatus {
 }
 
 pub fn check_permission() -> Result<PermissionStatus, PermissionError> {
-    let auth = unsafe { CBManager::authorization_class() };
-    Ok(auth.into())
+    let status = unsafe { CBManager::authorization_class() }.into();
+    report::record(Confidence::Verified, "CBManager.authorization", "CoreBluetooth", status);
+    Ok(status)
 }
 
 pub fn request_permission(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
@@ -8,6 +8,7 @@ use objc2::runtime::Bool;
 use objc2_contacts::{CNAuthorizationStatus, CNContactStore, CNEntityType};
 use objc2_foundation::NSError;
 
+use crate::report::{self, Confidence};
 use crate::types::{PermissionError, PermissionStatus};
 
 pub fn check_permission() -> Result<PermissionStatus, PermissionError> {
@@ -19,6 +20,7 @@ pub fn check_permission() -> Result<PermissionStatus, PermissionError> {
         CNAuthorizationStatus::Restricted => PermissionStatus::Restricted,
         _ => PermissionStatus::NotDetermined,
     };
+    report::record(Confidence::Verified, "CNContactStore.authorizationStatusForEntityType", "contacts", mapped);
     Ok(mapped)
 }
 
@@ -32,7 +34,7 @@ pub fn request_permission(tx: oneshot::Sender<Result<PermissionStatus, Permissio
             && let Some(sender) = guard.take()
         {
             if !error.is_null() {
-                let _ = sender.send(Err(PermissionError::SystemError(
+                let _ = sender.send(Err(PermissionError::system(
                     "Error in request".to_string(),
                 )));
                 return;
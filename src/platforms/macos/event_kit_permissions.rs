@@ -8,13 +8,14 @@ use objc2::runtime::Bool;
 use objc2_event_kit::{EKAuthorizationStatus, EKEntityType, EKEventStore};
 use objc2_foundation::NSError;
 
-use crate::types::{PermissionError, PermissionStatus, PermissionType};
+use crate::report::{self, Confidence};
+use crate::types::{ErrorKind, PermissionError, PermissionStatus, PermissionType};
 
 pub fn check_permission(typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
     let entity_type = match typ {
         PermissionType::Calendar => EKEntityType::Event,
         PermissionType::Reminders => EKEntityType::Reminder,
-        _ => return Err(PermissionError::Unknown),
+        _ => return Err(PermissionError::new(ErrorKind::Unknown)),
     };
     let status = unsafe { EKEventStore::authorizationStatusForEntityType(entity_type) };
     let mapped = match status {
@@ -23,6 +24,7 @@ pub fn check_permission(typ: PermissionType) -> Result<PermissionStatus, Permiss
         EKAuthorizationStatus::Restricted => PermissionStatus::Restricted,
         _ => PermissionStatus::NotDetermined,
     };
+    report::record(Confidence::Verified, "EKEventStore.authorizationStatusForEntityType", typ.to_string(), mapped);
     Ok(mapped)
 }
 
@@ -40,7 +42,7 @@ pub fn request_permission(
             if let Ok(mut guard) = tx.lock()
                 && let Some(sender) = guard.take()
             {
-                let _ = sender.send(Err(PermissionError::Unknown));
+                let _ = sender.send(Err(PermissionError::new(ErrorKind::Unknown)));
             }
             return;
         },
@@ -54,7 +56,7 @@ pub fn request_permission(
             && let Some(sender) = guard.take()
         {
             if !error.is_null() {
-                let _ = sender.send(Err(PermissionError::SystemError(
+                let _ = sender.send(Err(PermissionError::system(
                     "Error in request".to_string(),
                 )));
                 return;
@@ -80,7 +82,7 @@ pub fn request_permission(
                 if let Ok(mut guard) = tx.lock()
                     && let Some(sender) = guard.take()
                 {
-                    let _ = sender.send(Err(PermissionError::Unknown));
+                    let _ = sender.send(Err(PermissionError::new(ErrorKind::Unknown)));
                 }
             },
         }
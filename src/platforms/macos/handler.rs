@@ -1,12 +1,15 @@
 //! macOS permissions implementation using proven patterns from tauri-plugin-macos-permissions
 
-use crate::types::{PermissionError, PermissionStatus, PermissionType};
+use crate::remediation::Remediation;
+use crate::request::RequestOptions;
+use crate::types::{PermissionError, PermissionStatus, PermissionType, Support};
 use crate::traits::PermissionHandler;
-use super::{av_permissions, location_permissions, event_kit_permissions, contacts_permissions, bluetooth_permissions, notification_permissions, tcc_permissions};
+use super::{av_permissions, location_permissions, event_kit_permissions, contacts_permissions, bluetooth_permissions, notification_permissions, remediation, tcc_permissions};
 use tokio::sync::oneshot;
 
 #[cfg(target_os = "macos")]
 use {
+    crate::report::{self, Confidence},
     macos_accessibility_client::accessibility::{
         application_is_trusted, application_is_trusted_with_prompt,
     },
@@ -41,6 +44,25 @@ impl MacOSHandler {
 }
 
 impl PermissionHandler for MacOSHandler {
+    fn name(&self) -> &'static str {
+        "macos"
+    }
+
+    fn support(&self, typ: PermissionType) -> Support {
+        match typ {
+            PermissionType::WiFi => Support::Emulated { via: "CoreWLAN interface" },
+            // iOS-only permissions, and "All" is not a permission of its own
+            PermissionType::All | PermissionType::Calls | PermissionType::FaceID => {
+                Support::NotApplicable
+            },
+            _ => Support::Native,
+        }
+    }
+
+    fn remediation(&self, typ: PermissionType) -> Remediation {
+        remediation::remediation(typ)
+    }
+
     fn check_permission(&self, typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
         match typ {
             PermissionType::Camera | PermissionType::Microphone => av_permissions::check_permission(typ),
@@ -85,7 +107,7 @@ impl PermissionHandler for MacOSHandler {
     fn request_permission(&self, typ: PermissionType, tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
         match typ {
             PermissionType::Camera | PermissionType::Microphone => av_permissions::request_permission(typ, tx),
-            PermissionType::Location => location_permissions::request_permission(tx),
+            PermissionType::Location => location_permissions::request_permission(&RequestOptions::default(), tx),
             PermissionType::Calendar | PermissionType::Reminders => event_kit_permissions::request_permission(typ, tx),
             PermissionType::Contacts => contacts_permissions::request_permission(tx),
             PermissionType::Bluetooth => bluetooth_permissions::request_permission(tx),
@@ -125,6 +147,22 @@ impl PermissionHandler for MacOSHandler {
             PermissionType::WillfulWrite => self.request_tcc_permission(typ, tx),
         }
     }
+
+    /// Only location requests use `options`: their accuracy and distance threshold
+    /// tune the `CLLocationManager`. macOS prompts are system-modal and take their
+    /// explanation from the app's `Info.plist`, so the parent window and reason are
+    /// ignored.
+    fn request_permission_with(
+        &self,
+        typ: PermissionType,
+        options: &RequestOptions,
+        tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        match typ {
+            PermissionType::Location => location_permissions::request_permission(options, tx),
+            _ => self.request_permission(typ, tx),
+        }
+    }
 }
 
 impl MacOSHandler {
@@ -132,6 +170,7 @@ impl MacOSHandler {
         #[cfg(target_os = "macos")]
         {
             let is_trusted = application_is_trusted();
+            report::record(Confidence::Verified, "AXIsProcessTrusted", "accessibility", is_trusted);
             Ok(if is_trusted {
                 PermissionStatus::Authorized
             } else {
@@ -140,7 +179,7 @@ impl MacOSHandler {
         }
         
         #[cfg(not(target_os = "macos"))]
-        Ok(PermissionStatus::Authorized)
+        Ok(PermissionStatus::NotApplicable)
     }
 
     pub fn request_accessibility(&self, tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
@@ -159,7 +198,7 @@ impl MacOSHandler {
         
         #[cfg(not(target_os = "macos"))]
         {
-            tx.send(Ok(PermissionStatus::Authorized)).ok();
+            tx.send(Ok(PermissionStatus::NotApplicable)).ok();
         }
     }
 
@@ -170,7 +209,15 @@ impl MacOSHandler {
             
             unsafe {
                 let client = CWWiFiClient::sharedWiFiClient();
-                if let Some(_interface) = client.interface() {
+                let interface = client.interface();
+                // An interface only shows the radio is reachable, not that access was granted
+                report::record(
+                    Confidence::Heuristic,
+                    "CWWiFiClient.interface",
+                    "CoreWLAN",
+                    if interface.is_some() { "interface" } else { "none" },
+                );
+                if let Some(_interface) = interface {
                     // If we can get an interface, WiFi is available
                     Ok(PermissionStatus::Authorized)
                 } else {
@@ -181,13 +228,14 @@ impl MacOSHandler {
         }
         
         #[cfg(not(target_os = "macos"))]
-        Ok(PermissionStatus::Authorized)
+        Ok(PermissionStatus::NotApplicable)
     }
 
     pub fn check_screen_recording(&self) -> Result<PermissionStatus, PermissionError> {
         #[cfg(target_os = "macos")]
         unsafe {
             let has_access = CGPreflightScreenCaptureAccess();
+            report::record(Confidence::Verified, "CGPreflightScreenCaptureAccess", "screen capture", has_access);
             Ok(if has_access {
                 PermissionStatus::Authorized
             } else {
@@ -196,7 +244,7 @@ impl MacOSHandler {
         }
         
         #[cfg(not(target_os = "macos"))]
-        Ok(PermissionStatus::Authorized)
+        Ok(PermissionStatus::NotApplicable)
     }
 
     pub fn request_screen_recording(&self, tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
@@ -213,7 +261,7 @@ impl MacOSHandler {
         
         #[cfg(not(target_os = "macos"))]
         {
-            tx.send(Ok(PermissionStatus::Authorized)).ok();
+            tx.send(Ok(PermissionStatus::NotApplicable)).ok();
         }
     }
 
@@ -221,6 +269,7 @@ impl MacOSHandler {
         #[cfg(target_os = "macos")]
         unsafe {
             let status = IOHIDCheckAccess(1);
+            report::record(Confidence::Verified, "IOHIDCheckAccess", "kIOHIDRequestTypeListenEvent", status);
             Ok(if status == 0 {
                 PermissionStatus::Authorized
             } else {
@@ -229,7 +278,7 @@ impl MacOSHandler {
         }
         
         #[cfg(not(target_os = "macos"))]
-        Ok(PermissionStatus::Authorized)
+        Ok(PermissionStatus::NotApplicable)
     }
 
     pub fn request_input_monitoring(&self, tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
@@ -247,7 +296,7 @@ impl MacOSHandler {
         
         #[cfg(not(target_os = "macos"))]
         {
-            tx.send(Ok(PermissionStatus::Authorized)).ok();
+            tx.send(Ok(PermissionStatus::NotApplicable)).ok();
         }
     }
 
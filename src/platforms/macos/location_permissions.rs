@@ -6,8 +6,14 @@ use std::sync::{Arc, Mutex, OnceLock};
 use objc2::rc::Retained;
 use objc2::runtime::{NSObject, NSObjectProtocol, ProtocolObject};
 use objc2::{MainThreadMarker, MainThreadOnly, define_class, msg_send};
-use objc2_core_location::{CLAuthorizationStatus, CLLocationManager, CLLocationManagerDelegate};
+use objc2_core_location::{
+    CLAuthorizationStatus, CLLocationAccuracy, CLLocationManager, CLLocationManagerDelegate,
+    kCLLocationAccuracyBest, kCLLocationAccuracyHundredMeters, kCLLocationAccuracyNearestTenMeters,
+    kCLLocationAccuracyReduced,
+};
 
+use crate::report::{self, Confidence};
+use crate::request::{LocationAccuracy, RequestOptions};
 use crate::types::{PermissionError, PermissionStatus};
 
 type LocationTxType = Arc<Mutex<Option<oneshot::Sender<Result<PermissionStatus, PermissionError>>>>>;
@@ -54,18 +60,38 @@ impl From<CLAuthorizationStatus> for PermissionStatus {
 pub fn check_permission() -> Result<PermissionStatus, PermissionError> {
     if let Some(_mtm) = MainThreadMarker::new() {
         let manager = unsafe { CLLocationManager::new() };
-        let status = unsafe { manager.authorizationStatus() };
-        Ok(status.into())
+        let status = unsafe { manager.authorizationStatus() }.into();
+        report::record(Confidence::Verified, "CLLocationManager.authorizationStatus", "CoreLocation", status);
+        Ok(status)
     } else {
-        Err(PermissionError::SystemError(
+        Err(PermissionError::system(
             "Not on main thread".to_string(),
         ))
     }
 }
 
-pub fn request_permission(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
+/// Core Location accuracy for `accuracy`; city level and coarser ask for approximate location
+fn desired_accuracy(accuracy: LocationAccuracy) -> CLLocationAccuracy {
+    unsafe {
+        match accuracy {
+            LocationAccuracy::Country | LocationAccuracy::City => kCLLocationAccuracyReduced,
+            LocationAccuracy::Neighborhood => kCLLocationAccuracyHundredMeters,
+            LocationAccuracy::Street => kCLLocationAccuracyNearestTenMeters,
+            LocationAccuracy::Exact => kCLLocationAccuracyBest,
+        }
+    }
+}
+
+/// Ask Core Location for access, tuned by the accuracy and distance threshold in `options`
+pub fn request_permission(options: &RequestOptions, tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
     if let Some(mtm) = MainThreadMarker::new() {
         let manager = unsafe { CLLocationManager::new() };
+        if let Some(accuracy) = options.accuracy {
+            unsafe { manager.setDesiredAccuracy(desired_accuracy(accuracy)) };
+        }
+        if let Some(meters) = options.distance_threshold {
+            unsafe { manager.setDistanceFilter(f64::from(meters)) };
+        }
         let status = unsafe { manager.authorizationStatus() };
 
         if status == CLAuthorizationStatus::NotDetermined {
@@ -7,4 +7,7 @@ pub mod event_kit_permissions;
 pub mod handler;
 pub mod location_permissions;
 pub mod notification_permissions;
+pub mod remediation;
 pub mod tcc_permissions;
+
+pub use handler::MacOSHandler;
@@ -1,6 +1,7 @@
 //! macOS notification permission handling using UNUserNotificationCenter
 
-use crate::types::{PermissionError, PermissionStatus};
+use crate::report::{self, Confidence};
+use crate::types::{ErrorKind, PermissionError, PermissionStatus};
 use tokio::sync::oneshot;
 
 #[cfg(target_os = "macos")]
@@ -41,17 +42,23 @@ const SETTINGS_CALLBACK_TIMEOUT: Duration = Duration::from_secs(5);
 /// waiting for the macOS notification settings callback. For async contexts,
 /// prefer `check_permission_async()`.
 pub fn check_permission() -> Result<PermissionStatus, PermissionError> {
-    check_permission_with_cancel(None)
+    let status = check_permission_with_cancel(None);
+    if let Ok(status) = &status {
+        // The settings callback timing out leaves the status unknown
+        let confidence = if *status == PermissionStatus::Unknown { Confidence::Assumed } else { Confidence::Verified };
+        report::record(confidence, "UNUserNotificationCenter.getNotificationSettings", "notifications", status);
+    }
+    status
 }
 
 /// Check current notification permission status with cancellation support
 ///
 /// # Arguments
-/// * `cancel` - Optional cancellation token. When cancelled, returns `Err(PermissionError::Cancelled)`
+/// * `cancel` - Optional cancellation token. When cancelled, returns an `ErrorKind::Cancelled` error
 ///
 /// # Cancellation Behavior
 /// - Cancellation is checked every 100ms during the wait
-/// - If cancelled, immediately returns `Err(PermissionError::Cancelled)`
+/// - If cancelled, immediately returns an `ErrorKind::Cancelled` error
 /// - If not cancelled within 5 seconds, returns `Ok(PermissionStatus::Unknown)`
 #[cfg(target_os = "macos")]
 pub fn check_permission_with_cancel(
@@ -102,7 +109,7 @@ pub fn check_permission_with_cancel(
             {
                 // Signal callback to skip work if it fires later
                 cancelled.store(true, Ordering::Release);
-                return Err(PermissionError::Cancelled);
+                return Err(PermissionError::new(ErrorKind::Cancelled));
             }
 
             // Check if result already available
@@ -193,7 +200,7 @@ fn check_permission_internal(tx: oneshot::Sender<Result<PermissionStatus, Permis
 
     #[cfg(not(target_os = "macos"))]
     {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+        tx.send(Ok(PermissionStatus::NotApplicable)).ok();
     }
 }
 
@@ -221,7 +228,7 @@ pub fn request_permission_with_cancel(
     if let Some(ref token) = cancel
         && token.is_cancelled()
     {
-        tx.send(Err(PermissionError::Cancelled)).ok();
+        tx.send(Err(PermissionError::new(ErrorKind::Cancelled))).ok();
         return;
     }
 
@@ -246,7 +253,7 @@ pub fn request_permission_with_cancel(
                 let err_ref = &*error;
                 err_ref.localizedDescription().to_string()
             };
-            Err(PermissionError::SystemError(error_description))
+            Err(PermissionError::system(error_description))
         } else if granted.as_bool() {
             Ok(PermissionStatus::Authorized)
         } else {
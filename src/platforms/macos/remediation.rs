@@ -0,0 +1,69 @@
+//! Remediation guidance for TCC-protected permissions
+
+use super::tcc_permissions;
+use crate::remediation::Remediation;
+use crate::types::PermissionType;
+
+/// How the user grants `typ` on macOS
+pub fn remediation(typ: PermissionType) -> Remediation {
+    // iOS-only permissions, and "All" is not a permission of its own
+    if matches!(
+        typ,
+        PermissionType::All | PermissionType::Calls | PermissionType::FaceID
+    ) {
+        return Remediation::new(typ);
+    }
+
+    let mut remediation = Remediation::new(typ)
+        .with_step(format!(
+            "Open System Settings > Privacy & Security and allow this application under {}",
+            typ
+        ))
+        .with_settings_uri(tcc_permissions::privacy_pane_uri(typ));
+    if let Some(service) = tcc_service(typ) {
+        // A denied prompt is never shown again until the decision is reset
+        remediation = remediation
+            .with_step(format!(
+                "Or reset the earlier decision with `tccutil reset {}` so the application can ask again",
+                service
+            ))
+            .with_command(format!("tccutil reset {}", service));
+    }
+
+    // TCC grants for these are read once when the process starts
+    remediation.with_restart_required(matches!(
+        typ,
+        PermissionType::ScreenCapture
+            | PermissionType::FullDiskAccess
+            | PermissionType::AdminFiles
+            | PermissionType::InputMonitoring
+    ))
+}
+
+/// TCC service name understood by `tccutil`
+fn tcc_service(typ: PermissionType) -> Option<&'static str> {
+    Some(match typ {
+        PermissionType::Camera => "Camera",
+        PermissionType::Microphone => "Microphone",
+        PermissionType::Calendar => "Calendar",
+        PermissionType::Reminders => "Reminders",
+        PermissionType::Contacts | PermissionType::AddressBook => "AddressBook",
+        PermissionType::Photos | PermissionType::PhotosAdd => "Photos",
+        PermissionType::MediaLibrary => "MediaLibrary",
+        PermissionType::SpeechRecognition => "SpeechRecognition",
+        PermissionType::Bluetooth => "BluetoothAlways",
+        PermissionType::Accessibility | PermissionType::AccessibilityMouse => "Accessibility",
+        PermissionType::ScreenCapture => "ScreenCapture",
+        PermissionType::InputMonitoring => "ListenEvent",
+        PermissionType::PostEvent => "PostEvent",
+        PermissionType::AppleEvents => "AppleEvents",
+        PermissionType::DeveloperTools => "DeveloperTool",
+        PermissionType::FullDiskAccess | PermissionType::AdminFiles => "SystemPolicyAllFiles",
+        PermissionType::DesktopFolder => "SystemPolicyDesktopFolder",
+        PermissionType::DocumentsFolder => "SystemPolicyDocumentsFolder",
+        PermissionType::DownloadsFolder => "SystemPolicyDownloadsFolder",
+        PermissionType::NetworkVolumes => "SystemPolicyNetworkVolumes",
+        PermissionType::RemovableVolumes => "SystemPolicyRemovableVolumes",
+        _ => return None,
+    })
+}
@@ -6,25 +6,41 @@ use std::path::PathBuf;
 
 use home::home_dir;
 
+use crate::report::{self, Confidence};
 use crate::types::{PermissionError, PermissionStatus, PermissionType};
 
 pub fn check_permission(typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
     let path = get_protected_path(typ);
     if let Some(p) = path {
-        match File::open(&p) {
+        // TCC fails the open with EPERM when the app has not been granted access
+        let opened = File::open(&p);
+        report::record(Confidence::Heuristic, "open", p.display().to_string(), report::outcome(&opened));
+        match opened {
             Ok(_) => Ok(PermissionStatus::Authorized),
             Err(e) if e.kind() == ErrorKind::PermissionDenied => Ok(PermissionStatus::Denied),
-            Err(e) => Err(PermissionError::SystemError(e.to_string())),
+            Err(e) => Err(PermissionError::from(e).with_probe(p.display().to_string())),
         }
     } else {
         // If no path is defined, assume permission is generally available
+        report::record(Confidence::Assumed, "TCC", typ.to_string(), "no protected path to probe");
         Ok(PermissionStatus::NotDetermined)
     }
 }
 
 pub fn request_permission(typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
     // TCC permissions cannot be requested programmatically - they require manual user grant
-    // Open System Preferences to the appropriate privacy section
+    // Open System Preferences to the specific privacy pane
+    std::process::Command::new("open")
+        .arg(privacy_pane_uri(typ))
+        .output()
+        .map_err(|e| PermissionError::from(e).with_probe("open"))?;
+
+    // Return current status - user must manually grant permission
+    check_permission(typ)
+}
+
+/// URI opening the Privacy & Security pane where `typ` is granted
+pub fn privacy_pane_uri(typ: PermissionType) -> String {
     let privacy_pane = match typ {
         // Specialized permissions handled by other modules - open to general Privacy pane
         PermissionType::Camera => "Privacy_Camera",
@@ -71,18 +87,10 @@ pub fn request_permission(typ: PermissionType) -> Result<PermissionStatus, Permi
         // Notification is handled by notification_permissions module, not TCC
         PermissionType::Notification => "Privacy_Notifications",
     };
-
-    // Open System Preferences to the specific privacy pane
-    std::process::Command::new("open")
-        .arg(format!(
-            "x-apple.systempreferences:com.apple.preference.security?{}",
-            privacy_pane
-        ))
-        .output()
-        .map_err(|e| PermissionError::SystemError(e.to_string()))?;
-
-    // Return current status - user must manually grant permission
-    check_permission(typ)
+    format!(
+        "x-apple.systempreferences:com.apple.preference.security?{}",
+        privacy_pane
+    )
 }
 
 fn get_protected_path(typ: PermissionType) -> Option<PathBuf> {
@@ -15,7 +15,7 @@ pub fn check_accessibility() -> Result<PermissionStatus, PermissionError> {
         Ok(PermissionStatus::Authorized)
     }
     #[cfg(not(target_os = "windows"))]
-    Ok(PermissionStatus::Authorized)
+    Ok(PermissionStatus::NotApplicable)
 }
 
 pub fn request_accessibility(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
@@ -27,6 +27,6 @@ pub fn request_accessibility(tx: oneshot::Sender<Result<PermissionStatus, Permis
     }
     #[cfg(not(target_os = "windows"))]
     {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+        tx.send(Ok(PermissionStatus::NotApplicable)).ok();
     }
 }
@@ -11,6 +11,7 @@ use {
 };
 
 use super::helpers::convert_app_capability_status;
+use crate::report::{self, Confidence};
 use crate::types::{PermissionError, PermissionStatus, PermissionType};
 
 pub fn check_calendar() -> Result<PermissionStatus, PermissionError> {
@@ -40,16 +41,22 @@ pub fn check_nearby_interaction() -> Result<PermissionStatus, PermissionError> {
 fn check_app_capability(capability_name: &str) -> Result<PermissionStatus, PermissionError> {
     #[cfg(target_os = "windows")]
     {
-        match AppCapability::Create(&windows::core::HSTRING::from(capability_name)) {
-            Ok(capability) => match capability.CheckAccess() {
-                Ok(status) => Ok(convert_app_capability_status(status)),
-                Err(_) => Ok(PermissionStatus::Denied),
+        let checked = AppCapability::Create(&windows::core::HSTRING::from(capability_name))
+            .and_then(|capability| capability.CheckAccess());
+        match checked {
+            Ok(status) => {
+                let status = convert_app_capability_status(status);
+                report::record(Confidence::Verified, "AppCapability.CheckAccess", capability_name, status);
+                Ok(status)
+            },
+            Err(e) => {
+                report::record(Confidence::Assumed, "AppCapability.CheckAccess", capability_name, e);
+                Ok(PermissionStatus::Denied)
             },
-            Err(_) => Ok(PermissionStatus::Denied),
         }
     }
     #[cfg(not(target_os = "windows"))]
-    Ok(PermissionStatus::Authorized)
+    Ok(PermissionStatus::NotApplicable)
 }
 
 pub fn request_calendar(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
@@ -86,12 +93,12 @@ fn request_app_capability(
         let result = match AppCapability::Create(&windows::core::HSTRING::from(capability_name)) {
             Ok(capability) => match capability.CheckAccess() {
                 Ok(status) => Ok(convert_app_capability_status(status)),
-                Err(e) => Err(PermissionError::SystemError(format!(
+                Err(e) => Err(PermissionError::system(format!(
                     "Windows Runtime operation failed: {}",
                     e
                 ))),
             },
-            Err(e) => Err(PermissionError::SystemError(format!(
+            Err(e) => Err(PermissionError::system(format!(
                 "Windows Runtime operation failed: {}",
                 e
             ))),
@@ -100,6 +107,6 @@ fn request_app_capability(
     }
     #[cfg(not(target_os = "windows"))]
     {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+        tx.send(Ok(PermissionStatus::NotApplicable)).ok();
     }
 }
@@ -29,7 +29,7 @@
 //!     with_com(|| {
 //!         AppCapability::CreateForCapabilityName(&"webcam".into())
 //!             .map(|cap| convert_status(cap.AccessStatus()?))
-//!             .map_err(|e| PermissionError::PlatformError(format!("Camera check failed: {:?}", e)))
+//!             .map_err(|e| PermissionError::platform(format!("Camera check failed: {:?}", e)))
 //!     })
 //! }
 //! ```
@@ -149,7 +149,7 @@ impl Drop for ComGuard {
 ///                 // Process async future...
 ///                 Ok(PermissionStatus::Authorized)
 ///             }
-///             Err(e) => Err(PermissionError::PlatformError(format!("Location check failed: {:?}", e)))
+///             Err(e) => Err(PermissionError::platform(format!("Location check failed: {:?}", e)))
 ///         }
 ///     })
 /// }
@@ -10,6 +10,7 @@ use {
     windows::core::Result as WinResult,
 };
 
+use crate::report::{self, Confidence};
 use crate::types::{PermissionError, PermissionStatus, PermissionType};
 
 pub fn check_location() -> Result<PermissionStatus, PermissionError> {
@@ -19,14 +20,19 @@ pub fn check_location() -> Result<PermissionStatus, PermissionError> {
         let rt = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
-            .map_err(|e| PermissionError::SystemError(format!("Failed to create tokio runtime: {}", e)))?;
+            .map_err(|e| PermissionError::system(format!("Failed to create tokio runtime: {}", e)))?;
 
-        let status = match rt.block_on(async {
+        let access = rt.block_on(async {
             match Geolocator::RequestAccessAsync() {
                 Ok(future) => future.await,
                 Err(e) => Err(e),
             }
-        }) {
+        });
+        match &access {
+            Ok(access) => report::record(Confidence::Verified, "Geolocator.RequestAccessAsync", "location", format!("{:?}", access)),
+            Err(e) => report::record(Confidence::Assumed, "Geolocator.RequestAccessAsync", "location", e),
+        }
+        let status = match access {
             Ok(GeolocationAccessStatus::Allowed) => PermissionStatus::Authorized,
             Ok(GeolocationAccessStatus::Denied) => PermissionStatus::Denied,
             Ok(GeolocationAccessStatus::Unspecified) => PermissionStatus::NotDetermined,
@@ -36,7 +42,7 @@ pub fn check_location() -> Result<PermissionStatus, PermissionError> {
         Ok(status)
     }
     #[cfg(not(target_os = "windows"))]
-    Ok(PermissionStatus::Authorized)
+    Ok(PermissionStatus::NotApplicable)
 }
 
 pub fn check_bluetooth() -> Result<PermissionStatus, PermissionError> {
@@ -46,7 +52,7 @@ pub fn check_bluetooth() -> Result<PermissionStatus, PermissionError> {
         let rt = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
-            .map_err(|e| PermissionError::SystemError(format!("Failed to create tokio runtime: {}", e)))?;
+            .map_err(|e| PermissionError::system(format!("Failed to create tokio runtime: {}", e)))?;
 
         let status = match rt.block_on(async {
             match BluetoothAdapter::GetDefaultAsync() {
@@ -82,7 +88,7 @@ pub fn check_bluetooth() -> Result<PermissionStatus, PermissionError> {
         Ok(status)
     }
     #[cfg(not(target_os = "windows"))]
-    Ok(PermissionStatus::Authorized)
+    Ok(PermissionStatus::NotApplicable)
 }
 
 pub fn check_wifi() -> Result<PermissionStatus, PermissionError> {
@@ -92,14 +98,19 @@ pub fn check_wifi() -> Result<PermissionStatus, PermissionError> {
         let rt = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
-            .map_err(|e| PermissionError::SystemError(format!("Failed to create tokio runtime: {}", e)))?;
+            .map_err(|e| PermissionError::system(format!("Failed to create tokio runtime: {}", e)))?;
 
-        let status = match rt.block_on(async {
+        let access = rt.block_on(async {
             match WiFiAdapter::RequestAccessAsync() {
                 Ok(future) => future.await,
                 Err(e) => Err(e),
             }
-        }) {
+        });
+        match &access {
+            Ok(access) => report::record(Confidence::Verified, "WiFiAdapter.RequestAccessAsync", "wiFiControl", format!("{:?}", access)),
+            Err(e) => report::record(Confidence::Assumed, "WiFiAdapter.RequestAccessAsync", "wiFiControl", e),
+        }
+        let status = match access {
             Ok(WiFiAccessStatus::Allowed) => PermissionStatus::Authorized,
             Ok(WiFiAccessStatus::DeniedBySystem) => PermissionStatus::Denied,
             Ok(WiFiAccessStatus::DeniedByUser) => PermissionStatus::Denied,
@@ -109,7 +120,7 @@ pub fn check_wifi() -> Result<PermissionStatus, PermissionError> {
         Ok(status)
     }
     #[cfg(not(target_os = "windows"))]
-    Ok(PermissionStatus::Authorized)
+    Ok(PermissionStatus::NotApplicable)
 }
 
 pub fn request_location(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
@@ -124,11 +135,11 @@ pub fn request_location(tx: oneshot::Sender<Result<PermissionStatus, PermissionE
             let result = rt.block_on(async {
                 let status = Geolocator::RequestAccessAsync()
                     .map_err(|e| {
-                        PermissionError::SystemError(format!("Failed to request location: {}", e))
+                        PermissionError::system(format!("Failed to request location: {}", e))
                     })?
                     .await
                     .map_err(|e| {
-                        PermissionError::SystemError(format!("Location request failed: {}", e))
+                        PermissionError::system(format!("Location request failed: {}", e))
                     })?;
 
                 Ok(convert_geolocation_status(status))
@@ -139,7 +150,7 @@ pub fn request_location(tx: oneshot::Sender<Result<PermissionStatus, PermissionE
     }
     #[cfg(not(target_os = "windows"))]
     {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+        tx.send(Ok(PermissionStatus::NotApplicable)).ok();
     }
 }
 
@@ -173,7 +184,7 @@ pub fn request_bluetooth(tx: oneshot::Sender<Result<PermissionStatus, Permission
                                                     }
                                                 },
                                                 Err(e) => Err(
-                                                    PermissionError::SystemError(format!(
+                                                    PermissionError::system(format!(
                                                         "Failed to check classic Bluetooth support: {}",
                                                         e
                                                     )),
@@ -181,19 +192,19 @@ pub fn request_bluetooth(tx: oneshot::Sender<Result<PermissionStatus, Permission
                                             }
                                         }
                                     },
-                                    Err(e) => Err(PermissionError::SystemError(format!(
+                                    Err(e) => Err(PermissionError::system(format!(
                                         "Failed to check Bluetooth LE support: {}",
                                         e
                                     ))),
                                 }
                             },
-                            Err(e) => Err(PermissionError::SystemError(format!(
+                            Err(e) => Err(PermissionError::system(format!(
                                 "Bluetooth adapter request failed: {}",
                                 e
                             ))),
                         }
                     },
-                    Err(e) => Err(PermissionError::SystemError(format!(
+                    Err(e) => Err(PermissionError::system(format!(
                         "Failed to request Bluetooth adapter: {}",
                         e
                     ))),
@@ -205,7 +216,7 @@ pub fn request_bluetooth(tx: oneshot::Sender<Result<PermissionStatus, Permission
     }
     #[cfg(not(target_os = "windows"))]
     {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+        tx.send(Ok(PermissionStatus::NotApplicable)).ok();
     }
 }
 
@@ -225,12 +236,12 @@ pub fn request_wifi(tx: oneshot::Sender<Result<PermissionStatus, PermissionError
                         Ok(WiFiAccessStatus::DeniedBySystem) => Ok(PermissionStatus::Denied),
                         Ok(WiFiAccessStatus::DeniedByUser) => Ok(PermissionStatus::Denied),
                         Ok(_) => Ok(PermissionStatus::NotDetermined),
-                        Err(e) => Err(PermissionError::SystemError(format!(
+                        Err(e) => Err(PermissionError::system(format!(
                             "WiFi access request failed: {}",
                             e
                         ))),
                     },
-                    Err(e) => Err(PermissionError::SystemError(format!(
+                    Err(e) => Err(PermissionError::system(format!(
                         "Failed to request WiFi access: {}",
                         e
                     ))),
@@ -241,7 +252,7 @@ pub fn request_wifi(tx: oneshot::Sender<Result<PermissionStatus, PermissionError
     }
     #[cfg(not(target_os = "windows"))]
     {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+        tx.send(Ok(PermissionStatus::NotApplicable)).ok();
     }
 }
 
@@ -27,7 +27,7 @@ pub fn check_documents() -> Result<PermissionStatus, PermissionError> {
         }
     }
     #[cfg(not(target_os = "windows"))]
-    Ok(PermissionStatus::Authorized)
+    Ok(PermissionStatus::NotApplicable)
 }
 
 pub fn check_network_volumes() -> Result<PermissionStatus, PermissionError> {
@@ -58,7 +58,7 @@ pub fn check_network_volumes() -> Result<PermissionStatus, PermissionError> {
         }
     }
     #[cfg(not(target_os = "windows"))]
-    Ok(PermissionStatus::Authorized)
+    Ok(PermissionStatus::NotApplicable)
 }
 
 pub fn check_removable_volumes() -> Result<PermissionStatus, PermissionError> {
@@ -91,7 +91,7 @@ pub fn check_removable_volumes() -> Result<PermissionStatus, PermissionError> {
         }
     }
     #[cfg(not(target_os = "windows"))]
-    Ok(PermissionStatus::Authorized)
+    Ok(PermissionStatus::NotApplicable)
 }
 
 pub fn request_documents(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
@@ -100,12 +100,12 @@ pub fn request_documents(tx: oneshot::Sender<Result<PermissionStatus, Permission
         let result = match AppCapability::Create(&windows::core::HSTRING::from("documentsLibrary")) {
             Ok(capability) => match capability.CheckAccess() {
                 Ok(status) => Ok(convert_app_capability_status(status)),
-                Err(e) => Err(PermissionError::SystemError(format!(
+                Err(e) => Err(PermissionError::system(format!(
                     "Windows Runtime operation failed: {}",
                     e
                 ))),
             },
-            Err(e) => Err(PermissionError::SystemError(format!(
+            Err(e) => Err(PermissionError::system(format!(
                 "Windows Runtime operation failed: {}",
                 e
             ))),
@@ -114,7 +114,7 @@ pub fn request_documents(tx: oneshot::Sender<Result<PermissionStatus, Permission
     }
     #[cfg(not(target_os = "windows"))]
     {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+        tx.send(Ok(PermissionStatus::NotApplicable)).ok();
     }
 }
 
@@ -152,7 +152,7 @@ pub fn request_network_volumes(tx: oneshot::Sender<Result<PermissionStatus, Perm
     }
     #[cfg(not(target_os = "windows"))]
     {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+        tx.send(Ok(PermissionStatus::NotApplicable)).ok();
     }
 }
 
@@ -190,6 +190,6 @@ pub fn request_removable_volumes(tx: oneshot::Sender<Result<PermissionStatus, Pe
     }
     #[cfg(not(target_os = "windows"))]
     {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+        tx.send(Ok(PermissionStatus::NotApplicable)).ok();
     }
 }
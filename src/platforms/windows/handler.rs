@@ -0,0 +1,203 @@
+//! Windows permission handler dispatching to WinRT and Win32 capability probes
+
+use tokio::sync::oneshot;
+
+use super::{
+    accessibility, app_capabilities, connectivity, filesystem, media, notification_permissions,
+    platform_specific, remediation, system,
+};
+use crate::remediation::Remediation;
+use crate::traits::PermissionHandler;
+use crate::types::{PermissionError, PermissionStatus, PermissionType, Support};
+
+/// Windows-specific permission handler
+///
+/// Requests ignore [`RequestOptions`](crate::RequestOptions) apart from the timeout
+/// and cancellation applied by the manager: WinRT consent prompts accept no owner
+/// window, explanation or location settings.
+pub struct WindowsHandler;
+
+impl Default for WindowsHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WindowsHandler {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl PermissionHandler for WindowsHandler {
+    fn name(&self) -> &'static str {
+        "windows"
+    }
+
+    fn support(&self, typ: PermissionType) -> Support {
+        let via = match typ {
+            PermissionType::Camera
+            | PermissionType::Microphone
+            | PermissionType::Location
+            | PermissionType::WiFi
+            | PermissionType::Calendar
+            | PermissionType::Reminders
+            | PermissionType::Contacts
+            | PermissionType::AddressBook
+            | PermissionType::Photos
+            | PermissionType::PhotosAdd
+            | PermissionType::MediaLibrary
+            | PermissionType::Motion
+            | PermissionType::NearbyInteraction
+            | PermissionType::Notification => return Support::Native,
+
+            PermissionType::SpeechRecognition => "microphone capability",
+            PermissionType::Bluetooth => "default Bluetooth adapter",
+            PermissionType::Accessibility | PermissionType::AccessibilityMouse => {
+                "UI Automation availability"
+            },
+            PermissionType::DesktopFolder
+            | PermissionType::DocumentsFolder
+            | PermissionType::DownloadsFolder => "documentsLibrary capability",
+            PermissionType::NetworkVolumes => "mapped network drives",
+            PermissionType::RemovableVolumes => "removable drives",
+            PermissionType::ScreenCapture | PermissionType::RemoteDesktop => {
+                "Graphics Capture support"
+            },
+            PermissionType::InputMonitoring => "raw input registration",
+            PermissionType::FullDiskAccess | PermissionType::AdminFiles => {
+                "Administrators group membership"
+            },
+
+            PermissionType::All
+            | PermissionType::AppleEvents
+            | PermissionType::PostEvent
+            | PermissionType::DeveloperTools
+            | PermissionType::FileProviderDomain
+            | PermissionType::FileProviderPresence
+            | PermissionType::UbiquitousFileProvider
+            | PermissionType::WillfulWrite
+            | PermissionType::Calls
+            | PermissionType::FaceID
+            | PermissionType::FocusStatus
+            | PermissionType::Siri => return Support::NotApplicable,
+        };
+        Support::Emulated { via }
+    }
+
+    fn remediation(&self, typ: PermissionType) -> Remediation {
+        remediation::remediation(typ)
+    }
+
+    fn check_permission(&self, typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
+        match typ {
+            // Media permissions
+            PermissionType::Camera => media::check_camera(),
+            PermissionType::Microphone => media::check_microphone(),
+            PermissionType::SpeechRecognition => media::check_speech_recognition(),
+
+            // Connectivity permissions
+            PermissionType::Location => connectivity::check_location(),
+            PermissionType::Bluetooth => connectivity::check_bluetooth(),
+            PermissionType::WiFi => connectivity::check_wifi(),
+
+            // Accessibility permissions
+            PermissionType::Accessibility | PermissionType::AccessibilityMouse => {
+                accessibility::check_accessibility()
+            },
+
+            // App capability permissions
+            PermissionType::Calendar | PermissionType::Reminders => {
+                app_capabilities::check_calendar()
+            },
+            PermissionType::Contacts | PermissionType::AddressBook => {
+                app_capabilities::check_contacts()
+            },
+            PermissionType::Photos | PermissionType::PhotosAdd => app_capabilities::check_photos(),
+            PermissionType::MediaLibrary => app_capabilities::check_media_library(),
+            PermissionType::Motion => app_capabilities::check_motion(),
+            PermissionType::NearbyInteraction => app_capabilities::check_nearby_interaction(),
+
+            // Filesystem permissions
+            PermissionType::DesktopFolder
+            | PermissionType::DocumentsFolder
+            | PermissionType::DownloadsFolder => filesystem::check_documents(),
+            PermissionType::NetworkVolumes => filesystem::check_network_volumes(),
+            PermissionType::RemovableVolumes => filesystem::check_removable_volumes(),
+
+            // System permissions
+            PermissionType::ScreenCapture | PermissionType::RemoteDesktop => {
+                system::check_screen_capture()
+            },
+            PermissionType::InputMonitoring => system::check_input_monitoring(),
+            PermissionType::FullDiskAccess | PermissionType::AdminFiles => {
+                system::check_admin_access()
+            },
+
+            // Notification permission
+            PermissionType::Notification => notification_permissions::check_permission(),
+
+            // Platform-specific permissions
+            _ => platform_specific::check_platform_specific(typ),
+        }
+    }
+
+    fn request_permission(
+        &self,
+        typ: PermissionType,
+        tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        match typ {
+            // Media permissions
+            PermissionType::Camera => media::request_camera(tx),
+            PermissionType::Microphone => media::request_microphone(tx),
+            PermissionType::SpeechRecognition => media::request_speech_recognition(tx),
+
+            // Connectivity permissions
+            PermissionType::Location => connectivity::request_location(tx),
+            PermissionType::Bluetooth => connectivity::request_bluetooth(tx),
+            PermissionType::WiFi => connectivity::request_wifi(tx),
+
+            // Accessibility permissions
+            PermissionType::Accessibility | PermissionType::AccessibilityMouse => {
+                accessibility::request_accessibility(tx)
+            },
+
+            // App capability permissions
+            PermissionType::Calendar | PermissionType::Reminders => {
+                app_capabilities::request_calendar(tx)
+            },
+            PermissionType::Contacts | PermissionType::AddressBook => {
+                app_capabilities::request_contacts(tx)
+            },
+            PermissionType::Photos | PermissionType::PhotosAdd => {
+                app_capabilities::request_photos(tx)
+            },
+            PermissionType::MediaLibrary => app_capabilities::request_media_library(tx),
+            PermissionType::Motion => app_capabilities::request_motion(tx),
+            PermissionType::NearbyInteraction => app_capabilities::request_nearby_interaction(tx),
+
+            // Filesystem permissions
+            PermissionType::DesktopFolder
+            | PermissionType::DocumentsFolder
+            | PermissionType::DownloadsFolder => filesystem::request_documents(tx),
+            PermissionType::NetworkVolumes => filesystem::request_network_volumes(tx),
+            PermissionType::RemovableVolumes => filesystem::request_removable_volumes(tx),
+
+            // System permissions
+            PermissionType::ScreenCapture | PermissionType::RemoteDesktop => {
+                system::request_screen_capture(tx)
+            },
+            PermissionType::InputMonitoring => system::request_input_monitoring(tx),
+            PermissionType::FullDiskAccess | PermissionType::AdminFiles => {
+                system::request_admin_access(tx)
+            },
+
+            // Notification permission
+            PermissionType::Notification => notification_permissions::request_permission(tx),
+
+            // Platform-specific permissions
+            _ => platform_specific::request_platform_specific(typ, tx),
+        }
+    }
+}
@@ -17,5 +17,5 @@ pub fn convert_app_capability_status(status: AppCapabilityAccessStatus) -> Permi
 
 #[cfg(not(target_os = "windows"))]
 pub fn convert_app_capability_status(_status: i32) -> PermissionStatus {
-    PermissionStatus::Authorized
+    PermissionStatus::NotApplicable
 }
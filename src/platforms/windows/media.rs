@@ -12,6 +12,7 @@ use {
 };
 
 use super::helpers::convert_app_capability_status;
+use crate::report::{self, Confidence};
 use crate::types::{PermissionError, PermissionStatus, PermissionType};
 
 pub fn check_camera() -> Result<PermissionStatus, PermissionError> {
@@ -19,18 +20,22 @@ pub fn check_camera() -> Result<PermissionStatus, PermissionError> {
     {
         match AppCapability::Create(&windows::core::HSTRING::from("webcam")) {
             Ok(capability) => match capability.CheckAccess() {
-                Ok(status) => Ok(convert_app_capability_status(status)),
-                Err(_) => Err(PermissionError::SystemError(
+                Ok(status) => {
+                    let status = convert_app_capability_status(status);
+                    report::record(Confidence::Verified, "AppCapability.CheckAccess", "webcam", status);
+                    Ok(status)
+                },
+                Err(_) => Err(PermissionError::system(
                     "Failed to get camera status".to_string(),
                 )),
             },
-            Err(_) => Err(PermissionError::SystemError(
+            Err(_) => Err(PermissionError::system(
                 "Failed to create camera capability".to_string(),
             )),
         }
     }
     #[cfg(not(target_os = "windows"))]
-    Ok(PermissionStatus::Authorized)
+    Ok(PermissionStatus::NotApplicable)
 }
 
 pub fn check_microphone() -> Result<PermissionStatus, PermissionError> {
@@ -38,18 +43,22 @@ pub fn check_microphone() -> Result<PermissionStatus, PermissionError> {
     {
         match AppCapability::Create(&windows::core::HSTRING::from("microphone")) {
             Ok(capability) => match capability.CheckAccess() {
-                Ok(status) => Ok(convert_app_capability_status(status)),
-                Err(_) => Err(PermissionError::SystemError(
+                Ok(status) => {
+                    let status = convert_app_capability_status(status);
+                    report::record(Confidence::Verified, "AppCapability.CheckAccess", "microphone", status);
+                    Ok(status)
+                },
+                Err(_) => Err(PermissionError::system(
                     "Failed to get microphone status".to_string(),
                 )),
             },
-            Err(_) => Err(PermissionError::SystemError(
+            Err(_) => Err(PermissionError::system(
                 "Failed to create microphone capability".to_string(),
             )),
         }
     }
     #[cfg(not(target_os = "windows"))]
-    Ok(PermissionStatus::Authorized)
+    Ok(PermissionStatus::NotApplicable)
 }
 
 pub fn check_speech_recognition() -> Result<PermissionStatus, PermissionError> {
@@ -57,18 +66,22 @@ pub fn check_speech_recognition() -> Result<PermissionStatus, PermissionError> {
     {
         match AppCapability::Create(&windows::core::HSTRING::from("microphone")) {
             Ok(capability) => match capability.CheckAccess() {
-                Ok(status) => Ok(convert_app_capability_status(status)),
-                Err(_) => Err(PermissionError::SystemError(
+                Ok(status) => {
+                    let status = convert_app_capability_status(status);
+                    report::record(Confidence::Verified, "AppCapability.CheckAccess", "microphone", status);
+                    Ok(status)
+                },
+                Err(_) => Err(PermissionError::system(
                     "Failed to get speech recognition status".to_string(),
                 )),
             },
-            Err(_) => Err(PermissionError::SystemError(
+            Err(_) => Err(PermissionError::system(
                 "Failed to create speech recognition capability".to_string(),
             )),
         }
     }
     #[cfg(not(target_os = "windows"))]
-    Ok(PermissionStatus::Authorized)
+    Ok(PermissionStatus::NotApplicable)
 }
 
 pub fn request_camera(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
@@ -92,13 +105,13 @@ fn request_media_capture(tx: oneshot::Sender<Result<PermissionStatus, Permission
             
             let result = rt.block_on(async {
                 let settings = MediaCaptureInitializationSettings::new().map_err(|e| {
-                    PermissionError::SystemError(format!(
+                    PermissionError::system(format!(
                         "Failed to create {media_type} settings: {e}"
                     ))
                 })?;
 
                 let capture = MediaCapture::new().map_err(|e| {
-                    PermissionError::SystemError(format!(
+                    PermissionError::system(format!(
                         "Failed to create {media_type} MediaCapture: {e}"
                     ))
                 })?;
@@ -106,13 +119,13 @@ fn request_media_capture(tx: oneshot::Sender<Result<PermissionStatus, Permission
                 capture
                     .InitializeWithSettingsAsync(&settings)
                     .map_err(|e| {
-                        PermissionError::SystemError(format!(
+                        PermissionError::system(format!(
                             "Failed to initialize {media_type}: {e}"
                         ))
                     })?
                     .await
                     .map_err(|e| {
-                        PermissionError::SystemError(format!(
+                        PermissionError::system(format!(
                             "{media_type} initialization failed: {e}"
                         ))
                     })?;
@@ -125,7 +138,7 @@ fn request_media_capture(tx: oneshot::Sender<Result<PermissionStatus, Permission
     }
     #[cfg(not(target_os = "windows"))]
     {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+        tx.send(Ok(PermissionStatus::NotApplicable)).ok();
     }
 }
 
@@ -141,14 +154,14 @@ pub fn request_speech_recognition(tx: oneshot::Sender<Result<PermissionStatus, P
             
             let result = rt.block_on(async {
                 let settings = MediaCaptureInitializationSettings::new().map_err(|e| {
-                    PermissionError::SystemError(format!("Failed to create speech settings: {}", e))
+                    PermissionError::system(format!("Failed to create speech settings: {}", e))
                 })?;
 
                 // Set audio-only capture mode for speech
                 settings
                     .SetStreamingCaptureMode(windows::Media::Capture::StreamingCaptureMode::Audio)
                     .map_err(|e| {
-                        PermissionError::SystemError(format!(
+                        PermissionError::system(format!(
                             "Failed to set speech capture mode: {}",
                             e
                         ))
@@ -158,14 +171,14 @@ pub fn request_speech_recognition(tx: oneshot::Sender<Result<PermissionStatus, P
                 settings
                     .SetMediaCategory(windows::Media::Capture::MediaCategory::Speech)
                     .map_err(|e| {
-                        PermissionError::SystemError(format!(
+                        PermissionError::system(format!(
                             "Failed to set speech category: {}",
                             e
                         ))
                     })?;
 
                 let capture = MediaCapture::new().map_err(|e| {
-                    PermissionError::SystemError(format!(
+                    PermissionError::system(format!(
                         "Failed to create speech MediaCapture: {}",
                         e
                     ))
@@ -174,14 +187,14 @@ pub fn request_speech_recognition(tx: oneshot::Sender<Result<PermissionStatus, P
                 capture
                     .InitializeWithSettingsAsync(&settings)
                     .map_err(|e| {
-                        PermissionError::SystemError(format!(
+                        PermissionError::system(format!(
                             "Failed to initialize speech capture: {}",
                             e
                         ))
                     })?
                     .await
                     .map_err(|e| {
-                        PermissionError::SystemError(format!(
+                        PermissionError::system(format!(
                             "Speech capture initialization failed: {}",
                             e
                         ))
@@ -195,6 +208,6 @@ pub fn request_speech_recognition(tx: oneshot::Sender<Result<PermissionStatus, P
     }
     #[cfg(not(target_os = "windows"))]
     {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+        tx.send(Ok(PermissionStatus::NotApplicable)).ok();
     }
 }
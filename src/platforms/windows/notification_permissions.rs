@@ -3,7 +3,8 @@
 //! Supports configurable Application User Model IDs (AUMIDs) to ensure
 //! notifications display the correct application name instead of "Windows PowerShell".
 
-use crate::types::{PermissionError, PermissionStatus};
+use crate::report::{self, Confidence};
+use crate::types::{ErrorKind, PermissionError, PermissionStatus};
 use std::sync::OnceLock;
 use tokio::sync::oneshot;
 
@@ -27,7 +28,7 @@ where
     F: FnOnce() -> Result<T, PermissionError>,
 {
     super::com_init::with_com(|| f())
-        .map_err(|e| PermissionError::PlatformError(format!("COM initialization failed: {:?}", e)))?
+        .map_err(|e| PermissionError::platform(format!("COM initialization failed: {:?}", e)))?
 }
 
 /// Default fallback AUMID when no custom ID is configured
@@ -90,8 +91,8 @@ pub fn check_permission() -> Result<PermissionStatus, PermissionError> {
     {
         // Check Windows version compatibility (Toast requires Windows 10+)
         if OsVersion::current() < OsVersion::new(10, 0, 0, 0) {
-            return Err(PermissionError::SystemError(
-                "Toast notifications require Windows 10 or later. Current OS version does not support this feature.".to_string()
+            return Err(PermissionError::new(ErrorKind::NotSupportedOnPlatform).with_message(
+                "Toast notifications require Windows 10 or later. Current OS version does not support this feature."
             ));
         }
 
@@ -104,7 +105,7 @@ pub fn check_permission() -> Result<PermissionStatus, PermissionError> {
             let result = with_com(|| {
                 match Toast::new(&app_id) {
                     Ok(_) => Ok(PermissionStatus::Authorized),
-                    Err(e) => Err(PermissionError::PlatformError(format!("Toast creation failed: {:?}", e)))
+                    Err(e) => Err(PermissionError::platform(format!("Toast creation failed: {:?}", e)))
                 }
             });
             
@@ -117,7 +118,7 @@ pub fn check_permission() -> Result<PermissionStatus, PermissionError> {
                         with_com(|| {
                             match Toast::new(FALLBACK_APP_ID) {
                                 Ok(_) => Ok(PermissionStatus::Authorized),
-                                Err(_) => Err(PermissionError::PlatformError("Fallback Toast creation failed".to_string()))
+                                Err(_) => Err(PermissionError::platform("Fallback Toast creation failed".to_string()))
                             }
                         }).unwrap_or(PermissionStatus::Denied)
                     } else {
@@ -127,12 +128,15 @@ pub fn check_permission() -> Result<PermissionStatus, PermissionError> {
             }
         });
         
+        // Creating a toast shows notifications can be built, not that the user allows them
+        report::record(Confidence::Heuristic, "Toast::new", "toast notifications", status);
+
         // All subsequent calls: Return cached value (zero allocations)
         Ok(*status)
     }
 
     #[cfg(not(target_os = "windows"))]
-    Ok(PermissionStatus::Authorized)
+    Ok(PermissionStatus::NotApplicable)
 }
 
 /// Request notification permission (Windows doesn't require user permission)
@@ -150,6 +154,6 @@ pub fn request_permission(tx: oneshot::Sender<Result<PermissionStatus, Permissio
     
     #[cfg(not(target_os = "windows"))]
     {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+        tx.send(Ok(PermissionStatus::NotApplicable)).ok();
     }
 }
@@ -0,0 +1,73 @@
+//! Remediation guidance pointing at `ms-settings:` pages
+
+use crate::remediation::Remediation;
+use crate::types::PermissionType;
+
+/// How the user grants `typ` on Windows
+pub fn remediation(typ: PermissionType) -> Remediation {
+    let page = match typ {
+        PermissionType::Camera => "privacy-webcam",
+        PermissionType::Microphone => "privacy-microphone",
+        PermissionType::SpeechRecognition => "privacy-speech",
+        PermissionType::Location => "privacy-location",
+        PermissionType::Calendar => "privacy-calendar",
+        PermissionType::Reminders => "privacy-tasks",
+        PermissionType::Contacts | PermissionType::AddressBook => "privacy-contacts",
+        PermissionType::Photos | PermissionType::PhotosAdd => "privacy-pictures",
+        PermissionType::MediaLibrary => "privacy-musiclibrary",
+        PermissionType::DocumentsFolder => "privacy-documents",
+        PermissionType::Motion => "privacy-motion",
+        PermissionType::Notification => "notifications",
+        PermissionType::Accessibility | PermissionType::AccessibilityMouse => "easeofaccess",
+        PermissionType::Bluetooth | PermissionType::WiFi | PermissionType::NearbyInteraction => {
+            "privacy-radios"
+        },
+        PermissionType::ScreenCapture => "privacy-graphicsCaptureProgrammatic",
+        PermissionType::RemoteDesktop => "remotedesktop",
+        // Broad file system access is read when the application starts
+        PermissionType::DesktopFolder
+        | PermissionType::DownloadsFolder
+        | PermissionType::NetworkVolumes
+        | PermissionType::RemovableVolumes => {
+            return Remediation::new(typ)
+                .with_step(
+                    "Open Settings > Privacy & security > File system and allow this application",
+                )
+                .with_settings_uri("ms-settings:privacy-broadfilesystemaccess")
+                .with_restart_required(true);
+        },
+        PermissionType::FullDiskAccess | PermissionType::AdminFiles => {
+            return Remediation::new(typ)
+                .with_step("Run the application as administrator")
+                .with_step("Or add the user to the Administrators group and sign in again")
+                .with_command("net localgroup Administrators \"%USERNAME%\" /add")
+                .with_relogin_required(true);
+        },
+        PermissionType::InputMonitoring => {
+            return Remediation::new(typ).with_step(
+                "Run the application at the same or a higher integrity level than the \
+                 applications whose input it monitors",
+            );
+        },
+
+        PermissionType::All
+        | PermissionType::AppleEvents
+        | PermissionType::PostEvent
+        | PermissionType::DeveloperTools
+        | PermissionType::FileProviderDomain
+        | PermissionType::FileProviderPresence
+        | PermissionType::UbiquitousFileProvider
+        | PermissionType::WillfulWrite
+        | PermissionType::Calls
+        | PermissionType::FaceID
+        | PermissionType::FocusStatus
+        | PermissionType::Siri => return Remediation::new(typ),
+    };
+
+    Remediation::new(typ)
+        .with_step(format!(
+            "Open Settings > Privacy & security > {} and allow this application",
+            typ
+        ))
+        .with_settings_uri(format!("ms-settings:{}", page))
+}
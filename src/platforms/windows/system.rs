@@ -41,7 +41,7 @@ pub fn check_screen_capture() -> Result<PermissionStatus, PermissionError> {
         }
     }
     #[cfg(not(target_os = "windows"))]
-    Ok(PermissionStatus::Authorized)
+    Ok(PermissionStatus::NotApplicable)
 }
 
 pub fn check_input_monitoring() -> Result<PermissionStatus, PermissionError> {
@@ -69,7 +69,7 @@ pub fn check_input_monitoring() -> Result<PermissionStatus, PermissionError> {
         }
     }
     #[cfg(not(target_os = "windows"))]
-    Ok(PermissionStatus::Authorized)
+    Ok(PermissionStatus::NotApplicable)
 }
 
 pub fn check_admin_access() -> Result<PermissionStatus, PermissionError> {
@@ -110,7 +110,7 @@ pub fn check_admin_access() -> Result<PermissionStatus, PermissionError> {
         }
     }
     #[cfg(not(target_os = "windows"))]
-    Ok(PermissionStatus::Authorized)
+    Ok(PermissionStatus::NotApplicable)
 }
 
 pub fn request_screen_capture(tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>) {
@@ -125,7 +125,7 @@ pub fn request_screen_capture(tx: oneshot::Sender<Result<PermissionStatus, Permi
                         Ok(PermissionStatus::Denied)
                     }
                 },
-                Err(e) => Err(PermissionError::SystemError(format!(
+                Err(e) => Err(PermissionError::system(format!(
                     "Screen capture check failed: {}",
                     e
                 ))),
@@ -135,7 +135,7 @@ pub fn request_screen_capture(tx: oneshot::Sender<Result<PermissionStatus, Permi
     }
     #[cfg(not(target_os = "windows"))]
     {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+        tx.send(Ok(PermissionStatus::NotApplicable)).ok();
     }
 }
 
@@ -157,7 +157,7 @@ pub fn request_input_monitoring(tx: oneshot::Sender<Result<PermissionStatus, Per
                     std::mem::size_of::<RAWINPUTDEVICE>() as u32,
                 ) {
                     Ok(_) => Ok(PermissionStatus::Authorized),
-                    Err(e) => Err(PermissionError::SystemError(format!(
+                    Err(e) => Err(PermissionError::system(format!(
                         "Input monitoring registration failed: {}",
                         e
                     ))),
@@ -168,7 +168,7 @@ pub fn request_input_monitoring(tx: oneshot::Sender<Result<PermissionStatus, Per
     }
     #[cfg(not(target_os = "windows"))]
     {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+        tx.send(Ok(PermissionStatus::NotApplicable)).ok();
     }
 }
 
@@ -228,6 +228,6 @@ pub fn request_admin_access(tx: oneshot::Sender<Result<PermissionStatus, Permiss
     }
     #[cfg(not(target_os = "windows"))]
     {
-        tx.send(Ok(PermissionStatus::Authorized)).ok();
+        tx.send(Ok(PermissionStatus::NotApplicable)).ok();
     }
 }
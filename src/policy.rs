@@ -0,0 +1,381 @@
+//! Administrative policy that overrides OS answers
+//!
+//! A [`Policy`] forbids or pre-approves permission types, optionally only for
+//! particular tools or agents. [`PermissionManager`](crate::PermissionManager)
+//! consults it before the backend on every check and request: a forbidden
+//! permission reports [`PermissionStatus::Restricted`] and a pre-approved one
+//! [`PermissionStatus::Authorized`], without probing the OS or showing a prompt.
+//! The rule that decided is cited by
+//! [`check_permission_detailed`](crate::PermissionManager::check_permission_detailed)
+//! and [`policy_decision`](crate::PermissionManager::policy_decision).
+//!
+//! With the `toml` feature, [`PermissionManager::new`](crate::PermissionManager::new)
+//! loads the files at [`Policy::default_paths`]: a system-wide file managed by
+//! administrators, then one in the user's configuration directory
+//! (`$XDG_CONFIG_HOME` on Linux). Anything running as the user can write the
+//! latter, so only its `forbid` rules are enforced:
+//!
+//! ```toml
+//! [[rules]]
+//! name = "no-recording-for-agents"
+//! action = "forbid"
+//! permissions = ["camera", "microphone", "screen_capture"]
+//! agents = ["coder", "reviewer"]
+//!
+//! [[rules]]
+//! action = "approve"
+//! permissions = ["downloads_folder"]
+//! tools = ["fetch"]
+//! ```
+//!
+//! `"all"` matches every permission; a rule without `tools` or `agents` applies
+//! to every caller.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::types::{PermissionStatus, PermissionType};
+
+/// Identity of the tool and agent a manager acts for
+///
+/// Set with [`PermissionManager::with_caller`](crate::PermissionManager::with_caller)
+/// and matched against the `tools` and `agents` of policy rules.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Caller {
+    /// Name of the tool making the call
+    pub tool: Option<String>,
+    /// Name of the agent on whose behalf the tool runs
+    pub agent: Option<String>,
+}
+
+impl Caller {
+    /// Anonymous caller, matched only by rules that name no tools or agents
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the tool name
+    pub fn with_tool(mut self, tool: impl Into<String>) -> Self {
+        self.tool = Some(tool.into());
+        self
+    }
+
+    /// Set the agent name
+    pub fn with_agent(mut self, agent: impl Into<String>) -> Self {
+        self.agent = Some(agent.into());
+        self
+    }
+}
+
+/// What a policy rule does with the permissions it matches
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PolicyAction {
+    /// Report [`PermissionStatus::Restricted`] and never prompt
+    Forbid,
+    /// Report [`PermissionStatus::Authorized`] without asking the OS
+    Approve,
+}
+
+/// One rule of a [`Policy`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolicyRule {
+    /// Name cited when the rule decides a status
+    pub name: String,
+    /// Forbid or pre-approve
+    pub action: PolicyAction,
+    /// Permissions the rule covers; [`PermissionType::All`] covers every one
+    pub permissions: Vec<PermissionType>,
+    /// Tools the rule applies to; empty applies to every tool
+    pub tools: Vec<String>,
+    /// Agents the rule applies to; empty applies to every agent
+    pub agents: Vec<String>,
+    /// File the rule was read from, if any
+    pub origin: Option<PathBuf>,
+}
+
+impl PolicyRule {
+    /// Rule forbidding `permissions` for every caller
+    pub fn forbid(
+        name: impl Into<String>,
+        permissions: impl IntoIterator<Item = PermissionType>,
+    ) -> Self {
+        Self::new(name, PolicyAction::Forbid, permissions)
+    }
+
+    /// Rule pre-approving `permissions` for every caller
+    pub fn approve(
+        name: impl Into<String>,
+        permissions: impl IntoIterator<Item = PermissionType>,
+    ) -> Self {
+        Self::new(name, PolicyAction::Approve, permissions)
+    }
+
+    fn new(
+        name: impl Into<String>,
+        action: PolicyAction,
+        permissions: impl IntoIterator<Item = PermissionType>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            action,
+            permissions: permissions.into_iter().collect(),
+            tools: Vec::new(),
+            agents: Vec::new(),
+            origin: None,
+        }
+    }
+
+    /// Restrict the rule to `tool`, in addition to any tools already listed
+    pub fn for_tool(mut self, tool: impl Into<String>) -> Self {
+        self.tools.push(tool.into());
+        self
+    }
+
+    /// Restrict the rule to `agent`, in addition to any agents already listed
+    pub fn for_agent(mut self, agent: impl Into<String>) -> Self {
+        self.agents.push(agent.into());
+        self
+    }
+
+    /// Whether the rule applies to `typ` requested by `caller`
+    pub fn matches(&self, typ: PermissionType, caller: &Caller) -> bool {
+        let listed = |names: &[String], name: &Option<String>| {
+            names.is_empty() || name.as_ref().is_some_and(|name| names.contains(name))
+        };
+        self.permissions
+            .iter()
+            .any(|p| *p == typ || *p == PermissionType::All)
+            && listed(&self.tools, &caller.tool)
+            && listed(&self.agents, &caller.agent)
+    }
+}
+
+/// Rules that forbid or pre-approve permissions ahead of the backend
+///
+/// When several rules match, a forbidding rule wins over an approving one, and
+/// among rules with the same action the first one added decides.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Policy {
+    rules: Vec<PolicyRule>,
+}
+
+impl Policy {
+    /// Policy with no rules, which leaves every decision to the backend
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule
+    pub fn with_rule(mut self, rule: PolicyRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Append the rules of `other`
+    pub fn merge(&mut self, other: Policy) {
+        self.rules.extend(other.rules);
+    }
+
+    /// Rules in evaluation order
+    pub fn rules(&self) -> &[PolicyRule] {
+        &self.rules
+    }
+
+    /// Whether the policy has no rules
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Rule deciding `typ` for `caller`, if any
+    pub fn decide(&self, typ: PermissionType, caller: &Caller) -> Option<PolicyDecision> {
+        let matching = || self.rules.iter().filter(|rule| rule.matches(typ, caller));
+        matching()
+            .find(|rule| rule.action == PolicyAction::Forbid)
+            .or_else(|| matching().next())
+            .map(|rule| PolicyDecision { rule: rule.clone() })
+    }
+}
+
+/// A status decided by a policy rule instead of the backend
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolicyDecision {
+    /// The rule that decided
+    pub rule: PolicyRule,
+}
+
+impl PolicyDecision {
+    /// Status reported for the permission
+    pub fn status(&self) -> PermissionStatus {
+        match self.rule.action {
+            PolicyAction::Forbid => PermissionStatus::Restricted,
+            PolicyAction::Approve => PermissionStatus::Authorized,
+        }
+    }
+}
+
+impl fmt::Display for PolicyDecision {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let action = match self.rule.action {
+            PolicyAction::Forbid => "forbidden",
+            PolicyAction::Approve => "approved",
+        };
+        write!(f, "{} by policy rule `{}`", action, self.rule.name)?;
+        if let Some(origin) = &self.rule.origin {
+            write!(f, " in {}", origin.display())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "toml")]
+mod toml {
+    use std::path::{Path, PathBuf};
+
+    use toml_edit::TableLike;
+
+    use super::{Policy, PolicyAction, PolicyRule};
+    use crate::toml::{invalid, parse, permission, string, strings, tables};
+    use crate::types::{PermissionError, PermissionType};
+
+    /// File name of the policy inside each configuration directory
+    const FILE: &str = "permission-policy.toml";
+
+    fn rule(table: &dyn TableLike, index: usize) -> Result<PolicyRule, PermissionError> {
+        let action = match string(table, "action")? {
+            "forbid" => PolicyAction::Forbid,
+            "approve" => PolicyAction::Approve,
+            other => {
+                return Err(invalid(format!(
+                    "unknown action `{}`, expected `forbid` or `approve`",
+                    other
+                )));
+            },
+        };
+        let permissions = strings(table, "permissions")?
+            .into_iter()
+            .map(permission)
+            .collect::<Result<Vec<_>, _>>()?;
+        if permissions.is_empty() {
+            return Err(invalid("`permissions` must list at least one permission"));
+        }
+
+        let owned = |names: Vec<&str>| names.into_iter().map(str::to_string).collect();
+        Ok(PolicyRule {
+            name: match table.get("name") {
+                Some(_) => string(table, "name")?.to_string(),
+                None => format!("rules[{}]", index),
+            },
+            action,
+            permissions,
+            tools: owned(strings(table, "tools")?),
+            agents: owned(strings(table, "agents")?),
+            origin: None,
+        })
+    }
+
+    /// System-wide policy file managed by administrators
+    fn system_path() -> PathBuf {
+        #[cfg(target_os = "macos")]
+        return PathBuf::from("/Library/Application Support/kodegen").join(FILE);
+
+        #[cfg(target_os = "windows")]
+        return std::env::var_os("ProgramData")
+            .map_or_else(|| PathBuf::from(r"C:\ProgramData"), PathBuf::from)
+            .join("kodegen")
+            .join(FILE);
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        return PathBuf::from("/etc/kodegen").join(FILE);
+    }
+
+    /// Per-user policy file, which may only forbid
+    fn user_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("kodegen").join(FILE))
+    }
+
+    impl Policy {
+        /// Parse a policy from TOML
+        ///
+        /// Fails with [`ErrorKind::InvalidConfig`](crate::ErrorKind::InvalidConfig)
+        /// on malformed TOML, unknown actions or unknown permission identifiers.
+        pub fn from_toml(source: &str) -> Result<Self, PermissionError> {
+            let document = parse(source, "policy")?;
+            let Some(rules) = document.as_table().get("rules") else {
+                return Ok(Self::new());
+            };
+
+            let mut policy = Self::new();
+            for (index, table) in tables(rules, "rules")?.into_iter().enumerate() {
+                policy.rules.push(rule(table, index)?);
+            }
+            Ok(policy)
+        }
+
+        /// Read a policy file, recording `path` as the origin of its rules
+        pub fn from_file(path: impl AsRef<Path>) -> Result<Self, PermissionError> {
+            let path = path.as_ref();
+            let source = std::fs::read_to_string(path)?;
+            let mut policy =
+                Self::from_toml(&source).map_err(|e| e.with_probe(path.display().to_string()))?;
+            for rule in &mut policy.rules {
+                rule.origin = Some(path.to_path_buf());
+            }
+            Ok(policy)
+        }
+
+        /// Read a per-user policy file, keeping only its forbidding rules
+        ///
+        /// The file is writable by every process running as the user, including
+        /// the agents the policy constrains, so it may narrow what the OS allows
+        /// but never pre-approve anything: `approve` rules are dropped.
+        pub fn from_user_file(path: impl AsRef<Path>) -> Result<Self, PermissionError> {
+            let mut policy = Self::from_file(path)?;
+            policy.rules.retain(|rule| rule.action == PolicyAction::Forbid);
+            Ok(policy)
+        }
+
+        /// Policy files read by [`Policy::load`], system-wide first
+        ///
+        /// The second path, if any, is the per-user file, read with
+        /// [`Policy::from_user_file`].
+        pub fn default_paths() -> Vec<PathBuf> {
+            let mut paths = vec![system_path()];
+            paths.extend(user_path());
+            paths
+        }
+
+        /// Read and merge every file at [`Policy::default_paths`]
+        ///
+        /// Missing files are skipped. A file that exists but cannot be read or
+        /// parsed fails closed: it is replaced by a rule forbidding every
+        /// permission, named after the error, so a broken managed policy never
+        /// silently grants access.
+        pub fn load() -> Self {
+            let mut policy = Self::new();
+            let files = std::iter::once((system_path(), false))
+                .chain(user_path().map(|path| (path, true)));
+            for (path, per_user) in files {
+                if matches!(path.try_exists(), Ok(false)) {
+                    continue;
+                }
+                let file = if per_user {
+                    Self::from_user_file(&path)
+                } else {
+                    Self::from_file(&path)
+                };
+                match file {
+                    Ok(file) => policy.merge(file),
+                    Err(e) => {
+                        let mut rule = PolicyRule::forbid(
+                            format!("unreadable policy file: {}", e),
+                            [PermissionType::All],
+                        );
+                        rule.origin = Some(path);
+                        policy.rules.push(rule);
+                    },
+                }
+            }
+            policy
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Guidance for fixing denied permissions
+//!
+//! A `Denied` or `Restricted` status is only actionable if the user learns where
+//! to flip the switch, and that differs per platform and per sandbox: a group
+//! membership on Linux, a Flatpak override, an `ms-settings:` page on Windows, a
+//! Privacy & Security pane on macOS. [`PermissionManager::remediation`](crate::PermissionManager::remediation)
+//! returns that answer as a [`Remediation`] a UI can render or act on.
+
+use crate::types::PermissionType;
+
+/// How the user can grant a permission that was denied or restricted
+///
+/// Every part is optional: a backend may know a settings page but no command, and
+/// permissions the platform has no concept of come back [empty](Self::is_empty).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Remediation {
+    /// Permission the guidance is for
+    pub permission: PermissionType,
+    /// Instructions for the user, in the order they should be followed
+    pub steps: Vec<String>,
+    /// Shell commands that grant the permission, to be run by the user; they may
+    /// need administrator rights
+    pub commands: Vec<String>,
+    /// URIs opening the settings page where the permission is granted; empty on
+    /// Linux, whose desktops open their panels by command (see
+    /// [`PermissionManager::open_settings`](crate::PermissionManager::open_settings))
+    pub settings_uris: Vec<String>,
+    /// The user must log out and back in before the change takes effect
+    pub relogin_required: bool,
+    /// The application must be restarted before the change takes effect
+    pub restart_required: bool,
+}
+
+impl Remediation {
+    /// Guidance for `typ` with nothing to do yet
+    pub fn new(permission: PermissionType) -> Self {
+        Self {
+            permission,
+            steps: Vec::new(),
+            commands: Vec::new(),
+            settings_uris: Vec::new(),
+            relogin_required: false,
+            restart_required: false,
+        }
+    }
+
+    /// Add an instruction for the user
+    pub fn with_step(mut self, step: impl Into<String>) -> Self {
+        self.steps.push(step.into());
+        self
+    }
+
+    /// Add a shell command that grants the permission
+    pub fn with_command(mut self, command: impl Into<String>) -> Self {
+        self.commands.push(command.into());
+        self
+    }
+
+    /// Add a URI opening the relevant settings page
+    pub fn with_settings_uri(mut self, uri: impl Into<String>) -> Self {
+        self.settings_uris.push(uri.into());
+        self
+    }
+
+    /// Mark the change as taking effect only after logging in again
+    pub fn with_relogin_required(mut self, required: bool) -> Self {
+        self.relogin_required = required;
+        self
+    }
+
+    /// Mark the change as taking effect only after restarting the application
+    pub fn with_restart_required(mut self, required: bool) -> Self {
+        self.restart_required = required;
+        self
+    }
+
+    /// Whether there is no guidance at all
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty() && self.commands.is_empty() && self.settings_uris.is_empty()
+    }
+}
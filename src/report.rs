@@ -0,0 +1,151 @@
+//! Status provenance for [`PermissionManager::check_permission_detailed`](crate::PermissionManager::check_permission_detailed)
+//!
+//! Probes describe what they touched through [`record`] while a detailed check is
+//! collecting; outside a detailed check recording is a no-op. Custom backends can
+//! record their own probes the same way. The collector is task-local, so probes
+//! must run on the task performing the check: evidence recorded from a spawned
+//! task or a blocking thread is lost.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use crate::types::{PermissionError, PermissionStatus, PermissionType, Support};
+
+/// How far a reported status can be trusted
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Confidence {
+    /// No probe backed the status; it is a default or fallback answer
+    Assumed,
+    /// Derived from something related to the permission, such as a service being
+    /// reachable or a folder being readable
+    Heuristic,
+    /// Reported by the operating system's own permission records
+    Verified,
+}
+
+/// One probe run while determining a status
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Evidence {
+    /// What was done, e.g. `"PermissionStore.Lookup"`, `"open"` or `"D-Bus call"`
+    pub probe: &'static str,
+    /// Path, device node, bus name or table the probe touched
+    pub target: String,
+    /// What the probe returned
+    pub outcome: String,
+    /// Confidence of a status derived from this probe alone
+    pub confidence: Confidence,
+}
+
+/// Status of a permission together with how it was determined
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PermissionReport {
+    /// Permission that was checked
+    pub permission: PermissionType,
+    /// Resulting status
+    pub status: PermissionStatus,
+    /// How far `status` can be trusted
+    pub confidence: Confidence,
+    /// How the backend supports the permission
+    pub support: Support,
+    /// Probes that ran, in order
+    pub evidence: Vec<Evidence>,
+    /// Folder the status is about, as resolved by the backend, for permissions
+    /// guarding a user folder
+    pub path: Option<PathBuf>,
+}
+
+/// What a detailed check has collected so far
+#[derive(Default)]
+struct Collected {
+    evidence: Vec<Evidence>,
+    path: Option<PathBuf>,
+}
+
+tokio::task_local! {
+    static COLLECTED: RefCell<Collected>;
+}
+
+/// Record a probe run by the current detailed check, if any
+///
+/// Call it from [`PermissionHandler::check_permission`](crate::PermissionHandler::check_permission)
+/// or [`PermissionHandler::check_permission_async`](crate::PermissionHandler::check_permission_async)
+/// after each probe; the last recorded probe sets the report's confidence.
+pub fn record(
+    confidence: Confidence,
+    probe: &'static str,
+    target: impl Into<String>,
+    outcome: impl fmt::Display,
+) {
+    // Only pay for formatting when someone is collecting
+    let _ = COLLECTED.try_with(|collected| {
+        collected.borrow_mut().evidence.push(Evidence {
+            probe,
+            target: target.into(),
+            outcome: outcome.to_string(),
+            confidence,
+        });
+    });
+}
+
+/// Record the folder the current detailed check is about, if any
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) fn record_path(path: &Path) {
+    let _ = COLLECTED.try_with(|collected| {
+        collected.borrow_mut().path = Some(path.to_path_buf());
+    });
+}
+
+/// Describe the raw result of a probe: `"ok"` or the error
+#[cfg_attr(not(any(target_os = "linux", target_os = "macos")), allow(dead_code))]
+pub(crate) fn outcome<T, E: fmt::Display>(result: &Result<T, E>) -> impl fmt::Display + '_ {
+    struct Outcome<'a, T, E>(&'a Result<T, E>);
+
+    impl<T, E: fmt::Display> fmt::Display for Outcome<'_, T, E> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.0 {
+                Ok(_) => write!(f, "ok"),
+                Err(e) => write!(f, "{}", e),
+            }
+        }
+    }
+
+    Outcome(result)
+}
+
+/// Run `check` while collecting evidence, and build a report from its result
+///
+/// The last recorded probe decides the confidence, since fallbacks run after the
+/// probes they replace. A status no probe backed is only assumed, unless the
+/// permission does not apply on this platform at all.
+pub(crate) async fn collect(
+    typ: PermissionType,
+    support: Support,
+    check: impl Future<Output = Result<PermissionStatus, PermissionError>>,
+) -> Result<PermissionReport, PermissionError> {
+    let (status, Collected { evidence, path }) = COLLECTED
+        .scope(RefCell::default(), async {
+            let status = check.await;
+            (status, COLLECTED.with(RefCell::take))
+        })
+        .await;
+
+    let confidence = match (evidence.last(), support) {
+        (Some(last), _) => last.confidence,
+        (None, Support::NotApplicable) => Confidence::Verified,
+        (None, Support::Native | Support::Emulated { .. }) => Confidence::Assumed,
+    };
+    Ok(PermissionReport {
+        permission: typ,
+        status: status?,
+        confidence,
+        support,
+        evidence,
+        path,
+    })
+}
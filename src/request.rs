@@ -0,0 +1,125 @@
+//! Options controlling permission requests
+
+use std::fmt;
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+
+/// Options for [`PermissionManager::request_permission_with`](crate::PermissionManager::request_permission_with)
+///
+/// A request that times out fails with [`ErrorKind::Timeout`](crate::ErrorKind::Timeout);
+/// one whose token is cancelled fails with
+/// [`ErrorKind::Cancelled`](crate::ErrorKind::Cancelled).
+/// In both cases, and when the request future is dropped, the backend is told to
+/// dismiss the prompt where the platform allows it.
+///
+/// The parent window, reason and location settings are passed to the backend
+/// through [`PermissionHandler::request_permission_with`](crate::PermissionHandler::request_permission_with);
+/// backends ignore what their platform cannot express:
+///
+/// - Linux attaches location and screen capture portal dialogs to the parent
+///   window and passes the location settings to the Location portal.
+/// - macOS applies the location settings to Core Location. Its prompts are
+///   system-modal and explained by the app's `Info.plist`.
+/// - Windows consent prompts take none of these options.
+///
+/// No built-in backend shows the reason. When concurrent requests share one
+/// prompt, the options of the first request apply.
+#[derive(Clone, Debug, Default)]
+pub struct RequestOptions {
+    /// Give up after this long without an answer from the user
+    pub timeout: Option<Duration>,
+    /// Abort the request when this token is cancelled
+    pub cancel: Option<CancellationToken>,
+    /// Window the dialog belongs to, so it opens on top of it
+    pub parent_window: Option<WindowIdentifier>,
+    /// Why the permission is needed, for backends that can show it
+    pub reason: Option<String>,
+    /// Precision asked for by location requests
+    pub accuracy: Option<LocationAccuracy>,
+    /// Distance in meters the user must move before a location update, for
+    /// location requests
+    pub distance_threshold: Option<u32>,
+}
+
+/// Handle of an application window, as understood by xdg-desktop-portal
+///
+/// Formats as the portal's `parent_window` string (`x11:<hex id>` or
+/// `wayland:<handle>`).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum WindowIdentifier {
+    /// X11 window id
+    X11(u32),
+    /// Surface handle exported with the Wayland `xdg_foreign` protocol
+    Wayland(String),
+}
+
+impl fmt::Display for WindowIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::X11(id) => write!(f, "x11:{:x}", id),
+            Self::Wayland(handle) => write!(f, "wayland:{}", handle),
+        }
+    }
+}
+
+/// Location precision, from coarsest to finest
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LocationAccuracy {
+    /// Country level
+    Country,
+    /// City level
+    City,
+    /// Neighborhood level
+    Neighborhood,
+    /// Street level
+    Street,
+    /// As exact as the device allows
+    Exact,
+}
+
+impl RequestOptions {
+    /// Options with no timeout and no cancellation token
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Give up after `timeout` without an answer
+    ///
+    /// The timeout runs from the call, so it includes any time the request spends
+    /// queued behind other permissions' prompts before its own is shown.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Abort the request when `cancel` is cancelled
+    pub fn with_cancel(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Attach the dialog to `window`
+    pub fn with_parent_window(mut self, window: WindowIdentifier) -> Self {
+        self.parent_window = Some(window);
+        self
+    }
+
+    /// Explain why the permission is needed
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Ask location requests for `accuracy`
+    pub fn with_accuracy(mut self, accuracy: LocationAccuracy) -> Self {
+        self.accuracy = Some(accuracy);
+        self
+    }
+
+    /// Ask location requests for updates every `meters` moved
+    pub fn with_distance_threshold(mut self, meters: u32) -> Self {
+        self.distance_threshold = Some(meters);
+        self
+    }
+}
@@ -0,0 +1,99 @@
+//! Bridge between the synchronous API and async platform probes
+//!
+//! Async probes never run on the caller's runtime from synchronous code. They are
+//! spawned on a small private runtime and the calling thread waits for the result,
+//! which works without any runtime, inside a `current_thread` runtime and inside a
+//! multi-thread runtime alike (where `block_in_place` + `Handle::block_on` would
+//! panic on the first and stall a worker on the second).
+
+use std::future::Future;
+use std::sync::OnceLock;
+
+// Created once on first use and kept for the lifetime of the process, so sync
+// checks do not pay for runtime start-up and worker shutdown on every call
+static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+
+/// Get or create the private runtime used by synchronous checks
+///
+/// # Panics
+///
+/// Panics if runtime creation fails, which indicates a fundamental system problem
+/// that would prevent all permission checking from working anyway.
+fn runtime() -> &'static tokio::runtime::Runtime {
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name("kodegen-permissions")
+            .enable_all()
+            .build()
+            .expect("Failed to create tokio runtime for permission probes")
+    })
+}
+
+/// Run `future` to completion from synchronous code, with or without a runtime
+pub(crate) fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let handle = runtime().spawn(crate::trace::in_current_span(future));
+    // Waiting on a JoinHandle only parks this thread; it never enters a runtime
+    match futures::executor::block_on(handle) {
+        Ok(output) => output,
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Err(e) => panic!("permission probe task failed: {e}"),
+    }
+}
+
+/// Spawn `future` on the current runtime, or on the private runtime if there is none
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn(future);
+        },
+        Err(_) => {
+            runtime().spawn(future);
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn probe() -> u32 {
+        // Suspend once so the future really runs on the private runtime
+        tokio::task::yield_now().await;
+        42
+    }
+
+    #[test]
+    fn test_block_on_without_runtime() {
+        assert_eq!(block_on(probe()), 42);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_block_on_inside_current_thread_runtime() {
+        assert_eq!(block_on(probe()), 42);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_block_on_inside_multi_thread_runtime() {
+        assert_eq!(block_on(probe()), 42);
+    }
+
+    #[test]
+    fn test_block_on_resumes_panics() {
+        let panic = std::panic::catch_unwind(|| {
+            block_on(async {
+                panic!("probe crashed");
+            })
+        })
+        .unwrap_err();
+        assert_eq!(panic.downcast_ref::<&str>(), Some(&"probe crashed"));
+    }
+}
@@ -0,0 +1,187 @@
+//! Request scheduling for [`PermissionManager`](crate::PermissionManager)
+//!
+//! Concurrent requests for the same permission share a single in-flight backend
+//! request. Requests that show a prompt (see
+//! [`PermissionHandler::requires_prompt`]) wait for a ticket so only one dialog is
+//! on screen at a time; tickets are served in the order requests were scheduled.
+//! Requests that need no UI bypass the queue and run in parallel.
+
+use std::collections::{BTreeSet, HashMap};
+use std::pin::pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+
+use futures::FutureExt;
+use futures::future::{BoxFuture, Shared, WeakShared};
+use tokio::sync::{Notify, oneshot};
+
+use crate::request::RequestOptions;
+use crate::traits::PermissionHandler;
+use crate::types::{PermissionError, PermissionStatus, PermissionType};
+
+type RequestResult = Result<PermissionStatus, PermissionError>;
+
+/// Backend request shared by every caller asking for the same permission
+pub(crate) type SharedRequest = Shared<BoxFuture<'static, RequestResult>>;
+
+type InFlight = HashMap<PermissionType, (u64, WeakShared<BoxFuture<'static, RequestResult>>)>;
+
+#[derive(Default)]
+pub(crate) struct RequestScheduler {
+    // Weak handles: once every caller has dropped its request, the backend request
+    // is dropped too, which closes its prompt
+    in_flight: Arc<Mutex<InFlight>>,
+    next_id: AtomicU64,
+    prompts: Arc<PromptQueue>,
+}
+
+impl RequestScheduler {
+    /// Join the in-flight request for `typ`, or schedule a new one on `backend`
+    ///
+    /// Scheduling happens synchronously, so prompts are ordered by call order.
+    /// A joined request keeps the options it was scheduled with, and its prompt
+    /// is never reported to the joining caller.
+    pub(crate) fn join(
+        &self,
+        typ: PermissionType,
+        options: &RequestOptions,
+        backend: &Arc<dyn PermissionHandler>,
+    ) -> (SharedRequest, Prompted) {
+        let mut in_flight = lock(&self.in_flight);
+        if let Some((_, weak)) = in_flight.get(&typ)
+            && let Some(request) = weak.upgrade()
+        {
+            return (request, Prompted::default());
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let ticket = backend
+            .requires_prompt(typ)
+            .then(|| PromptQueue::ticket(&self.prompts));
+        let backend = Arc::clone(backend);
+        let options = options.clone();
+        let registry = Arc::clone(&self.in_flight);
+        let prompted = Prompted::default();
+        let shown = Arc::downgrade(&prompted.0);
+
+        let request = async move {
+            if let Some(ticket) = &ticket {
+                ticket.turn().await;
+                // Only worth asking while the caller still holds its flag
+                if let Some(shown) = Weak::upgrade(&shown)
+                    && backend.shows_prompt(typ).await
+                {
+                    shown.store(true, Ordering::Relaxed);
+                }
+            }
+
+            let (tx, rx) = oneshot::channel();
+            backend.request_permission_with(typ, &options, tx);
+            let result = rx
+                .await
+                .unwrap_or_else(|_| Err(PermissionError::system("Permission channel closed")));
+
+            // Let the next prompt through and retire this request
+            drop(ticket);
+            let mut in_flight = lock(&registry);
+            if in_flight
+                .get(&typ)
+                .is_some_and(|(current, _)| *current == id)
+            {
+                in_flight.remove(&typ);
+            }
+            result
+        }
+        .boxed()
+        .shared();
+
+        if let Some(weak) = request.downgrade() {
+            in_flight.insert(typ, (id, weak));
+        }
+        (request, prompted)
+    }
+}
+
+/// Whether the request a caller scheduled has put a dialog on screen
+///
+/// Set once the request's turn in the prompt queue comes and the backend reports
+/// a dialog (see [`PermissionHandler::shows_prompt`]). Stays unset for callers
+/// that joined another caller's request, and for requests given up on before
+/// their turn.
+#[derive(Clone, Default)]
+pub(crate) struct Prompted(Arc<AtomicBool>);
+
+impl Prompted {
+    #[cfg_attr(not(feature = "audit"), allow(dead_code))]
+    pub(crate) fn get(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// First-come, first-served queue admitting one prompt at a time
+#[derive(Default)]
+struct PromptQueue {
+    state: Mutex<QueueState>,
+    notify: Notify,
+}
+
+#[derive(Default)]
+struct QueueState {
+    next: u64,
+    serving: u64,
+    // Tickets dropped before their turn, skipped when reached
+    abandoned: BTreeSet<u64>,
+}
+
+impl PromptQueue {
+    fn ticket(queue: &Arc<Self>) -> Ticket {
+        let mut state = lock(&queue.state);
+        let id = state.next;
+        state.next += 1;
+        Ticket {
+            id,
+            queue: Arc::clone(queue),
+        }
+    }
+}
+
+/// Place in the [`PromptQueue`]; dropping it releases or forfeits the turn
+struct Ticket {
+    id: u64,
+    queue: Arc<PromptQueue>,
+}
+
+impl Ticket {
+    async fn turn(&self) {
+        loop {
+            let mut notified = pin!(self.queue.notify.notified());
+            // Register before checking so a release in between is not missed
+            notified.as_mut().enable();
+            if lock(&self.queue.state).serving == self.id {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+impl Drop for Ticket {
+    fn drop(&mut self) {
+        let mut guard = lock(&self.queue.state);
+        let state = &mut *guard;
+        if state.serving == self.id {
+            state.serving += 1;
+            while state.abandoned.remove(&state.serving) {
+                state.serving += 1;
+            }
+            drop(guard);
+            self.queue.notify.notify_waiters();
+        } else {
+            state.abandoned.insert(self.id);
+        }
+    }
+}
@@ -0,0 +1,315 @@
+//! Scriptable permission backend for deterministic tests
+//!
+//! Enabled with the `testing` cargo feature. [`FakePermissionBackend`] never touches
+//! the operating system: every answer is programmed per [`PermissionType`], and every
+//! call is recorded so tests can assert on what the code under test asked for.
+//!
+//! # Example
+//! ```rust
+//! use std::sync::Arc;
+//! use kodegen_native_permissions::testing::FakePermissionBackend;
+//! use kodegen_native_permissions::{PermissionManager, PermissionStatus, PermissionType};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! // Camera is denied until the user accepts the prompt
+//! let backend = Arc::new(FakePermissionBackend::new());
+//! backend.set_status(PermissionType::Camera, PermissionStatus::Denied);
+//! backend.push_response(PermissionType::Camera, Ok(PermissionStatus::Authorized));
+//!
+//! let manager = PermissionManager::with_backend(backend.clone());
+//! assert_eq!(manager.check_permission(PermissionType::Camera).unwrap(), PermissionStatus::Denied);
+//! assert_eq!(
+//!     manager.request_permission(PermissionType::Camera).await.unwrap(),
+//!     PermissionStatus::Authorized
+//! );
+//! assert_eq!(backend.request_count(PermissionType::Camera), 1);
+//! # }
+//! ```
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use tokio::sync::oneshot;
+
+use crate::remediation::Remediation;
+use crate::traits::PermissionHandler;
+use crate::types::{PermissionError, PermissionStatus, PermissionType, Support};
+
+/// A call received by [`FakePermissionBackend`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FakeCall {
+    /// `check_permission` was called
+    Check(PermissionType),
+    /// `request_permission` was called
+    Request(PermissionType),
+}
+
+#[derive(Default)]
+struct FakeState {
+    statuses: HashMap<PermissionType, PermissionStatus>,
+    errors: HashMap<PermissionType, PermissionError>,
+    delays: HashMap<PermissionType, Duration>,
+    responses: HashMap<PermissionType, VecDeque<Result<PermissionStatus, PermissionError>>>,
+    silent: HashSet<PermissionType>,
+    support: HashMap<PermissionType, Support>,
+    remediations: HashMap<PermissionType, Remediation>,
+    calls: Vec<FakeCall>,
+}
+
+/// Permission backend whose answers are programmed by the test
+///
+/// Resolution rules:
+/// - `check_permission` returns the configured error if one is set, otherwise the
+///   configured status (`NotDetermined` by default).
+/// - `request_permission` answers with the next queued response if any, otherwise
+///   behaves like `check_permission`. A successful request answer becomes the new
+///   status, so later checks observe what the "user" chose.
+/// - A configured delay is applied before every answer for that type.
+/// - Requests count as interactive prompts unless the type is marked silent.
+pub struct FakePermissionBackend {
+    state: Mutex<FakeState>,
+    default_status: PermissionStatus,
+}
+
+impl Default for FakePermissionBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakePermissionBackend {
+    /// Create a backend that reports `NotDetermined` for every permission
+    pub fn new() -> Self {
+        Self::with_default_status(PermissionStatus::NotDetermined)
+    }
+
+    /// Create a backend that reports `status` for every unconfigured permission
+    pub fn with_default_status(status: PermissionStatus) -> Self {
+        Self {
+            state: Mutex::new(FakeState::default()),
+            default_status: status,
+        }
+    }
+
+    /// Set the status reported for `typ`
+    pub fn set_status(&self, typ: PermissionType, status: PermissionStatus) -> &Self {
+        self.with_state(|state| {
+            state.statuses.insert(typ, status);
+        });
+        self
+    }
+
+    /// Make every call for `typ` fail with `error` until [`clear_error`](Self::clear_error)
+    pub fn set_error(&self, typ: PermissionType, error: PermissionError) -> &Self {
+        self.with_state(|state| {
+            state.errors.insert(typ, error);
+        });
+        self
+    }
+
+    /// Remove a previously configured error for `typ`
+    pub fn clear_error(&self, typ: PermissionType) -> &Self {
+        self.with_state(|state| {
+            state.errors.remove(&typ);
+        });
+        self
+    }
+
+    /// Delay every answer for `typ` by `delay`
+    pub fn set_delay(&self, typ: PermissionType, delay: Duration) -> &Self {
+        self.with_state(|state| {
+            state.delays.insert(typ, delay);
+        });
+        self
+    }
+
+    /// Report requests for `typ` as needing no prompt (see `requires_prompt`)
+    pub fn set_silent(&self, typ: PermissionType) -> &Self {
+        self.with_state(|state| {
+            state.silent.insert(typ);
+        });
+        self
+    }
+
+    /// Report `support` for `typ` (every type is `Native` by default)
+    pub fn set_support(&self, typ: PermissionType, support: Support) -> &Self {
+        self.with_state(|state| {
+            state.support.insert(typ, support);
+        });
+        self
+    }
+
+    /// Offer `remediation` for its permission (no guidance by default)
+    pub fn set_remediation(&self, remediation: Remediation) -> &Self {
+        self.with_state(|state| {
+            state.remediations.insert(remediation.permission, remediation);
+        });
+        self
+    }
+
+    /// Queue the answer for the next `request_permission` call for `typ`
+    pub fn push_response(
+        &self,
+        typ: PermissionType,
+        response: Result<PermissionStatus, PermissionError>,
+    ) -> &Self {
+        self.with_state(|state| {
+            state.responses.entry(typ).or_default().push_back(response);
+        });
+        self
+    }
+
+    /// Queue answers for successive `request_permission` calls for `typ`
+    pub fn push_responses(
+        &self,
+        typ: PermissionType,
+        responses: impl IntoIterator<Item = Result<PermissionStatus, PermissionError>>,
+    ) -> &Self {
+        self.with_state(|state| {
+            state.responses.entry(typ).or_default().extend(responses);
+        });
+        self
+    }
+
+    /// All calls received so far, in order
+    pub fn calls(&self) -> Vec<FakeCall> {
+        self.with_state(|state| state.calls.clone())
+    }
+
+    /// Number of `check_permission` calls received for `typ`
+    pub fn check_count(&self, typ: PermissionType) -> usize {
+        self.count(FakeCall::Check(typ))
+    }
+
+    /// Number of `request_permission` calls received for `typ`
+    pub fn request_count(&self, typ: PermissionType) -> usize {
+        self.count(FakeCall::Request(typ))
+    }
+
+    /// Forget all recorded calls
+    pub fn clear_calls(&self) {
+        self.with_state(|state| state.calls.clear());
+    }
+
+    fn count(&self, call: FakeCall) -> usize {
+        self.with_state(|state| state.calls.iter().filter(|c| **c == call).count())
+    }
+
+    fn with_state<T>(&self, f: impl FnOnce(&mut FakeState) -> T) -> T {
+        // A panicking test must not poison the fake for the remaining assertions
+        let mut guard = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut guard)
+    }
+
+    fn record_check(
+        &self,
+        typ: PermissionType,
+    ) -> (Result<PermissionStatus, PermissionError>, Option<Duration>) {
+        self.with_state(|state| {
+            state.calls.push(FakeCall::Check(typ));
+            (self.current(state, typ), state.delays.get(&typ).copied())
+        })
+    }
+
+    fn current(
+        &self,
+        state: &FakeState,
+        typ: PermissionType,
+    ) -> Result<PermissionStatus, PermissionError> {
+        if let Some(error) = state.errors.get(&typ) {
+            return Err(error.clone());
+        }
+        Ok(state
+            .statuses
+            .get(&typ)
+            .copied()
+            .unwrap_or(self.default_status))
+    }
+}
+
+impl PermissionHandler for FakePermissionBackend {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    fn check_permission(&self, typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
+        let (result, delay) = self.record_check(typ);
+        if let Some(delay) = delay {
+            std::thread::sleep(delay);
+        }
+        result
+    }
+
+    fn check_permission_async(
+        &self,
+        typ: PermissionType,
+    ) -> BoxFuture<'_, Result<PermissionStatus, PermissionError>> {
+        let (result, delay) = self.record_check(typ);
+        Box::pin(async move {
+            if let Some(delay) = delay {
+                tokio::time::sleep(delay).await;
+            }
+            result
+        })
+    }
+
+    fn support(&self, typ: PermissionType) -> Support {
+        self.with_state(|state| state.support.get(&typ).copied().unwrap_or(Support::Native))
+    }
+
+    fn remediation(&self, typ: PermissionType) -> Remediation {
+        self.with_state(|state| {
+            state
+                .remediations
+                .get(&typ)
+                .cloned()
+                .unwrap_or_else(|| Remediation::new(typ))
+        })
+    }
+
+    fn requires_prompt(&self, typ: PermissionType) -> bool {
+        self.with_state(|state| !state.silent.contains(&typ))
+    }
+
+    fn request_permission(
+        &self,
+        typ: PermissionType,
+        tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        let (result, delay) = self.with_state(|state| {
+            state.calls.push(FakeCall::Request(typ));
+            let result = match state.responses.get_mut(&typ).and_then(VecDeque::pop_front) {
+                Some(response) => response,
+                None => self.current(state, typ),
+            };
+            if let Ok(status) = &result {
+                state.statuses.insert(typ, *status);
+            }
+            (result, state.delays.get(&typ).copied())
+        });
+
+        match delay {
+            None => {
+                tx.send(result).ok();
+            },
+            Some(delay) => match tokio::runtime::Handle::try_current() {
+                // Tokio timers honour paused time in tests
+                Ok(handle) => {
+                    handle.spawn(async move {
+                        tokio::time::sleep(delay).await;
+                        tx.send(result).ok();
+                    });
+                },
+                Err(_) => {
+                    std::thread::spawn(move || {
+                        std::thread::sleep(delay);
+                        tx.send(result).ok();
+                    });
+                },
+            },
+        }
+    }
+}
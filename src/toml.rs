@@ -0,0 +1,61 @@
+//! Helpers shared by the TOML readers of manifests and policy files
+
+use toml_edit::{Document, Item, TableLike};
+
+use crate::types::{ErrorKind, PermissionError, PermissionType};
+
+/// An [`ErrorKind::InvalidConfig`] error
+pub(crate) fn invalid(message: impl Into<String>) -> PermissionError {
+    PermissionError::new(ErrorKind::InvalidConfig).with_message(message)
+}
+
+/// Parse a TOML document, naming `what` it holds on failure
+pub(crate) fn parse(source: &str, what: &str) -> Result<Document<String>, PermissionError> {
+    Document::parse(source.to_string())
+        .map_err(|e| invalid(format!("{} is not valid TOML", what)).with_source(e))
+}
+
+/// Tables of an array of tables or an array of inline tables
+pub(crate) fn tables<'a>(
+    item: &'a Item,
+    key: &str,
+) -> Result<Vec<&'a dyn TableLike>, PermissionError> {
+    if let Some(array) = item.as_array_of_tables() {
+        return Ok(array.iter().map(|table| table as &dyn TableLike).collect());
+    }
+    item.as_array()
+        .and_then(|array| {
+            array
+                .iter()
+                .map(|value| value.as_inline_table().map(|t| t as &dyn TableLike))
+                .collect()
+        })
+        .ok_or_else(|| invalid(format!("`{}` must be an array of tables", key)))
+}
+
+/// Required string field
+pub(crate) fn string<'a>(table: &'a dyn TableLike, key: &str) -> Result<&'a str, PermissionError> {
+    table
+        .get(key)
+        .and_then(Item::as_str)
+        .ok_or_else(|| invalid(format!("`{}` must be a string", key)))
+}
+
+/// Optional array of strings; a missing field is empty
+pub(crate) fn strings<'a>(
+    table: &'a dyn TableLike,
+    key: &str,
+) -> Result<Vec<&'a str>, PermissionError> {
+    let Some(item) = table.get(key) else {
+        return Ok(Vec::new());
+    };
+    item.as_array()
+        .and_then(|array| array.iter().map(|value| value.as_str()).collect())
+        .ok_or_else(|| invalid(format!("`{}` must be an array of strings", key)))
+}
+
+/// Parse a permission identifier
+pub(crate) fn permission(id: &str) -> Result<PermissionType, PermissionError> {
+    id.parse()
+        .map_err(|e| invalid(format!("unknown permission `{}`", id)).with_source(e))
+}
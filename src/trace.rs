@@ -0,0 +1,182 @@
+//! Optional `tracing` instrumentation
+//!
+//! With the `tracing` feature, checks, requests and platform probes each run in
+//! a `DEBUG` span carrying the permission, backend, bus name and object path or
+//! file path, plus `duration_ms` and `result` once they finish. Failures also
+//! emit a `DEBUG` event with the full error, and a watched permission that cannot
+//! be re-checked a `WARN` event. Without the feature, [`Timed`] is empty and
+//! every function compiles to nothing.
+//!
+//! Probe spans (`dbus_probe`, `system_probe`) are only emitted by the Linux
+//! backend; on macOS and Windows a check or request shows up as its own span
+//! alone. Synchronous calls that run on the crate's private runtime carry the
+//! caller's span and subscriber along, so probe spans stay children of the check
+//! that caused them.
+
+use std::fmt;
+use std::future::Future;
+
+use crate::types::{PermissionError, PermissionStatus, PermissionType};
+
+/// A span timing one check, request or probe
+pub(crate) struct Timed {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    start: std::time::Instant,
+}
+
+#[cfg(feature = "tracing")]
+impl Timed {
+    fn new(span: tracing::Span) -> Self {
+        Self {
+            span,
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// Span for a check through the manager
+    pub(crate) fn check(typ: PermissionType, backend: &str) -> Self {
+        Self::new(tracing::debug_span!(
+            "check_permission",
+            permission = typ.as_str(),
+            backend,
+            duration_ms = tracing::field::Empty,
+            result = tracing::field::Empty,
+        ))
+    }
+
+    /// Span for a request through the manager, from scheduling to answer
+    pub(crate) fn request(typ: PermissionType, backend: &str) -> Self {
+        Self::new(tracing::debug_span!(
+            "request_permission",
+            permission = typ.as_str(),
+            backend,
+            duration_ms = tracing::field::Empty,
+            result = tracing::field::Empty,
+        ))
+    }
+
+    /// Span for a D-Bus method call or portal request
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub(crate) fn bus_call(bus_name: &str, path: &str, method: &str) -> Self {
+        Self::new(tracing::debug_span!(
+            "dbus_probe",
+            bus_name,
+            path,
+            method,
+            duration_ms = tracing::field::Empty,
+            result = tracing::field::Empty,
+        ))
+    }
+
+    /// Span for a probe of the local system: opening a device node, listing a
+    /// folder, running a command
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub(crate) fn local(probe: &str, path: &str) -> Self {
+        Self::new(tracing::debug_span!(
+            "system_probe",
+            probe,
+            path,
+            duration_ms = tracing::field::Empty,
+            result = tracing::field::Empty,
+        ))
+    }
+
+    /// Run `future` inside the span
+    pub(crate) fn instrument<F: Future>(&self, future: F) -> impl Future<Output = F::Output> {
+        tracing::Instrument::instrument(future, self.span.clone())
+    }
+
+    /// Enter the span until the guard is dropped
+    pub(crate) fn enter(&self) -> tracing::span::Entered<'_> {
+        self.span.enter()
+    }
+
+    /// Record the duration and the resulting status or error
+    pub(crate) fn finish(&self, result: &Result<PermissionStatus, PermissionError>) {
+        match result {
+            Ok(status) => self.record(status.as_str()),
+            Err(e) => self.fail(e),
+        }
+    }
+
+    /// Record the duration and whether a lower-level probe succeeded
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub(crate) fn finish_probe<T, E: fmt::Display>(&self, result: &Result<T, E>) {
+        match result {
+            Ok(_) => self.record("ok"),
+            Err(e) => self.fail(e),
+        }
+    }
+
+    fn record(&self, result: &str) {
+        let elapsed = self.start.elapsed().as_secs_f64() * 1000.0;
+        self.span.record("duration_ms", elapsed);
+        self.span.record("result", result);
+    }
+
+    fn fail(&self, error: &dyn fmt::Display) {
+        self.record("error");
+        tracing::debug!(parent: &self.span, error = %error, "permission operation failed");
+    }
+}
+
+/// Run `future` in the caller's span and with the caller's subscriber, wherever
+/// it is polled
+#[cfg(feature = "tracing")]
+pub(crate) fn in_current_span<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    use tracing::instrument::WithSubscriber;
+
+    tracing::Instrument::in_current_span(future).with_current_subscriber()
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn in_current_span<F: Future>(future: F) -> F {
+    future
+}
+
+/// Log a failed re-check of a watched permission
+#[cfg(feature = "tracing")]
+pub(crate) fn watch_failed(typ: PermissionType, error: &PermissionError) {
+    tracing::warn!(permission = typ.as_str(), error = %error, "watched permission check failed");
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn watch_failed(_typ: PermissionType, _error: &PermissionError) {}
+
+/// Stand-in for an entered span guard
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct Entered;
+
+#[cfg(not(feature = "tracing"))]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+impl Timed {
+    pub(crate) fn check(_typ: PermissionType, _backend: &str) -> Self {
+        Self {}
+    }
+
+    pub(crate) fn request(_typ: PermissionType, _backend: &str) -> Self {
+        Self {}
+    }
+
+    pub(crate) fn bus_call(_bus_name: &str, _path: &str, _method: &str) -> Self {
+        Self {}
+    }
+
+    pub(crate) fn local(_probe: &str, _path: &str) -> Self {
+        Self {}
+    }
+
+    pub(crate) fn instrument<F: Future>(&self, future: F) -> F {
+        future
+    }
+
+    pub(crate) fn enter(&self) -> Entered {
+        Entered
+    }
+
+    pub(crate) fn finish(&self, _result: &Result<PermissionStatus, PermissionError>) {}
+
+    pub(crate) fn finish_probe<T, E: fmt::Display>(&self, _result: &Result<T, E>) {}
+}
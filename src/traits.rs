@@ -1,18 +1,133 @@
 //! Permission handler traits and interfaces
 
-use tokio::sync::oneshot;
+use std::time::Duration;
 
-use crate::types::{PermissionError, PermissionStatus, PermissionType};
+use futures::future::BoxFuture;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::remediation::Remediation;
+use crate::request::RequestOptions;
+use crate::types::{ErrorKind, PermissionError, PermissionStatus, PermissionType, Support};
 
 /// Trait for platform-specific permission handling
+///
+/// Each supported platform ships an implementation (`MacOSHandler`, `WindowsHandler`,
+/// `LinuxHandler`). Custom implementations can be passed to
+/// [`PermissionManager::with_backend`](crate::PermissionManager::with_backend) to
+/// replace or decorate the platform backend.
 pub trait PermissionHandler: Send + Sync {
+    /// Short identifier of this backend, recorded in the errors it produces
+    fn name(&self) -> &'static str {
+        "custom"
+    }
+
+    /// How this backend supports `typ`
+    ///
+    /// [`PermissionManager`](crate::PermissionManager) answers
+    /// [`PermissionStatus::NotApplicable`] for [`Support::NotApplicable`] types
+    /// without calling the backend. The default reports every type as native.
+    fn support(&self, typ: PermissionType) -> Support {
+        let _ = typ;
+        Support::Native
+    }
+
+    /// How the user can grant `typ` after denying it, or when it is restricted
+    ///
+    /// The default offers no guidance.
+    fn remediation(&self, typ: PermissionType) -> Remediation {
+        Remediation::new(typ)
+    }
+
+    /// Open the system settings where the user grants `typ`
+    ///
+    /// Returns once the settings application has been started. The default fails
+    /// with [`ErrorKind::NotSupportedOnPlatform`].
+    fn open_settings(&self, typ: PermissionType) -> Result<(), PermissionError> {
+        Err(PermissionError::new(ErrorKind::NotSupportedOnPlatform)
+            .with_message(format!("cannot open the settings for {}", typ)))
+    }
+
     /// Check the current status of a permission without requesting it
     fn check_permission(&self, typ: PermissionType) -> Result<PermissionStatus, PermissionError>;
 
+    /// Check the current status of a permission without blocking the async runtime
+    ///
+    /// Backends with async-native probes should override this. The default
+    /// implementation runs [`check_permission`](Self::check_permission) inline when
+    /// polled.
+    fn check_permission_async(
+        &self,
+        typ: PermissionType,
+    ) -> BoxFuture<'_, Result<PermissionStatus, PermissionError>> {
+        Box::pin(async move { self.check_permission(typ) })
+    }
+
     /// Request a permission from the user, sending result via channel
+    ///
+    /// The receiver is dropped when the caller cancels, times out or drops the
+    /// request. Backends that can dismiss their prompt should do so once
+    /// `tx.closed()` resolves.
     fn request_permission(
         &self,
         typ: PermissionType,
         tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
     );
+
+    /// Request a permission, presenting the prompt as `options` asks
+    ///
+    /// Backends that can parent their dialog, show a reason or tune location
+    /// accuracy override this. Timeouts and cancellation are enforced by the
+    /// manager, which closes `tx` as described for
+    /// [`request_permission`](Self::request_permission). The default ignores
+    /// `options`.
+    fn request_permission_with(
+        &self,
+        typ: PermissionType,
+        options: &RequestOptions,
+        tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        let _ = options;
+        self.request_permission(typ, tx)
+    }
+
+    /// Whether requesting `typ` may show an interactive prompt
+    ///
+    /// [`PermissionManager`](crate::PermissionManager) shows prompting requests one
+    /// at a time and runs the rest in parallel. The default assumes every request
+    /// may prompt.
+    fn requires_prompt(&self, typ: PermissionType) -> bool {
+        let _ = typ;
+        true
+    }
+
+    /// Whether a request for `typ` made now shows a dialog
+    ///
+    /// Asked when a prompting request's turn comes, right before it reaches the
+    /// backend, so the audit log records whether the user was actually prompted.
+    /// Backends that answer without a dialog when a decision is already on record
+    /// override this. The default is [`requires_prompt`](Self::requires_prompt).
+    fn shows_prompt(&self, typ: PermissionType) -> BoxFuture<'_, bool> {
+        let prompt = self.requires_prompt(typ);
+        Box::pin(async move { prompt })
+    }
+
+    /// Subscribe to native change notifications for `types`
+    ///
+    /// Backends that can observe permission changes without polling send the
+    /// affected type on `tx` whenever its status may have changed, and return the
+    /// subset of `types` they cover. The subscription should end once `tx` is
+    /// closed. Types that are not returned are polled by
+    /// [`PermissionManager::watch`](crate::PermissionManager::watch), and so are
+    /// the returned ones once the backend drops `tx`. `interval` is the watch's
+    /// polling interval, for backends that have to poll when a subscription
+    /// fails. The default implementation covers nothing.
+    fn watch_changes(
+        &self,
+        types: &[PermissionType],
+        interval: Duration,
+        tx: mpsc::UnboundedSender<PermissionType>,
+    ) -> Vec<PermissionType> {
+        let _ = (types, interval, tx);
+        Vec::new()
+    }
 }
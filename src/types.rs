@@ -1,6 +1,9 @@
 //! Permission types and error definitions
 
+use std::borrow::Cow;
+use std::error::Error;
 use std::fmt;
+use std::sync::Arc;
 
 /// System permission types supported across platforms
 #[derive(Hash, Eq, PartialEq, Clone, Copy, Debug)]
@@ -62,32 +65,372 @@ pub enum PermissionStatus {
     PromptRequired,
     /// Permission status is unknown
     Unknown,
+    /// The permission does not exist on this platform
+    NotApplicable,
 }
 
-/// Errors that can occur during permission operations
-#[derive(Debug)]
-pub enum PermissionError {
+/// How a backend supports a permission on the current platform
+///
+/// Returned by [`PermissionManager::support`](crate::PermissionManager::support).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Support {
+    /// The operating system has this permission and the backend queries it
+    Native,
+    /// The operating system has no such permission; the backend derives a status
+    /// from something related
+    Emulated {
+        /// What the status is derived from
+        via: &'static str,
+    },
+    /// The permission does not exist on this platform; checks and requests report
+    /// [`PermissionStatus::NotApplicable`]
+    NotApplicable,
+}
+
+/// Category of a [`PermissionError`]
+///
+/// Every kind maps to a stable numeric [`code`](ErrorKind::code) that is never
+/// reused or renumbered, so it can be handed to clients as-is. New kinds may be
+/// added in minor releases.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// The operation was refused
     Denied,
+    /// The operation is restricted by system policy
     Restricted,
-    SystemError(String),
-    PlatformError(String),
-    Unknown,
+    /// The user dismissed the prompt without answering
+    UserCancelled,
+    /// The caller cancelled the operation
     Cancelled,
+    /// The operation did not complete in time
+    Timeout,
+    /// A system service needed for the operation is not running
+    ServiceUnavailable,
+    /// The D-Bus session or system bus could not be reached
+    BusUnavailable,
+    /// A sandbox (Flatpak, Snap, App Sandbox) blocked the operation
+    SandboxBlocked,
+    /// The permission or operation does not exist on this platform
+    NotSupportedOnPlatform,
+    /// An operating system call failed
+    System,
+    /// A platform framework reported an error
+    Platform,
+    /// A manifest or configuration file could not be parsed
+    InvalidConfig,
+    /// The folder, file or device to check does not exist
+    NotFound,
+    /// The cause could not be determined
+    Unknown,
 }
 
-impl fmt::Display for PermissionError {
+impl ErrorKind {
+    /// Stable numeric code for this kind
+    pub fn code(self) -> u32 {
+        match self {
+            Self::Unknown => 1000,
+            Self::Denied => 1001,
+            Self::Restricted => 1002,
+            Self::UserCancelled => 1003,
+            Self::Cancelled => 1004,
+            Self::Timeout => 1005,
+            Self::ServiceUnavailable => 1006,
+            Self::BusUnavailable => 1007,
+            Self::SandboxBlocked => 1008,
+            Self::NotSupportedOnPlatform => 1009,
+            Self::System => 1010,
+            Self::Platform => 1011,
+            Self::InvalidConfig => 1012,
+            Self::NotFound => 1013,
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Denied => write!(f, "Permission denied"),
             Self::Restricted => write!(f, "Permission restricted"),
-            Self::SystemError(s) => write!(f, "System error: {}", s),
-            Self::PlatformError(s) => write!(f, "Platform error: {}", s),
-            Self::Unknown => write!(f, "Unknown error"),
+            Self::UserCancelled => write!(f, "Cancelled by user"),
             Self::Cancelled => write!(f, "Operation cancelled"),
+            Self::Timeout => write!(f, "Operation timed out"),
+            Self::ServiceUnavailable => write!(f, "Service unavailable"),
+            Self::BusUnavailable => write!(f, "Message bus unavailable"),
+            Self::SandboxBlocked => write!(f, "Blocked by sandbox"),
+            Self::NotSupportedOnPlatform => write!(f, "Not supported on this platform"),
+            Self::System => write!(f, "System error"),
+            Self::Platform => write!(f, "Platform error"),
+            Self::InvalidConfig => write!(f, "Invalid configuration"),
+            Self::NotFound => write!(f, "Not found"),
+            Self::Unknown => write!(f, "Unknown error"),
+        }
+    }
+}
+
+/// Errors that can occur during permission operations
+///
+/// Besides its [`ErrorKind`], an error records which backend and which probe
+/// (D-Bus service, device node, framework call) failed, and keeps the underlying
+/// error reachable through [`std::error::Error::source`].
+///
+/// ```rust
+/// use kodegen_native_permissions::{ErrorKind, PermissionError};
+///
+/// let error = PermissionError::new(ErrorKind::ServiceUnavailable)
+///     .with_message("BlueZ is not running")
+///     .with_probe("org.bluez");
+/// assert_eq!(error.code(), 1006);
+/// assert_eq!(error.probe(), Some("org.bluez"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PermissionError {
+    kind: ErrorKind,
+    message: Option<String>,
+    backend: Option<Cow<'static, str>>,
+    probe: Option<String>,
+    source: Option<Arc<dyn Error + Send + Sync>>,
+}
+
+impl PermissionError {
+    /// Create an error of the given kind with no further detail
+    pub fn new(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            message: None,
+            backend: None,
+            probe: None,
+            source: None,
+        }
+    }
+
+    /// Shorthand for an [`ErrorKind::System`] error with a message
+    pub fn system(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::System).with_message(message)
+    }
+
+    /// Shorthand for an [`ErrorKind::Platform`] error with a message
+    pub fn platform(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Platform).with_message(message)
+    }
+
+    /// Attach a human-readable description
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Attach the underlying error, exposed through [`Error::source`]
+    pub fn with_source(mut self, source: impl Error + Send + Sync + 'static) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    /// Record the backend that produced this error
+    pub fn with_backend(mut self, backend: &'static str) -> Self {
+        self.backend = Some(Cow::Borrowed(backend));
+        self
+    }
+
+    /// Record the probe that failed (bus name, object path, device node, API)
+    pub fn with_probe(mut self, probe: impl Into<String>) -> Self {
+        self.probe = Some(probe.into());
+        self
+    }
+
+    /// Category of this error
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Stable numeric code of this error's kind
+    pub fn code(&self) -> u32 {
+        self.kind.code()
+    }
+
+    /// Human-readable description, if any
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Backend that produced this error, if recorded
+    pub fn backend(&self) -> Option<&str> {
+        self.backend.as_deref()
+    }
+
+    /// Probe that failed, if recorded
+    pub fn probe(&self) -> Option<&str> {
+        self.probe.as_deref()
+    }
+
+    /// Record `backend` unless a backend is already set
+    pub(crate) fn or_backend(mut self, backend: &'static str) -> Self {
+        self.backend.get_or_insert(Cow::Borrowed(backend));
+        self
+    }
+}
+
+impl From<ErrorKind> for PermissionError {
+    fn from(kind: ErrorKind) -> Self {
+        Self::new(kind)
+    }
+}
+
+impl From<std::io::Error> for PermissionError {
+    fn from(error: std::io::Error) -> Self {
+        let kind = match error.kind() {
+            std::io::ErrorKind::TimedOut => ErrorKind::Timeout,
+            std::io::ErrorKind::PermissionDenied => ErrorKind::Denied,
+            std::io::ErrorKind::NotFound => ErrorKind::NotFound,
+            std::io::ErrorKind::Unsupported => ErrorKind::NotSupportedOnPlatform,
+            _ => ErrorKind::System,
+        };
+        Self::new(kind).with_source(error)
+    }
+}
+
+impl From<tokio::time::error::Elapsed> for PermissionError {
+    fn from(error: tokio::time::error::Elapsed) -> Self {
+        Self::new(ErrorKind::Timeout).with_source(error)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl From<zbus::Error> for PermissionError {
+    fn from(error: zbus::Error) -> Self {
+        let kind = match &error {
+            zbus::Error::Address(_)
+            | zbus::Error::InputOutput(_)
+            | zbus::Error::Handshake(_)
+            | zbus::Error::Connection(..) => ErrorKind::BusUnavailable,
+            zbus::Error::MethodError(name, _, _) => dbus_error_kind(name.as_str()),
+            zbus::Error::FDO(e) => match e.as_ref() {
+                zbus::fdo::Error::ServiceUnknown(_)
+                | zbus::fdo::Error::NameHasNoOwner(_)
+                | zbus::fdo::Error::UnknownObject(_)
+                | zbus::fdo::Error::UnknownInterface(_)
+                | zbus::fdo::Error::UnknownMethod(_) => ErrorKind::ServiceUnavailable,
+                zbus::fdo::Error::AccessDenied(_) => ErrorKind::SandboxBlocked,
+                zbus::fdo::Error::NoReply(_)
+                | zbus::fdo::Error::Timeout(_)
+                | zbus::fdo::Error::TimedOut(_) => ErrorKind::Timeout,
+                zbus::fdo::Error::NotSupported(_) => ErrorKind::NotSupportedOnPlatform,
+                _ => ErrorKind::System,
+            },
+            _ => ErrorKind::System,
+        };
+        Self::new(kind).with_source(error)
+    }
+}
+
+/// Classify a D-Bus error name returned by a method call
+#[cfg(target_os = "linux")]
+fn dbus_error_kind(name: &str) -> ErrorKind {
+    match name {
+        "org.freedesktop.DBus.Error.ServiceUnknown"
+        | "org.freedesktop.DBus.Error.NameHasNoOwner"
+        | "org.freedesktop.DBus.Error.UnknownObject"
+        | "org.freedesktop.DBus.Error.UnknownInterface"
+        | "org.freedesktop.DBus.Error.UnknownMethod" => ErrorKind::ServiceUnavailable,
+        "org.freedesktop.DBus.Error.AccessDenied" => ErrorKind::SandboxBlocked,
+        "org.freedesktop.DBus.Error.NoReply"
+        | "org.freedesktop.DBus.Error.Timeout"
+        | "org.freedesktop.DBus.Error.TimedOut" => ErrorKind::Timeout,
+        "org.freedesktop.DBus.Error.NotSupported" => ErrorKind::NotSupportedOnPlatform,
+        "org.freedesktop.portal.Error.NotAllowed" => ErrorKind::Denied,
+        _ => ErrorKind::System,
+    }
+}
+
+impl fmt::Display for PermissionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+        match (&self.backend, &self.probe) {
+            (Some(backend), Some(probe)) => write!(f, " ({} backend, probe {})", backend, probe),
+            (Some(backend), None) => write!(f, " ({} backend)", backend),
+            (None, Some(probe)) => write!(f, " (probe {})", probe),
+            (None, None) => Ok(()),
         }
     }
 }
 
+impl Error for PermissionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|e| e as &(dyn Error + 'static))
+    }
+}
+
+/// Wire form of a [`PermissionError`]
+///
+/// `code` is written for clients that only know the numeric codes and ignored on
+/// input; the source chain travels as its message.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ErrorRepr {
+    kind: ErrorKind,
+    #[serde(default)]
+    code: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    backend: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    probe: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+}
+
+/// Deserialized stand-in for the original source error
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+struct SourceMessage(String);
+
+#[cfg(feature = "serde")]
+impl fmt::Display for SourceMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Error for SourceMessage {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PermissionError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ErrorRepr {
+            kind: self.kind,
+            code: self.kind.code(),
+            message: self.message.clone(),
+            backend: self.backend.as_deref().map(str::to_string),
+            probe: self.probe.clone(),
+            source: self.source.as_ref().map(|e| e.to_string()),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PermissionError {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = ErrorRepr::deserialize(deserializer)?;
+        Ok(Self {
+            kind: repr.kind,
+            message: repr.message,
+            backend: repr.backend.map(Cow::Owned),
+            probe: repr.probe,
+            source: repr
+                .source
+                .map(|message| Arc::new(SourceMessage(message)) as Arc<dyn Error + Send + Sync>),
+        })
+    }
+}
+
 impl fmt::Display for PermissionType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -144,6 +487,7 @@ impl fmt::Display for PermissionStatus {
             Self::Restricted => write!(f, "Restricted"),
             Self::PromptRequired => write!(f, "Prompt Required"),
             Self::Unknown => write!(f, "Unknown"),
+            Self::NotApplicable => write!(f, "Not Applicable"),
         }
     }
 }
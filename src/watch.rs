@@ -0,0 +1,160 @@
+//! Permission change subscriptions
+//!
+//! [`PermissionManager::watch`](crate::PermissionManager::watch) spawns a diff engine
+//! that re-checks the watched permissions and yields a [`PermissionChange`] whenever a
+//! status differs from the last observed one. Types the backend reports through
+//! [`PermissionHandler::watch_changes`](crate::PermissionHandler::watch_changes) are
+//! re-checked only when the backend signals them; all others are polled on a fixed
+//! interval. Re-checks go through the
+//! manager like [`check_permission_async`](crate::PermissionManager::check_permission_async),
+//! minus the cache lookup and the audit log.
+
+use std::collections::{HashMap, HashSet};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime};
+
+use futures::Stream;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::manager::PermissionManager;
+use crate::types::{PermissionStatus, PermissionType};
+
+/// Default interval between polls of permissions without native notifications
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A permission whose status changed while being watched
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PermissionChange {
+    /// Permission that changed
+    pub permission: PermissionType,
+    /// Last status observed before the change
+    pub old: PermissionStatus,
+    /// Newly observed status
+    pub new: PermissionStatus,
+    /// When the change was observed
+    pub timestamp: SystemTime,
+}
+
+/// Stream of [`PermissionChange`]s returned by
+/// [`PermissionManager::watch`](crate::PermissionManager::watch)
+///
+/// Dropping the stream stops the underlying diff engine and releases any native
+/// subscriptions held by the backend.
+pub struct PermissionWatch {
+    rx: mpsc::UnboundedReceiver<PermissionChange>,
+    task: JoinHandle<()>,
+}
+
+impl Stream for PermissionWatch {
+    type Item = PermissionChange;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for PermissionWatch {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Start a diff engine for `types` on the current Tokio runtime
+pub(crate) fn spawn(
+    manager: PermissionManager,
+    types: &[PermissionType],
+    interval: Duration,
+) -> PermissionWatch {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let mut seen = HashSet::new();
+    let types: Vec<PermissionType> = types.iter().copied().filter(|t| seen.insert(*t)).collect();
+    let task = tokio::spawn(run(manager, types, interval, tx));
+    PermissionWatch { rx, task }
+}
+
+async fn run(
+    manager: PermissionManager,
+    types: Vec<PermissionType>,
+    interval: Duration,
+    tx: mpsc::UnboundedSender<PermissionChange>,
+) {
+    let (native_tx, mut native_rx) = mpsc::unbounded_channel();
+    let mut native: HashSet<PermissionType> = manager
+        .backend()
+        .watch_changes(&types, interval, native_tx)
+        .into_iter()
+        .collect();
+    let mut polled: Vec<PermissionType> = types
+        .iter()
+        .copied()
+        .filter(|t| !native.contains(t))
+        .collect();
+
+    let mut last: HashMap<PermissionType, PermissionStatus> =
+        probe(&manager, types).await.into_iter().collect();
+
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    // The first tick completes immediately and the snapshot above already covers it
+    ticker.tick().await;
+
+    loop {
+        let due = tokio::select! {
+            _ = tx.closed() => return,
+            _ = ticker.tick(), if !polled.is_empty() => polled.clone(),
+            typ = native_rx.recv(), if !native.is_empty() => {
+                let Some(typ) = typ else {
+                    // The backend stopped notifying; poll its types from now on
+                    polled.extend(native.drain());
+                    continue;
+                };
+                let mut due = vec![typ];
+                while let Ok(typ) = native_rx.try_recv() {
+                    if !due.contains(&typ) {
+                        due.push(typ);
+                    }
+                }
+                // The backend told us the cached value is stale
+                for typ in &due {
+                    manager.invalidate(*typ);
+                }
+                due
+            },
+        };
+
+        for (typ, new) in probe(&manager, due).await {
+            if let Some(old) = last.insert(typ, new)
+                && old != new
+            {
+                let change = PermissionChange {
+                    permission: typ,
+                    old,
+                    new,
+                    timestamp: SystemTime::now(),
+                };
+                if tx.send(change).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Re-check `types`; failed checks are logged and skipped
+async fn probe(
+    manager: &PermissionManager,
+    types: Vec<PermissionType>,
+) -> Vec<(PermissionType, PermissionStatus)> {
+    let mut statuses = Vec::with_capacity(types.len());
+    for typ in types {
+        match manager.recheck(typ).await {
+            Ok(status) => statuses.push((typ, status)),
+            Err(e) => crate::trace::watch_failed(typ, &e),
+        }
+    }
+    statuses
+}
@@ -0,0 +1,114 @@
+//! Integration tests for path access checks
+
+use std::sync::Arc;
+
+use kodegen_native_permissions::testing::FakePermissionBackend;
+use kodegen_native_permissions::{
+    AccessMode, ErrorKind, PermissionManager, PermissionStatus, PermissionType, Policy, PolicyRule,
+};
+
+const ALL_MODES: [AccessMode; 4] = [
+    AccessMode::Read,
+    AccessMode::Write,
+    AccessMode::Execute,
+    AccessMode::Create,
+];
+
+fn manager() -> PermissionManager {
+    PermissionManager::with_backend(Arc::new(FakePermissionBackend::new()))
+}
+
+#[test]
+fn test_modes_of_existing_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("main.rs");
+    std::fs::write(&file, "fn main() {}").unwrap();
+
+    let access = manager().check_path(&file, &ALL_MODES);
+    assert_eq!(access.category, None);
+    assert_eq!(access.modes.len(), 4);
+    assert!(access.allows(AccessMode::Read));
+    assert!(access.allows(AccessMode::Write));
+    assert!(access.allows(AccessMode::Create));
+    // Plain files written by the test are not executable on Unix
+    #[cfg(unix)]
+    assert!(matches!(
+        access.status(AccessMode::Execute),
+        Some(Ok(PermissionStatus::Denied))
+    ));
+
+    // Probing leaves the file and its directory untouched
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "fn main() {}");
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_missing_path_can_be_created() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("src").join("generated").join("lib.rs");
+
+    let access = manager().check_path(&path, &[AccessMode::Create, AccessMode::Read]);
+    assert!(access.allows(AccessMode::Create));
+    assert_eq!(
+        access.status(AccessMode::Read).unwrap().as_ref().unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+    assert!(access.status(AccessMode::Write).is_none());
+    assert!(!path.parent().unwrap().exists());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_category_and_policy() {
+    let manager = manager().with_policy(
+        Policy::new().with_rule(PolicyRule::forbid("no-system", [PermissionType::FullDiskAccess])),
+    );
+
+    let access = manager.check_path("/etc/../etc/hostname", &[AccessMode::Read]);
+    assert_eq!(access.category, Some(PermissionType::FullDiskAccess));
+    assert!(matches!(
+        access.status(AccessMode::Read),
+        Some(Ok(PermissionStatus::Restricted))
+    ));
+
+    // Generic POSIX locations are not full disk access territory
+    assert_eq!(
+        manager.check_path("/var/tmp", &[AccessMode::Read]).category,
+        None
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_execute_follows_the_matching_class() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("run.sh");
+    std::fs::write(&script, "#!/bin/sh\n").unwrap();
+    let execute = |mode| {
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(mode)).unwrap();
+        let access = manager().check_path(&script, &[AccessMode::Execute]);
+        access.status(AccessMode::Execute).unwrap().clone().unwrap()
+    };
+
+    assert_eq!(execute(0o700), PermissionStatus::Authorized);
+    // Group and others may run it but its owner may not, except as root, for
+    // whom any exec bit will do
+    let expected = if unsafe { libc::geteuid() } == 0 {
+        PermissionStatus::Authorized
+    } else {
+        PermissionStatus::Denied
+    };
+    assert_eq!(execute(0o611), expected);
+    assert_eq!(execute(0o600), PermissionStatus::Denied);
+}
+
+#[test]
+fn test_own_home_is_not_protected() {
+    let Some(home) = dirs::home_dir() else {
+        return;
+    };
+    let access = manager().check_path(home.join("project"), &[AccessMode::Read]);
+    assert_eq!(access.category, None);
+}
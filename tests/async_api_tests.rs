@@ -1,6 +1,45 @@
 //! Integration tests for async Tokio API
 
-use kodegen_native_permissions::{PermissionManager, PermissionType};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use futures::future::BoxFuture;
+use kodegen_native_permissions::testing::FakePermissionBackend;
+use kodegen_native_permissions::{
+    PermissionError, PermissionHandler, PermissionManager, PermissionStatus, PermissionType,
+};
+use tokio::sync::oneshot;
+
+/// Backend whose async probe grants what its sync probe denies
+#[derive(Default)]
+struct AsyncOnlyBackend {
+    sync_checks: AtomicUsize,
+}
+
+impl PermissionHandler for AsyncOnlyBackend {
+    fn check_permission(&self, _typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
+        self.sync_checks.fetch_add(1, Ordering::SeqCst);
+        Ok(PermissionStatus::Denied)
+    }
+
+    fn check_permission_async(
+        &self,
+        _typ: PermissionType,
+    ) -> BoxFuture<'_, Result<PermissionStatus, PermissionError>> {
+        Box::pin(async {
+            tokio::task::yield_now().await;
+            Ok(PermissionStatus::Authorized)
+        })
+    }
+
+    fn request_permission(
+        &self,
+        typ: PermissionType,
+        tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        tx.send(self.check_permission(typ)).ok();
+    }
+}
 
 #[tokio::test]
 async fn test_manager_creation() {
@@ -59,3 +98,46 @@ async fn test_clear_cache() {
     // Clear should not panic
     manager.clear_cache();
 }
+
+#[tokio::test]
+async fn test_check_permission_async() {
+    let backend = Arc::new(AsyncOnlyBackend::default());
+    let manager = PermissionManager::with_backend(backend.clone());
+
+    // The async check takes the backend's async probe, not its sync one
+    let status = manager.check_permission_async(PermissionType::Camera).await;
+    assert!(matches!(status, Ok(PermissionStatus::Authorized)));
+    assert_eq!(backend.sync_checks.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_check_permission_sync_current_thread() {
+    let backend = Arc::new(AsyncOnlyBackend::default());
+    let manager = PermissionManager::with_backend(backend.clone());
+
+    // The sync check must not deadlock the single runtime thread
+    let status = manager.check_permission(PermissionType::Camera);
+    assert!(matches!(status, Ok(PermissionStatus::Denied)));
+    assert_eq!(backend.sync_checks.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_check_permission_sync_multi_thread() {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend.set_status(PermissionType::Microphone, PermissionStatus::Restricted);
+    let manager = PermissionManager::with_backend(backend.clone());
+
+    let status = manager.check_permission(PermissionType::Microphone);
+    assert!(matches!(status, Ok(PermissionStatus::Restricted)));
+    assert_eq!(backend.check_count(PermissionType::Microphone), 1);
+}
+
+#[test]
+fn test_check_permission_sync_without_runtime() {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend.set_status(PermissionType::Location, PermissionStatus::Denied);
+    let manager = PermissionManager::with_backend(backend);
+
+    let status = manager.check_permission(PermissionType::Location);
+    assert!(matches!(status, Ok(PermissionStatus::Denied)));
+}
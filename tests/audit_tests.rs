@@ -0,0 +1,166 @@
+//! Integration tests for the hash-chained audit log
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use kodegen_native_permissions::audit::{self, AuditViolation};
+use kodegen_native_permissions::testing::FakePermissionBackend;
+use kodegen_native_permissions::{
+    AccessMode, AuditLog, Caller, PermissionManager, PermissionStatus, PermissionType, Policy,
+    PolicyRule, RequestOptions,
+};
+
+async fn write_log(path: &std::path::Path) -> String {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend
+        .set_status(PermissionType::Camera, PermissionStatus::Denied)
+        .push_response(PermissionType::Camera, Ok(PermissionStatus::Authorized));
+    let log = Arc::new(AuditLog::open(path).unwrap());
+    let manager = PermissionManager::with_backend(backend)
+        .with_audit(log.clone())
+        .with_caller(Caller::new().with_tool("snapshot").with_agent("coder"));
+
+    manager.check_permission(PermissionType::Camera).unwrap();
+    manager
+        .request_permission(PermissionType::Camera)
+        .await
+        .unwrap();
+    manager
+        .check_permission_async(PermissionType::Microphone)
+        .await
+        .unwrap();
+    log.head()
+}
+
+#[tokio::test]
+async fn test_records_checks_and_requests() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("audit.jsonl");
+    let head = write_log(&path).await;
+
+    let summary = audit::verify_head(&path, &head).unwrap();
+    assert_eq!(summary.entries, 3);
+    assert_eq!(summary.head, head);
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<_> = contents.lines().collect();
+    assert!(lines[0].contains(r#""operation":"check","permission":"camera","tool":"snapshot","agent":"coder","outcome":"denied","prompted":false,"backend":"fake""#));
+    assert!(lines[1].contains(r#""operation":"request","#));
+    assert!(lines[1].contains(r#""outcome":"authorized","prompted":true,"#));
+
+    // Reopening continues the chain
+    let log = AuditLog::open(&path).unwrap();
+    assert_eq!(log.head(), head);
+}
+
+#[tokio::test]
+async fn test_detects_edits_and_truncation() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("audit.jsonl");
+    let head = write_log(&path).await;
+    let original = std::fs::read_to_string(&path).unwrap();
+
+    std::fs::write(&path, original.replacen("denied", "authorized", 1)).unwrap();
+    assert_eq!(
+        audit::verify(&path),
+        Err(AuditViolation::Edited { line: 1 })
+    );
+    assert!(AuditLog::open(&path).is_err());
+
+    let without_first = original.split_once('\n').unwrap().1;
+    std::fs::write(&path, without_first).unwrap();
+    assert_eq!(
+        audit::verify(&path),
+        Err(AuditViolation::OutOfSequence { line: 1 })
+    );
+
+    let without_last = &original[..original.trim_end().rfind('\n').unwrap() + 1];
+    std::fs::write(&path, without_last).unwrap();
+    assert_eq!(audit::verify(&path).unwrap().entries, 2);
+    assert_eq!(
+        audit::verify_head(&path, &head),
+        Err(AuditViolation::Truncated { entries: 2 })
+    );
+
+    std::fs::write(&path, &original[..original.len() - 10]).unwrap();
+    assert_eq!(
+        audit::verify(&path),
+        Err(AuditViolation::Malformed { line: 3 })
+    );
+}
+
+#[tokio::test]
+async fn test_handles_share_one_chain() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("audit.jsonl");
+    let backend = Arc::new(FakePermissionBackend::new());
+    let first = PermissionManager::with_backend(backend.clone())
+        .with_audit(Arc::new(AuditLog::open(&path).unwrap()));
+    let second = PermissionManager::with_backend(backend)
+        .with_audit(Arc::new(AuditLog::open(&path).unwrap()));
+
+    // Each handle continues from the other's entries, not its own last one
+    for _ in 0..2 {
+        first.check_permission(PermissionType::Camera).unwrap();
+        second.check_permission(PermissionType::Microphone).unwrap();
+    }
+    assert_eq!(audit::verify(&path).unwrap().entries, 4);
+}
+
+#[tokio::test]
+async fn test_records_only_prompts_shown_to_the_caller() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("audit.jsonl");
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend.set_delay(PermissionType::Camera, Duration::from_millis(100));
+    let manager = PermissionManager::with_backend(backend)
+        .with_audit(Arc::new(AuditLog::open(&path).unwrap()));
+
+    // The second camera request shares the first one's prompt, and the
+    // microphone request gives up while the camera prompt is still on screen
+    let queued = RequestOptions::new().with_timeout(Duration::from_millis(20));
+    let (first, joined, timed_out) = tokio::join!(
+        manager.request_permission(PermissionType::Camera),
+        manager.request_permission(PermissionType::Camera),
+        manager.request_permission_with(PermissionType::Microphone, queued),
+    );
+    first.unwrap();
+    joined.unwrap();
+    assert!(timed_out.is_err());
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let prompted = |permission: &str| {
+        let mut prompted: Vec<_> = contents
+            .lines()
+            .filter(|line| line.contains(&format!(r#""permission":"{permission}""#)))
+            .map(|line| line.contains(r#""prompted":true"#))
+            .collect();
+        prompted.sort();
+        prompted
+    };
+    assert_eq!(prompted("camera"), [false, true]);
+    assert_eq!(prompted("microphone"), [false]);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_records_path_checks_under_a_category() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("audit.jsonl");
+    let policy =
+        Policy::new().with_rule(PolicyRule::forbid("no-system", [PermissionType::FullDiskAccess]));
+    let manager = PermissionManager::with_backend(Arc::new(FakePermissionBackend::new()))
+        .with_audit(Arc::new(AuditLog::open(&path).unwrap()))
+        .with_policy(policy);
+
+    manager.check_path("/etc/hostname", &[AccessMode::Read, AccessMode::Write]);
+    // Unprotected locations have no permission to record
+    manager.check_path(dir.path(), &[AccessMode::Read]);
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<_> = contents.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].contains(r#""operation":"read","permission":"full_disk_access","#));
+    assert!(lines[0].contains(r#""outcome":"restricted","prompted":false,"backend":"policy""#));
+    assert!(lines[1].contains(r#""operation":"write","#));
+}
@@ -0,0 +1,116 @@
+//! Integration tests for injectable permission backends
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use kodegen_native_permissions::{
+    PermissionError, PermissionHandler, PermissionManager, PermissionStatus, PermissionType,
+};
+use tokio::sync::oneshot;
+
+/// Backend that answers every permission with a fixed status and counts calls
+struct StaticBackend {
+    status: PermissionStatus,
+    checks: AtomicUsize,
+}
+
+impl PermissionHandler for StaticBackend {
+    fn check_permission(&self, _typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
+        self.checks.fetch_add(1, Ordering::SeqCst);
+        Ok(self.status)
+    }
+
+    fn request_permission(
+        &self,
+        _typ: PermissionType,
+        tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        tx.send(Ok(PermissionStatus::Authorized)).ok();
+    }
+}
+
+/// Decorator that forces Camera to Denied and defers everything else
+struct DenyCamera(Arc<dyn PermissionHandler>);
+
+impl PermissionHandler for DenyCamera {
+    fn check_permission(&self, typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
+        match typ {
+            PermissionType::Camera => Ok(PermissionStatus::Denied),
+            _ => self.0.check_permission(typ),
+        }
+    }
+
+    fn request_permission(
+        &self,
+        typ: PermissionType,
+        tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        match typ {
+            PermissionType::Camera => {
+                tx.send(Ok(PermissionStatus::Denied)).ok();
+            },
+            _ => self.0.request_permission(typ, tx),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_custom_backend_is_used_and_cached() {
+    let backend = Arc::new(StaticBackend {
+        status: PermissionStatus::NotDetermined,
+        checks: AtomicUsize::new(0),
+    });
+    let manager = PermissionManager::with_backend(backend.clone());
+
+    assert_eq!(
+        manager
+            .check_permission(PermissionType::Microphone)
+            .unwrap(),
+        PermissionStatus::NotDetermined
+    );
+    assert_eq!(
+        manager
+            .check_permission(PermissionType::Microphone)
+            .unwrap(),
+        PermissionStatus::NotDetermined
+    );
+    assert_eq!(backend.checks.load(Ordering::SeqCst), 1);
+
+    // A request result replaces the cached status
+    let status = manager
+        .request_permission(PermissionType::Microphone)
+        .await
+        .unwrap();
+    assert_eq!(status, PermissionStatus::Authorized);
+    assert_eq!(
+        manager
+            .check_permission(PermissionType::Microphone)
+            .unwrap(),
+        PermissionStatus::Authorized
+    );
+}
+
+#[tokio::test]
+async fn test_decorated_backend() {
+    let inner = Arc::new(StaticBackend {
+        status: PermissionStatus::Authorized,
+        checks: AtomicUsize::new(0),
+    });
+    let manager = PermissionManager::with_backend(Arc::new(DenyCamera(inner)));
+
+    assert_eq!(
+        manager.check_permission(PermissionType::Camera).unwrap(),
+        PermissionStatus::Denied
+    );
+    assert_eq!(
+        manager.check_permission(PermissionType::Location).unwrap(),
+        PermissionStatus::Authorized
+    );
+    assert_eq!(
+        manager
+            .request_permission(PermissionType::Camera)
+            .await
+            .unwrap(),
+        PermissionStatus::Denied
+    );
+}
@@ -0,0 +1,104 @@
+//! Integration tests for batch checks and requests
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::StreamExt;
+use kodegen_native_permissions::testing::FakePermissionBackend;
+use kodegen_native_permissions::{
+    BatchOptions, ErrorKind, PermissionError, PermissionHandler, PermissionManager,
+    PermissionStatus, PermissionType,
+};
+use tokio::sync::oneshot;
+
+/// Backend whose camera probe panics
+struct PanickingBackend;
+
+impl PermissionHandler for PanickingBackend {
+    fn check_permission(&self, typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
+        assert_ne!(typ, PermissionType::Camera, "camera probe crashed");
+        Ok(PermissionStatus::Authorized)
+    }
+
+    fn request_permission(
+        &self,
+        typ: PermissionType,
+        tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        tx.send(self.check_permission(typ)).ok();
+    }
+}
+
+#[tokio::test]
+async fn test_results_keep_input_order() {
+    let backend = Arc::new(FakePermissionBackend::with_default_status(
+        PermissionStatus::Authorized,
+    ));
+    backend.set_delay(PermissionType::Camera, Duration::from_millis(50));
+    backend.set_status(PermissionType::Location, PermissionStatus::Denied);
+    let manager = PermissionManager::with_backend(backend);
+    let types = [
+        PermissionType::Camera,
+        PermissionType::Location,
+        PermissionType::Camera,
+    ];
+
+    // The stream yields the fast location check first
+    let first = manager
+        .check_many_stream(&types, &BatchOptions::new())
+        .next()
+        .await
+        .unwrap();
+    assert_eq!(
+        (first.index, first.permission),
+        (1, PermissionType::Location)
+    );
+
+    // The slow camera checks finish last but stay in place
+    let results = manager.check_many(&types, &BatchOptions::new()).await;
+    let order: Vec<_> = results.iter().map(|r| (r.index, r.permission)).collect();
+    assert_eq!(order, [(0, types[0]), (1, types[1]), (2, types[2])]);
+    assert!(matches!(results[1].result, Ok(PermissionStatus::Denied)));
+}
+
+#[tokio::test]
+async fn test_concurrency_limit() {
+    let backend = Arc::new(FakePermissionBackend::new());
+    let types = [
+        PermissionType::Camera,
+        PermissionType::Microphone,
+        PermissionType::Location,
+    ];
+    for typ in types {
+        backend.set_silent(typ);
+        backend.set_delay(typ, Duration::from_millis(30));
+    }
+    let manager = PermissionManager::with_backend(backend);
+
+    let start = Instant::now();
+    let results = manager
+        .request_many(&types, &BatchOptions::new().with_concurrency(1))
+        .await;
+    assert_eq!(results.len(), 3);
+    assert!(start.elapsed() >= Duration::from_millis(90));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_panicked_task_becomes_error() {
+    let manager = PermissionManager::with_backend(Arc::new(PanickingBackend));
+    let types = [PermissionType::Camera, PermissionType::Microphone];
+
+    let results = manager.check_many(&types, &BatchOptions::new()).await;
+    assert_eq!(
+        results[0].result.as_ref().unwrap_err().kind(),
+        ErrorKind::Unknown
+    );
+    assert!(matches!(
+        results[1].result,
+        Ok(PermissionStatus::Authorized)
+    ));
+
+    // The map no longer comes back short
+    let requested = manager.request_permissions(&types).await;
+    assert_eq!(requested.len(), 2);
+}
@@ -0,0 +1,59 @@
+//! Integration tests for the blocking API facade
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use kodegen_native_permissions::testing::FakePermissionBackend;
+use kodegen_native_permissions::{
+    ErrorKind, PermissionManager, PermissionStatus, PermissionType, RequestOptions, blocking,
+};
+
+fn camera_backend() -> Arc<FakePermissionBackend> {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend
+        .set_status(PermissionType::Camera, PermissionStatus::NotDetermined)
+        .push_response(PermissionType::Camera, Ok(PermissionStatus::Authorized));
+    backend
+}
+
+#[test]
+fn test_requests_without_runtime() {
+    let backend = camera_backend();
+    let manager = blocking::PermissionManager::with_backend(backend.clone());
+
+    assert_eq!(
+        manager.check_permission(PermissionType::Camera).unwrap(),
+        PermissionStatus::NotDetermined
+    );
+    assert_eq!(
+        manager.request_permission(PermissionType::Camera).unwrap(),
+        PermissionStatus::Authorized
+    );
+
+    // Timeouts run on the internal executor too
+    backend.set_delay(PermissionType::Microphone, Duration::from_secs(5));
+    let result = manager.request_permission_with(
+        PermissionType::Microphone,
+        RequestOptions::new().with_timeout(Duration::from_millis(20)),
+    );
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::Timeout);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_shares_cache_with_async_manager() {
+    let backend = camera_backend();
+    let manager = PermissionManager::with_backend(backend.clone());
+
+    // Safe to call from inside a current-thread runtime
+    let blocking = manager.blocking();
+    blocking.request_permission(PermissionType::Camera).unwrap();
+
+    assert_eq!(
+        manager
+            .check_permission_async(PermissionType::Camera)
+            .await
+            .unwrap(),
+        PermissionStatus::Authorized
+    );
+    assert_eq!(backend.check_count(PermissionType::Camera), 0);
+}
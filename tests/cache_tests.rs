@@ -0,0 +1,183 @@
+//! Integration tests for cache TTL and invalidation policies
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use kodegen_native_permissions::testing::FakePermissionBackend;
+use kodegen_native_permissions::{
+    CachePolicy, DiskCache, PermissionManager, PermissionStatus, PermissionType, SessionIdentity,
+};
+
+#[tokio::test(start_paused = true)]
+async fn test_negative_results_expire_before_positive() {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend
+        .set_status(PermissionType::Camera, PermissionStatus::Denied)
+        .set_status(PermissionType::Microphone, PermissionStatus::Authorized);
+    let manager = PermissionManager::with_backend(backend.clone()).with_cache_policy(
+        CachePolicy::default()
+            .with_positive_ttl(Some(Duration::from_secs(60)))
+            .with_negative_ttl(Some(Duration::from_secs(5))),
+    );
+
+    manager.check_permission(PermissionType::Camera).unwrap();
+    manager
+        .check_permission(PermissionType::Microphone)
+        .unwrap();
+
+    // User grants the camera in system settings
+    backend.set_status(PermissionType::Camera, PermissionStatus::Authorized);
+    assert_eq!(
+        manager.check_permission(PermissionType::Camera).unwrap(),
+        PermissionStatus::Denied
+    );
+
+    tokio::time::advance(Duration::from_secs(6)).await;
+    assert_eq!(
+        manager.check_permission(PermissionType::Camera).unwrap(),
+        PermissionStatus::Authorized
+    );
+    manager
+        .check_permission(PermissionType::Microphone)
+        .unwrap();
+    assert_eq!(backend.check_count(PermissionType::Camera), 2);
+    assert_eq!(backend.check_count(PermissionType::Microphone), 1);
+}
+
+#[test]
+fn test_volatile_types_are_not_cached() {
+    let backend = Arc::new(FakePermissionBackend::new());
+    let manager = PermissionManager::with_backend(backend.clone())
+        .with_cache_policy(CachePolicy::never_expire().with_uncached(PermissionType::WiFi));
+
+    for _ in 0..3 {
+        manager.check_permission(PermissionType::WiFi).unwrap();
+        manager.check_permission(PermissionType::Camera).unwrap();
+    }
+    assert_eq!(backend.check_count(PermissionType::WiFi), 3);
+    assert_eq!(backend.check_count(PermissionType::Camera), 1);
+
+    manager.invalidate(PermissionType::Camera);
+    manager.check_permission(PermissionType::Camera).unwrap();
+    assert_eq!(backend.check_count(PermissionType::Camera), 2);
+}
+
+#[test]
+fn test_shared_cache_across_managers() {
+    let first = Arc::new(FakePermissionBackend::with_default_status(
+        PermissionStatus::Authorized,
+    ));
+    let second = Arc::new(FakePermissionBackend::new());
+    let policy = CachePolicy::never_expire().with_shared(true);
+    let a = PermissionManager::with_backend(first.clone()).with_cache_policy(policy.clone());
+    let b = PermissionManager::with_backend(second.clone()).with_cache_policy(policy);
+
+    // AddressBook is not touched by any other test in this binary
+    a.check_permission(PermissionType::AddressBook).unwrap();
+    assert_eq!(
+        b.check_permission(PermissionType::AddressBook).unwrap(),
+        PermissionStatus::Authorized
+    );
+    assert_eq!(second.check_count(PermissionType::AddressBook), 0);
+}
+
+fn session(boot_id: &str) -> SessionIdentity {
+    SessionIdentity {
+        boot_id: Some(boot_id.to_string()),
+        user: Some("1000".to_string()),
+        session: Some("2".to_string()),
+    }
+}
+
+/// Manager for a fresh "process" whose backend grants the camera
+fn process(disk: DiskCache) -> (Arc<FakePermissionBackend>, PermissionManager) {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend.set_status(PermissionType::Camera, PermissionStatus::Authorized);
+    let manager = PermissionManager::with_backend(backend.clone())
+        .with_cache_policy(CachePolicy::default().with_disk(disk));
+    (backend, manager)
+}
+
+#[test]
+fn test_disk_cache_survives_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let disk = DiskCache::at(dir.path().join("app.cache"), session("boot-a"));
+
+    let (backend, manager) = process(disk.clone());
+    manager.check_permission(PermissionType::Camera).unwrap();
+    assert_eq!(backend.check_count(PermissionType::Camera), 1);
+    drop(manager);
+
+    let (backend, manager) = process(disk.clone());
+    assert_eq!(
+        manager.check_permission(PermissionType::Camera).unwrap(),
+        PermissionStatus::Authorized
+    );
+    assert_eq!(backend.check_count(PermissionType::Camera), 0);
+
+    // Clearing the cache clears it for later processes too
+    manager.clear_cache();
+    let (backend, manager) = process(disk);
+    manager.check_permission(PermissionType::Camera).unwrap();
+    assert_eq!(backend.check_count(PermissionType::Camera), 1);
+}
+
+#[test]
+fn test_uncached_types_never_touch_the_disk() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.cache");
+
+    let (backend, manager) = process(DiskCache::at(&path, session("boot-a")));
+    for _ in 0..3 {
+        manager.check_permission(PermissionType::Bluetooth).unwrap();
+    }
+    manager.invalidate(PermissionType::Camera);
+    drop(manager);
+    assert_eq!(backend.check_count(PermissionType::Bluetooth), 3);
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_disk_cache_invalidated_by_new_session() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.cache");
+
+    let (_, manager) = process(DiskCache::at(&path, session("boot-a")));
+    manager.check_permission(PermissionType::Camera).unwrap();
+
+    // After a reboot the statuses written before it are ignored
+    let (backend, manager) = process(DiskCache::at(&path, session("boot-b")));
+    manager.check_permission(PermissionType::Camera).unwrap();
+    assert_eq!(backend.check_count(PermissionType::Camera), 1);
+
+    // A different user never sees them either
+    let mut other_user = session("boot-b");
+    other_user.user = Some("1001".to_string());
+    let (backend, manager) = process(DiskCache::at(&path, other_user));
+    manager.check_permission(PermissionType::Camera).unwrap();
+    assert_eq!(backend.check_count(PermissionType::Camera), 1);
+}
+
+#[tokio::test]
+async fn test_disk_cache_written_off_the_runtime() {
+    let dir = tempfile::tempdir().unwrap();
+    let disk = DiskCache::at(dir.path().join("app.cache"), session("boot-a"));
+
+    let (_, manager) = process(disk.clone());
+    manager
+        .check_permission_async(PermissionType::Camera)
+        .await
+        .unwrap();
+    // Dropping the manager waits for the queued write
+    drop(manager);
+
+    let (backend, manager) = process(disk);
+    assert_eq!(
+        manager
+            .check_permission_async(PermissionType::Camera)
+            .await
+            .unwrap(),
+        PermissionStatus::Authorized
+    );
+    assert_eq!(backend.check_count(PermissionType::Camera), 0);
+}
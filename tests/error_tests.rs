@@ -0,0 +1,50 @@
+//! Integration tests for structured permission errors
+
+use std::error::Error;
+use std::io;
+
+use kodegen_native_permissions::{ErrorKind, PermissionError};
+
+#[test]
+fn test_io_error_kept_as_source() {
+    let error = PermissionError::from(io::Error::new(io::ErrorKind::TimedOut, "device busy"))
+        .with_message("Device access failed")
+        .with_probe("/dev/video0");
+
+    assert_eq!(error.kind(), ErrorKind::Timeout);
+    assert_eq!(error.code(), ErrorKind::Timeout.code());
+    assert_eq!(error.source().unwrap().to_string(), "device busy");
+    assert_eq!(
+        error.to_string(),
+        "Operation timed out: Device access failed (probe /dev/video0)"
+    );
+}
+
+#[test]
+fn test_missing_file_is_not_found() {
+    let error = PermissionError::from(io::Error::from(io::ErrorKind::NotFound));
+    assert_eq!(error.kind(), ErrorKind::NotFound);
+}
+
+#[test]
+fn test_codes_are_stable() {
+    let codes = [
+        (ErrorKind::Unknown, 1000),
+        (ErrorKind::Denied, 1001),
+        (ErrorKind::Restricted, 1002),
+        (ErrorKind::UserCancelled, 1003),
+        (ErrorKind::Cancelled, 1004),
+        (ErrorKind::Timeout, 1005),
+        (ErrorKind::ServiceUnavailable, 1006),
+        (ErrorKind::BusUnavailable, 1007),
+        (ErrorKind::SandboxBlocked, 1008),
+        (ErrorKind::NotSupportedOnPlatform, 1009),
+        (ErrorKind::System, 1010),
+        (ErrorKind::Platform, 1011),
+        (ErrorKind::InvalidConfig, 1012),
+        (ErrorKind::NotFound, 1013),
+    ];
+    for (kind, code) in codes {
+        assert_eq!(kind.code(), code, "{kind:?}");
+    }
+}
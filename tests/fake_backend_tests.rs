@@ -0,0 +1,101 @@
+//! Integration tests for the scriptable fake backend
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use kodegen_native_permissions::testing::{FakeCall, FakePermissionBackend};
+use kodegen_native_permissions::{
+    ErrorKind, PermissionError, PermissionManager, PermissionStatus, PermissionType,
+};
+
+#[tokio::test]
+async fn test_denied_then_granted_after_prompt() {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend
+        .set_status(PermissionType::Camera, PermissionStatus::Denied)
+        .push_response(PermissionType::Camera, Ok(PermissionStatus::Authorized));
+    let manager = PermissionManager::with_backend(backend.clone());
+
+    assert_eq!(
+        manager.check_permission(PermissionType::Camera).unwrap(),
+        PermissionStatus::Denied
+    );
+    assert_eq!(
+        manager
+            .request_permission(PermissionType::Camera)
+            .await
+            .unwrap(),
+        PermissionStatus::Authorized
+    );
+
+    manager.clear_cache();
+    assert_eq!(
+        manager.check_permission(PermissionType::Camera).unwrap(),
+        PermissionStatus::Authorized
+    );
+    assert_eq!(
+        backend.calls(),
+        vec![
+            FakeCall::Check(PermissionType::Camera),
+            FakeCall::Request(PermissionType::Camera),
+            FakeCall::Check(PermissionType::Camera),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_response_sequence_falls_back_to_status() {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend.push_responses(
+        PermissionType::Microphone,
+        [
+            Ok(PermissionStatus::Denied),
+            Err(PermissionError::system("portal unavailable")),
+        ],
+    );
+    let manager = PermissionManager::with_backend(backend.clone());
+
+    let first = manager.request_permission(PermissionType::Microphone).await;
+    assert_eq!(first.unwrap(), PermissionStatus::Denied);
+    let second = manager.request_permission(PermissionType::Microphone).await;
+    assert_eq!(second.unwrap_err().kind(), ErrorKind::System);
+
+    // Queue exhausted: the last successful answer is the current status
+    let third = manager.request_permission(PermissionType::Microphone).await;
+    assert_eq!(third.unwrap(), PermissionStatus::Denied);
+    assert_eq!(backend.request_count(PermissionType::Microphone), 3);
+}
+
+#[tokio::test]
+async fn test_configured_error() {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend.set_error(PermissionType::Location, ErrorKind::Restricted.into());
+    let manager = PermissionManager::with_backend(backend.clone());
+
+    let error = manager.check_permission(PermissionType::Location).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Restricted);
+    assert_eq!(error.backend(), Some("fake"));
+
+    backend.clear_error(PermissionType::Location);
+    assert_eq!(
+        manager.check_permission(PermissionType::Location).unwrap(),
+        PermissionStatus::NotDetermined
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_delayed_request() {
+    let backend = Arc::new(FakePermissionBackend::with_default_status(
+        PermissionStatus::Authorized,
+    ));
+    backend.set_delay(PermissionType::Bluetooth, Duration::from_secs(30));
+    let manager = PermissionManager::with_backend(backend);
+
+    let start = tokio::time::Instant::now();
+    let status = manager
+        .request_permission(PermissionType::Bluetooth)
+        .await
+        .unwrap();
+    assert_eq!(status, PermissionStatus::Authorized);
+    assert!(start.elapsed() >= Duration::from_secs(30));
+}
@@ -0,0 +1,163 @@
+//! Integration tests for stable identifiers, categories and serde support
+
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime};
+
+use kodegen_native_permissions::{
+    Confidence, ErrorKind, Evidence, PermissionCategory, PermissionChange, PermissionError,
+    PermissionReport, PermissionStatus, PermissionType, Support,
+};
+use serde::Deserialize;
+use serde::de::IntoDeserializer;
+use serde::de::value::{Error as ValueError, MapDeserializer};
+use serde_json::json;
+
+#[test]
+fn test_identifiers_round_trip() {
+    let ids: HashSet<_> = PermissionType::all().map(|typ| typ.as_str()).collect();
+    assert_eq!(ids.len(), PermissionType::all().count());
+    assert!(!PermissionType::all().any(|typ| typ == PermissionType::All));
+
+    for typ in PermissionType::all() {
+        assert_eq!(typ.as_str().parse::<PermissionType>().unwrap(), typ);
+    }
+    assert_eq!(PermissionType::FaceID.as_str(), "face_id");
+    assert_eq!(PermissionType::WiFi.as_str(), "wifi");
+    assert_eq!(
+        "prompt_required".parse::<PermissionStatus>().unwrap(),
+        PermissionStatus::PromptRequired
+    );
+
+    let error = "Full Disk Access".parse::<PermissionType>().unwrap_err();
+    assert_eq!(error.value(), "Full Disk Access");
+}
+
+#[test]
+fn test_categories_partition_permissions() {
+    let total: usize = PermissionCategory::all()
+        .map(|category| category.permissions().count())
+        .sum();
+    assert_eq!(total, PermissionType::all().count());
+
+    assert_eq!(PermissionType::Camera.category(), PermissionCategory::Media);
+    assert_eq!(
+        PermissionType::DownloadsFolder.category(),
+        PermissionCategory::Files
+    );
+    assert_eq!(
+        PermissionType::Bluetooth.category(),
+        PermissionCategory::Connectivity
+    );
+    assert_eq!(
+        PermissionType::Contacts.category(),
+        PermissionCategory::PersonalData
+    );
+}
+
+#[test]
+fn test_deserialize_from_identifiers() {
+    let typ = PermissionType::deserialize(IntoDeserializer::<ValueError>::into_deserializer(
+        "screen_capture",
+    ))
+    .unwrap();
+    assert_eq!(typ, PermissionType::ScreenCapture);
+    assert!(
+        PermissionStatus::deserialize(IntoDeserializer::<ValueError>::into_deserializer(
+            "Authorized"
+        ))
+        .is_err()
+    );
+
+    let fields = [("kind", "service_unavailable")];
+    let error =
+        PermissionError::deserialize(MapDeserializer::<_, ValueError>::new(fields.into_iter()))
+            .unwrap();
+    assert_eq!(error.kind(), ErrorKind::ServiceUnavailable);
+    assert_eq!(error.code(), 1006);
+    assert_eq!(error.message(), None);
+
+    let fields = [("kind", "Service unavailable")];
+    assert!(
+        PermissionError::deserialize(MapDeserializer::<_, ValueError>::new(fields.into_iter()))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_wire_format() {
+    let error = PermissionError::new(ErrorKind::Timeout)
+        .with_message("No answer")
+        .with_backend("linux")
+        .with_probe("org.freedesktop.portal.Camera")
+        .with_source(std::io::Error::other("bus closed"));
+    let json = serde_json::to_value(&error).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "kind": "timeout",
+            "code": 1005,
+            "message": "No answer",
+            "backend": "linux",
+            "probe": "org.freedesktop.portal.Camera",
+            "source": "bus closed",
+        })
+    );
+    let decoded: PermissionError = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(decoded.kind(), ErrorKind::Timeout);
+    assert_eq!(decoded.message(), Some("No answer"));
+    assert_eq!(decoded.probe(), Some("org.freedesktop.portal.Camera"));
+    assert_eq!(serde_json::to_value(&decoded).unwrap(), json);
+
+    let report = PermissionReport {
+        permission: PermissionType::Photos,
+        status: PermissionStatus::Authorized,
+        confidence: Confidence::Heuristic,
+        support: Support::Emulated {
+            via: "user folder access",
+        },
+        evidence: vec![Evidence {
+            probe: "read_dir",
+            target: "/home/user/Pictures".to_string(),
+            outcome: "ok".to_string(),
+            confidence: Confidence::Heuristic,
+        }],
+        path: Some("/home/user/Pictures".into()),
+    };
+    assert_eq!(
+        serde_json::to_value(&report).unwrap(),
+        json!({
+            "permission": "photos",
+            "status": "authorized",
+            "confidence": "heuristic",
+            "support": { "emulated": { "via": "user folder access" } },
+            "evidence": [{
+                "probe": "read_dir",
+                "target": "/home/user/Pictures",
+                "outcome": "ok",
+                "confidence": "heuristic",
+            }],
+            "path": "/home/user/Pictures",
+        })
+    );
+
+    let change = PermissionChange {
+        permission: PermissionType::Camera,
+        old: PermissionStatus::NotDetermined,
+        new: PermissionStatus::Denied,
+        timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+    };
+    assert_eq!(
+        serde_json::to_value(change).unwrap(),
+        json!({
+            "permission": "camera",
+            "old": "not_determined",
+            "new": "denied",
+            "timestamp": { "secs_since_epoch": 1_700_000_000, "nanos_since_epoch": 0 },
+        })
+    );
+
+    for typ in PermissionType::all() {
+        let json = serde_json::to_string(&typ).unwrap();
+        assert_eq!(serde_json::from_str::<PermissionType>(&json).unwrap(), typ);
+    }
+}
@@ -0,0 +1,73 @@
+//! Integration tests for the Linux D-Bus service probes, against a private bus
+
+#![cfg(target_os = "linux")]
+
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+
+use kodegen_native_permissions::{PermissionManager, PermissionStatus, PermissionType};
+
+/// Private `dbus-daemon`, killed on drop
+struct Bus {
+    daemon: Child,
+    address: String,
+}
+
+impl Drop for Bus {
+    fn drop(&mut self) {
+        self.daemon.kill().ok();
+        self.daemon.wait().ok();
+    }
+}
+
+/// Start a bus, or `None` if `dbus-daemon` is not installed
+fn start_bus() -> Option<Bus> {
+    let mut daemon = Command::new("dbus-daemon")
+        .args(["--session", "--nofork", "--print-address"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let mut address = String::new();
+    BufReader::new(daemon.stdout.take()?)
+        .read_line(&mut address)
+        .ok()?;
+    Some(Bus {
+        daemon,
+        address: address.trim().to_string(),
+    })
+}
+
+#[tokio::test]
+async fn test_bluez_and_network_manager_are_probed_on_the_system_bus() {
+    let Some(bus) = start_bus() else {
+        return;
+    };
+    // SAFETY: this is the only test in the binary and no other thread reads the
+    // environment yet
+    unsafe {
+        std::env::set_var("DBUS_SYSTEM_BUS_ADDRESS", &bus.address);
+        std::env::set_var("DBUS_SESSION_BUS_ADDRESS", "unix:path=/nonexistent/bus");
+    }
+    let manager = PermissionManager::new();
+
+    // Nothing owns the service names yet
+    assert!(manager.check_permission_async(PermissionType::Bluetooth).await.is_err());
+
+    // BlueZ answers through the object manager at its root
+    let bluez = zbus::connection::Builder::address(bus.address.as_str())
+        .unwrap()
+        .name("org.bluez")
+        .unwrap()
+        .serve_at("/", zbus::fdo::ObjectManager)
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(
+        manager.check_permission_async(PermissionType::Bluetooth).await.unwrap(),
+        PermissionStatus::Authorized
+    );
+    assert!(manager.check_permission_async(PermissionType::WiFi).await.is_err());
+    drop(bluez);
+}
@@ -0,0 +1,119 @@
+//! Integration tests for requirement manifests and readiness evaluation
+
+use std::sync::Arc;
+
+use kodegen_native_permissions::testing::FakePermissionBackend;
+use kodegen_native_permissions::{
+    ErrorKind, FeatureSpec, Manifest, PermissionManager, PermissionStatus, PermissionType,
+    Readiness,
+};
+
+#[tokio::test]
+async fn test_evaluate_readiness() {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend
+        .set_status(PermissionType::Microphone, PermissionStatus::Authorized)
+        .set_status(PermissionType::Location, PermissionStatus::NotDetermined)
+        .set_status(PermissionType::Camera, PermissionStatus::Denied)
+        .set_status(
+            PermissionType::ScreenCapture,
+            PermissionStatus::NotDetermined,
+        );
+    let manager = PermissionManager::with_backend(backend.clone());
+
+    let manifest = Manifest::new()
+        .with_feature(
+            FeatureSpec::new("voice_notes")
+                .with_required(PermissionType::Microphone, "Record the note")
+                .with_optional(PermissionType::Location, "Tag the note"),
+        )
+        .with_feature(
+            FeatureSpec::new("video_call")
+                .with_required(PermissionType::Camera, "Send video")
+                .with_required(PermissionType::Microphone, "Send audio"),
+        )
+        .with_feature(
+            FeatureSpec::new("screen_share")
+                .with_required(PermissionType::ScreenCapture, "Share the screen"),
+        );
+    let report = manifest.evaluate(&manager).await;
+
+    let readiness: Vec<_> = report.features.iter().map(|f| f.readiness).collect();
+    assert_eq!(
+        readiness,
+        [Readiness::Degraded, Readiness::Blocked, Readiness::Blocked]
+    );
+    assert!(!report.is_ready());
+    assert_eq!(
+        report.to_request,
+        [PermissionType::ScreenCapture, PermissionType::Location]
+    );
+
+    let unmet: Vec<_> = report
+        .feature("video_call")
+        .unwrap()
+        .unmet()
+        .map(|state| state.requirement.permission)
+        .collect();
+    assert_eq!(unmet, [PermissionType::Camera]);
+    // Shared permissions are checked once
+    assert_eq!(backend.check_count(PermissionType::Microphone), 1);
+}
+
+#[test]
+fn test_parse_toml_manifest() {
+    let manifest = Manifest::from_toml(
+        r#"
+        [[features]]
+        name = "voice_notes"
+
+        [[features.permissions]]
+        permission = "microphone"
+        reason = "Record the note"
+
+        [[features.permissions]]
+        permission = "location"
+        required = false
+        reason = "Tag the note"
+
+        [[features]]
+        name = "dictation"
+        permissions = [{ permission = "speech_recognition", reason = "Transcribe" }]
+        "#,
+    )
+    .unwrap();
+
+    let expected = Manifest::new()
+        .with_feature(
+            FeatureSpec::new("voice_notes")
+                .with_required(PermissionType::Microphone, "Record the note")
+                .with_optional(PermissionType::Location, "Tag the note"),
+        )
+        .with_feature(
+            FeatureSpec::new("dictation")
+                .with_required(PermissionType::SpeechRecognition, "Transcribe"),
+        );
+    assert_eq!(manifest, expected);
+}
+
+#[test]
+fn test_invalid_toml_manifest() {
+    let unknown = r#"
+        [[features]]
+        name = "camera"
+        permissions = [{ permission = "webcam", reason = "Video" }]
+    "#;
+    let error = Manifest::from_toml(unknown).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidConfig);
+    assert_eq!(error.message(), Some("unknown permission `webcam`"));
+
+    let missing_reason = r#"
+        [[features]]
+        name = "camera"
+        permissions = [{ permission = "camera" }]
+    "#;
+    assert_eq!(
+        Manifest::from_toml(missing_reason).unwrap_err().kind(),
+        ErrorKind::InvalidConfig
+    );
+}
@@ -0,0 +1,157 @@
+//! Integration tests for the administrative policy layer
+
+use std::sync::Arc;
+
+use kodegen_native_permissions::testing::FakePermissionBackend;
+use kodegen_native_permissions::{
+    Caller, Confidence, ErrorKind, PermissionManager, PermissionStatus, PermissionType, Policy,
+    PolicyRule,
+};
+
+#[tokio::test]
+async fn test_forbidden_for_agent_never_prompts() {
+    let backend = Arc::new(FakePermissionBackend::with_default_status(
+        PermissionStatus::NotDetermined,
+    ));
+    let policy = Policy::new().with_rule(
+        PolicyRule::forbid("no-camera-for-coder", [PermissionType::Camera]).for_agent("coder"),
+    );
+    let manager = PermissionManager::with_backend(backend.clone()).with_policy(policy);
+    let coder = manager
+        .clone()
+        .with_caller(Caller::new().with_agent("coder").with_tool("snapshot"));
+
+    assert_eq!(
+        coder.check_permission(PermissionType::Camera).unwrap(),
+        PermissionStatus::Restricted
+    );
+    assert_eq!(
+        coder
+            .request_permission(PermissionType::Camera)
+            .await
+            .unwrap(),
+        PermissionStatus::Restricted
+    );
+    assert!(backend.calls().is_empty());
+
+    // Other callers still reach the backend
+    assert_eq!(
+        manager.check_permission(PermissionType::Camera).unwrap(),
+        PermissionStatus::NotDetermined
+    );
+    assert_eq!(backend.check_count(PermissionType::Camera), 1);
+}
+
+#[tokio::test]
+async fn test_forbid_wins_and_is_cited() {
+    let backend = Arc::new(FakePermissionBackend::new());
+    let policy = Policy::new()
+        .with_rule(PolicyRule::approve(
+            "fetch-may-download",
+            [PermissionType::DownloadsFolder, PermissionType::Location],
+        ))
+        .with_rule(PolicyRule::forbid("no-location", [PermissionType::All]).for_tool("fetch"));
+    let manager = PermissionManager::with_backend(backend.clone())
+        .with_policy(policy)
+        .with_caller(Caller::new().with_tool("fetch"));
+
+    assert_eq!(
+        manager
+            .check_permission_async(PermissionType::DownloadsFolder)
+            .await
+            .unwrap(),
+        PermissionStatus::Restricted
+    );
+    let report = manager
+        .check_permission_detailed(PermissionType::Location)
+        .await
+        .unwrap();
+    assert_eq!(report.status, PermissionStatus::Restricted);
+    assert_eq!(report.confidence, Confidence::Verified);
+    assert_eq!(
+        report.evidence[0].outcome,
+        "forbidden by policy rule `no-location`"
+    );
+
+    let anonymous = manager.clone().with_caller(Caller::new());
+    assert_eq!(
+        anonymous
+            .check_permission(PermissionType::Location)
+            .unwrap(),
+        PermissionStatus::Authorized
+    );
+    assert!(backend.calls().is_empty());
+}
+
+#[test]
+fn test_policy_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("permission-policy.toml");
+    std::fs::write(
+        &path,
+        r#"
+        [[rules]]
+        name = "no-recording"
+        action = "forbid"
+        permissions = ["camera", "microphone"]
+        agents = ["coder"]
+
+        [[rules]]
+        action = "approve"
+        permissions = ["all"]
+        "#,
+    )
+    .unwrap();
+
+    let policy = Policy::from_file(&path).unwrap();
+    let coder = Caller::new().with_agent("coder");
+    let decision = policy.decide(PermissionType::Microphone, &coder).unwrap();
+    assert_eq!(decision.status(), PermissionStatus::Restricted);
+    assert_eq!(
+        decision.to_string(),
+        format!(
+            "forbidden by policy rule `no-recording` in {}",
+            path.display()
+        )
+    );
+    let decision = policy
+        .decide(PermissionType::Camera, &Caller::new())
+        .unwrap();
+    assert_eq!(decision.rule.name, "rules[1]");
+
+    let error =
+        Policy::from_toml("[[rules]]\naction = \"allow\"\npermissions = [\"camera\"]").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidConfig);
+}
+
+#[test]
+fn test_user_policy_file_cannot_approve() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("permission-policy.toml");
+    std::fs::write(
+        &path,
+        r#"
+        [[rules]]
+        name = "self-approved"
+        action = "approve"
+        permissions = ["all"]
+
+        [[rules]]
+        name = "no-microphone"
+        action = "forbid"
+        permissions = ["microphone"]
+        "#,
+    )
+    .unwrap();
+
+    let policy = Policy::from_user_file(&path).unwrap();
+    assert_eq!(policy.rules().len(), 1);
+    assert_eq!(
+        policy
+            .decide(PermissionType::Microphone, &Caller::new())
+            .unwrap()
+            .status(),
+        PermissionStatus::Restricted
+    );
+    assert!(policy.decide(PermissionType::Camera, &Caller::new()).is_none());
+}
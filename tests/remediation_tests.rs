@@ -0,0 +1,45 @@
+//! Integration tests for remediation guidance
+
+use std::sync::Arc;
+
+use kodegen_native_permissions::testing::FakePermissionBackend;
+use kodegen_native_permissions::{
+    PermissionManager, PermissionType, Policy, PolicyRule, Remediation,
+};
+
+#[test]
+fn test_backend_guidance_unless_forbidden() {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend.set_remediation(
+        Remediation::new(PermissionType::Camera)
+            .with_settings_uri("ms-settings:privacy-webcam")
+            .with_restart_required(true),
+    );
+    let manager = PermissionManager::with_backend(backend);
+
+    let camera = manager.remediation(PermissionType::Camera);
+    assert_eq!(camera.settings_uris, ["ms-settings:privacy-webcam"]);
+    assert!(camera.restart_required);
+    assert!(manager.remediation(PermissionType::Location).is_empty());
+
+    // A policy denial can only be lifted by the policy's owner
+    let forbidden = manager
+        .with_policy(
+            Policy::new().with_rule(PolicyRule::forbid("no-camera", [PermissionType::Camera])),
+        )
+        .remediation(PermissionType::Camera);
+    assert!(forbidden.settings_uris.is_empty());
+    assert!(forbidden.steps[0].contains("no-camera"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_linux_device_group_needs_relogin() {
+    // Outside Flatpak, device access comes from group membership
+    if std::path::Path::new("/.flatpak-info").exists() {
+        return;
+    }
+    let remediation = PermissionManager::new().remediation(PermissionType::InputMonitoring);
+    assert_eq!(remediation.commands, ["sudo usermod -aG input \"$USER\""]);
+    assert!(remediation.relogin_required);
+}
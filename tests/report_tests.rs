@@ -0,0 +1,104 @@
+//! Integration tests for detailed permission reports
+
+use std::sync::Arc;
+
+use kodegen_native_permissions::testing::FakePermissionBackend;
+use kodegen_native_permissions::report;
+use kodegen_native_permissions::{
+    Confidence, Evidence, PermissionError, PermissionHandler, PermissionManager,
+    PermissionStatus, PermissionType, Support,
+};
+use tokio::sync::oneshot;
+
+#[tokio::test]
+async fn test_confidence_is_assumed_without_evidence() {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend
+        .set_status(PermissionType::Camera, PermissionStatus::Authorized)
+        .set_status(PermissionType::WiFi, PermissionStatus::Authorized)
+        .set_support(PermissionType::WiFi, Support::Emulated { via: "test" })
+        .set_support(PermissionType::Bluetooth, Support::NotApplicable);
+    let manager = PermissionManager::with_backend(backend);
+
+    let camera = manager
+        .check_permission_detailed(PermissionType::Camera)
+        .await
+        .unwrap();
+    assert_eq!(camera.status, PermissionStatus::Authorized);
+    assert_eq!(camera.confidence, Confidence::Assumed);
+    assert!(camera.evidence.is_empty());
+
+    let wifi = manager
+        .check_permission_detailed(PermissionType::WiFi)
+        .await
+        .unwrap();
+    assert_eq!(wifi.confidence, Confidence::Assumed);
+
+    // Nothing to probe when the permission does not exist on the platform
+    let bluetooth = manager
+        .check_permission_detailed(PermissionType::Bluetooth)
+        .await
+        .unwrap();
+    assert_eq!(bluetooth.status, PermissionStatus::NotApplicable);
+    assert_eq!(bluetooth.confidence, Confidence::Verified);
+}
+
+/// Backend citing the lookup and fallback probes behind its answer
+struct ProbingBackend;
+
+impl PermissionHandler for ProbingBackend {
+    fn check_permission(&self, _typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
+        report::record(
+            Confidence::Verified,
+            "PermissionStore.Lookup",
+            "devices/camera",
+            "no entry",
+        );
+        report::record(Confidence::Heuristic, "open", "/dev/video0", "ok");
+        Ok(PermissionStatus::Authorized)
+    }
+
+    fn request_permission(
+        &self,
+        typ: PermissionType,
+        tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        tx.send(self.check_permission(typ)).ok();
+    }
+}
+
+#[tokio::test]
+async fn test_backend_evidence_in_report() {
+    let manager = PermissionManager::with_backend(Arc::new(ProbingBackend));
+
+    let report = manager
+        .check_permission_detailed(PermissionType::Camera)
+        .await
+        .unwrap();
+    assert_eq!(report.status, PermissionStatus::Authorized);
+    // The fallback ran last, so it decides the confidence
+    assert_eq!(report.confidence, Confidence::Heuristic);
+    assert_eq!(
+        report.evidence,
+        [
+            Evidence {
+                probe: "PermissionStore.Lookup",
+                target: "devices/camera".to_string(),
+                outcome: "no entry".to_string(),
+                confidence: Confidence::Verified,
+            },
+            Evidence {
+                probe: "open",
+                target: "/dev/video0".to_string(),
+                outcome: "ok".to_string(),
+                confidence: Confidence::Heuristic,
+            },
+        ]
+    );
+
+    // Plain checks collect nothing
+    assert!(matches!(
+        manager.check_permission(PermissionType::Camera),
+        Ok(PermissionStatus::Authorized)
+    ));
+}
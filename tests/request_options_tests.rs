@@ -0,0 +1,196 @@
+//! Integration tests for request timeouts, cancellation and presentation options
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use kodegen_native_permissions::testing::FakePermissionBackend;
+use kodegen_native_permissions::{
+    ErrorKind, LocationAccuracy, PermissionError, PermissionHandler, PermissionManager,
+    PermissionStatus, PermissionType, RequestOptions, WindowIdentifier,
+};
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::sync::CancellationToken;
+
+fn slow_camera() -> Arc<FakePermissionBackend> {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend
+        .set_delay(PermissionType::Camera, Duration::from_secs(60))
+        .push_response(PermissionType::Camera, Ok(PermissionStatus::Authorized));
+    backend
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_request_times_out() {
+    let manager = PermissionManager::with_backend(slow_camera());
+
+    let result = manager
+        .request_permission_with(
+            PermissionType::Camera,
+            RequestOptions::new().with_timeout(Duration::from_secs(5)),
+        )
+        .await;
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::Timeout);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_request_cancelled() {
+    let manager = PermissionManager::with_backend(slow_camera());
+    let cancel = CancellationToken::new();
+
+    let request = manager.request_permission_with(
+        PermissionType::Camera,
+        RequestOptions::new()
+            .with_timeout(Duration::from_secs(30))
+            .with_cancel(cancel.clone()),
+    );
+    let canceller = async {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        cancel.cancel();
+    };
+    let (result, ()) = tokio::join!(request, canceller);
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::Cancelled);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_answer_before_timeout() {
+    let manager = PermissionManager::with_backend(slow_camera());
+
+    let result = manager
+        .request_permission_with(
+            PermissionType::Camera,
+            RequestOptions::new().with_timeout(Duration::from_secs(120)),
+        )
+        .await;
+    assert_eq!(result.unwrap(), PermissionStatus::Authorized);
+}
+
+/// Backend that never answers and reports every prompt it is told to dismiss
+struct Unanswered {
+    dismissed: mpsc::UnboundedSender<PermissionType>,
+}
+
+impl PermissionHandler for Unanswered {
+    fn check_permission(&self, _typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
+        Ok(PermissionStatus::NotDetermined)
+    }
+
+    fn request_permission(
+        &self,
+        typ: PermissionType,
+        mut tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        let dismissed = self.dismissed.clone();
+        tokio::spawn(async move {
+            tx.closed().await;
+            dismissed.send(typ).ok();
+        });
+    }
+}
+
+/// Next dismissed prompt, failing the test if the backend is never told
+async fn next_dismissal(rx: &mut mpsc::UnboundedReceiver<PermissionType>) -> PermissionType {
+    tokio::time::timeout(Duration::from_secs(1), rx.recv())
+        .await
+        .expect("prompt was not dismissed")
+        .unwrap()
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_prompt_dismissed() {
+    let (dismissed, mut rx) = mpsc::unbounded_channel();
+    let manager = PermissionManager::with_backend(Arc::new(Unanswered { dismissed }));
+
+    // Timed out
+    let result = manager
+        .request_permission_with(
+            PermissionType::Camera,
+            RequestOptions::new().with_timeout(Duration::from_secs(5)),
+        )
+        .await;
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::Timeout);
+    assert_eq!(next_dismissal(&mut rx).await, PermissionType::Camera);
+
+    // Cancelled
+    let cancel = CancellationToken::new();
+    let request = manager.request_permission_with(
+        PermissionType::Microphone,
+        RequestOptions::new().with_cancel(cancel.clone()),
+    );
+    let canceller = async {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        cancel.cancel();
+    };
+    let (result, ()) = tokio::join!(request, canceller);
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::Cancelled);
+    assert_eq!(next_dismissal(&mut rx).await, PermissionType::Microphone);
+
+    // Dropped
+    let request = manager.request_permission(PermissionType::Location);
+    assert!(
+        tokio::time::timeout(Duration::from_secs(1), request)
+            .await
+            .is_err()
+    );
+    assert_eq!(next_dismissal(&mut rx).await, PermissionType::Location);
+}
+
+/// Backend remembering the options of every request
+#[derive(Default)]
+struct OptionsRecorder {
+    seen: Mutex<Vec<RequestOptions>>,
+}
+
+impl PermissionHandler for OptionsRecorder {
+    fn check_permission(&self, _typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
+        Ok(PermissionStatus::NotDetermined)
+    }
+
+    fn request_permission(
+        &self,
+        typ: PermissionType,
+        tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        self.request_permission_with(typ, &RequestOptions::default(), tx)
+    }
+
+    fn request_permission_with(
+        &self,
+        _typ: PermissionType,
+        options: &RequestOptions,
+        tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        self.seen.lock().unwrap().push(options.clone());
+        tx.send(Ok(PermissionStatus::Authorized)).ok();
+    }
+}
+
+#[tokio::test]
+async fn test_options_reach_backend() {
+    let backend = Arc::new(OptionsRecorder::default());
+    let manager = PermissionManager::with_backend(backend.clone());
+
+    manager
+        .request_permission_with(
+            PermissionType::Location,
+            RequestOptions::new()
+                .with_parent_window(WindowIdentifier::Wayland("a1b2".to_string()))
+                .with_reason("Tag photos with where they were taken")
+                .with_accuracy(LocationAccuracy::Street)
+                .with_distance_threshold(100),
+        )
+        .await
+        .unwrap();
+    manager
+        .request_permission(PermissionType::Camera)
+        .await
+        .unwrap();
+
+    let seen = backend.seen.lock().unwrap();
+    assert_eq!(seen.len(), 2);
+    let window = seen[0].parent_window.as_ref().unwrap();
+    assert_eq!(window.to_string(), "wayland:a1b2");
+    assert_eq!(seen[0].reason.as_deref(), Some("Tag photos with where they were taken"));
+    assert_eq!(seen[0].accuracy, Some(LocationAccuracy::Street));
+    assert_eq!(seen[0].distance_threshold, Some(100));
+    assert!(seen[1].parent_window.is_none());
+}
@@ -0,0 +1,73 @@
+//! Integration tests for request coalescing and prompt serialization
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use kodegen_native_permissions::testing::{FakeCall, FakePermissionBackend};
+use kodegen_native_permissions::{PermissionManager, PermissionStatus, PermissionType};
+use tokio::time::Instant;
+
+const PROMPT: Duration = Duration::from_secs(10);
+
+fn slow_backend(types: &[PermissionType]) -> Arc<FakePermissionBackend> {
+    let backend = Arc::new(FakePermissionBackend::new());
+    for &typ in types {
+        backend
+            .set_delay(typ, PROMPT)
+            .push_response(typ, Ok(PermissionStatus::Authorized));
+    }
+    backend
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_concurrent_requests_share_one_prompt() {
+    let backend = slow_backend(&[PermissionType::Camera]);
+    let manager = PermissionManager::with_backend(backend.clone());
+    let other = manager.clone();
+
+    let (first, second) = tokio::join!(
+        manager.request_permission(PermissionType::Camera),
+        other.request_permission(PermissionType::Camera),
+    );
+    assert_eq!(first.unwrap(), PermissionStatus::Authorized);
+    assert_eq!(second.unwrap(), PermissionStatus::Authorized);
+    assert_eq!(backend.request_count(PermissionType::Camera), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_prompts_shown_one_at_a_time_in_order() {
+    let types = [
+        PermissionType::Location,
+        PermissionType::Camera,
+        PermissionType::Microphone,
+    ];
+    let backend = slow_backend(&types);
+    let manager = PermissionManager::with_backend(backend.clone());
+
+    let start = Instant::now();
+    let results = manager.request_permissions(&types).await;
+    assert!(results.values().all(|r| r.is_ok()));
+    assert!(start.elapsed() >= PROMPT * 3);
+
+    let order: Vec<_> = types.iter().map(|&typ| FakeCall::Request(typ)).collect();
+    assert_eq!(backend.calls(), order);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_silent_requests_run_in_parallel() {
+    let types = [
+        PermissionType::Bluetooth,
+        PermissionType::WiFi,
+        PermissionType::Contacts,
+    ];
+    let backend = slow_backend(&types);
+    for typ in types {
+        backend.set_silent(typ);
+    }
+    let manager = PermissionManager::with_backend(backend);
+
+    let start = Instant::now();
+    let results = manager.request_permissions(&types).await;
+    assert!(results.values().all(|r| r.is_ok()));
+    assert!(start.elapsed() < PROMPT * 2);
+}
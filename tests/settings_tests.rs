@@ -0,0 +1,104 @@
+//! Integration tests for opening the Linux settings panel
+
+#![cfg(target_os = "linux")]
+
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use kodegen_native_permissions::platforms::linux::{Desktop, Launcher, SettingsCommand};
+use kodegen_native_permissions::{ErrorKind, LinuxHandler, PermissionManager, PermissionType};
+
+/// Launcher recording every command, with some programs "not installed"
+#[derive(Default)]
+struct Recorder {
+    missing: Vec<&'static str>,
+    launched: Mutex<Vec<String>>,
+}
+
+impl Launcher for Recorder {
+    fn launch(&self, command: &SettingsCommand) -> io::Result<()> {
+        self.launched.lock().unwrap().push(command.to_string());
+        if self.missing.contains(&command.program.as_str()) {
+            return Err(io::ErrorKind::NotFound.into());
+        }
+        Ok(())
+    }
+}
+
+fn manager(desktop: Desktop, launcher: Arc<Recorder>) -> PermissionManager {
+    PermissionManager::with_backend(Arc::new(
+        LinuxHandler::new()
+            .with_desktop(desktop)
+            .with_launcher(launcher),
+    ))
+}
+
+#[test]
+fn test_desktop_panel_with_fallback() {
+    let recorder = Arc::new(Recorder::default());
+    manager(Desktop::Gnome, recorder.clone())
+        .open_settings(PermissionType::Camera)
+        .unwrap();
+    assert_eq!(
+        *recorder.launched.lock().unwrap(),
+        ["gnome-control-center camera"]
+    );
+
+    // Plasma 5 has neither systemsettings nor kcmshell6
+    let recorder = Arc::new(Recorder {
+        missing: vec!["systemsettings", "kcmshell6"],
+        ..Recorder::default()
+    });
+    manager(Desktop::Kde, recorder.clone())
+        .open_settings(PermissionType::Bluetooth)
+        .unwrap();
+    assert_eq!(
+        *recorder.launched.lock().unwrap(),
+        [
+            "systemsettings kcm_bluetooth",
+            "kcmshell6 kcm_bluetooth",
+            "systemsettings5 kcm_bluetooth",
+        ]
+    );
+}
+
+#[test]
+fn test_missing_and_unsupported_panels() {
+    let recorder = Arc::new(Recorder {
+        missing: vec!["xfce4-notifyd-config"],
+        ..Recorder::default()
+    });
+    let manager = manager(Desktop::Xfce, recorder);
+    let missing = manager
+        .open_settings(PermissionType::Notification)
+        .unwrap_err();
+    assert_eq!(missing.kind(), ErrorKind::NotFound);
+    assert_eq!(missing.backend(), Some("linux"));
+
+    let unsupported = manager
+        .open_settings(PermissionType::FullDiskAccess)
+        .unwrap_err();
+    assert_eq!(unsupported.kind(), ErrorKind::NotSupportedOnPlatform);
+}
+
+#[test]
+fn test_cinnamon_camera_opens_flatseal() {
+    let recorder = Arc::new(Recorder::default());
+    manager(Desktop::Cinnamon, recorder.clone())
+        .open_settings(PermissionType::Camera)
+        .unwrap();
+    assert_eq!(*recorder.launched.lock().unwrap(), ["flatseal"]);
+}
+
+#[test]
+fn test_desktop_from_names() {
+    assert_eq!(Desktop::from_names("ubuntu:GNOME"), Desktop::Gnome);
+    assert_eq!(Desktop::from_names("Budgie:GNOME"), Desktop::Gnome);
+    assert_eq!(Desktop::from_names("KDE"), Desktop::Kde);
+    assert_eq!(Desktop::from_names("X-Cinnamon"), Desktop::Cinnamon);
+    assert_eq!(
+        Desktop::from_names("sway"),
+        Desktop::Other("sway".to_string())
+    );
+    assert_eq!(Desktop::from_names(""), Desktop::Unknown);
+}
@@ -0,0 +1,55 @@
+//! Integration tests for the platform support matrix
+
+use std::sync::Arc;
+
+use kodegen_native_permissions::testing::FakePermissionBackend;
+use kodegen_native_permissions::{PermissionManager, PermissionStatus, PermissionType, Support};
+
+#[tokio::test]
+async fn test_not_applicable_skips_backend() {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend.set_support(PermissionType::FaceID, Support::NotApplicable);
+    let manager = PermissionManager::with_backend(backend.clone());
+
+    assert_eq!(
+        manager.support(PermissionType::FaceID),
+        Support::NotApplicable
+    );
+    assert_eq!(
+        manager.check_permission(PermissionType::FaceID).unwrap(),
+        PermissionStatus::NotApplicable
+    );
+    assert_eq!(
+        manager
+            .request_permission(PermissionType::FaceID)
+            .await
+            .unwrap(),
+        PermissionStatus::NotApplicable
+    );
+    assert!(backend.calls().is_empty());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_linux_support_matrix() {
+    let manager = PermissionManager::new();
+
+    assert_eq!(manager.support(PermissionType::Camera), Support::Native);
+    assert!(matches!(
+        manager.support(PermissionType::Bluetooth),
+        Support::Emulated { .. }
+    ));
+    assert_eq!(
+        manager.support(PermissionType::ScreenCapture),
+        Support::Emulated {
+            via: "framebuffer device"
+        }
+    );
+    for typ in [PermissionType::Siri, PermissionType::DeveloperTools] {
+        assert_eq!(manager.support(typ), Support::NotApplicable);
+        assert_eq!(
+            manager.check_permission(typ).unwrap(),
+            PermissionStatus::NotApplicable
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Integration tests for the optional tracing spans
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
+
+use kodegen_native_permissions::testing::FakePermissionBackend;
+use kodegen_native_permissions::{
+    PermissionError, PermissionManager, PermissionStatus, PermissionType,
+};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+use tracing_core::span::Current;
+
+/// Span name and every field recorded on it, as strings
+type Spans = Arc<Mutex<Vec<(&'static str, HashMap<String, String>)>>>;
+
+/// Subscriber keeping every span it sees, and the parent of each
+#[derive(Default)]
+struct Recorder {
+    spans: Spans,
+    parents: Arc<Mutex<HashMap<u64, u64>>>,
+    metadata: Mutex<HashMap<u64, &'static Metadata<'static>>>,
+    // Spans entered on each thread, innermost last
+    entered: Mutex<HashMap<ThreadId, Vec<u64>>>,
+}
+
+struct Fields<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for Fields<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl Recorder {
+    /// Innermost span entered on this thread
+    fn innermost(&self) -> Option<u64> {
+        let entered = self.entered.lock().unwrap();
+        entered.get(&thread::current().id())?.last().copied()
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = HashMap::new();
+        span.record(&mut Fields(&mut fields));
+        let mut spans = self.spans.lock().unwrap();
+        spans.push((span.metadata().name(), fields));
+        let id = spans.len() as u64;
+        self.metadata.lock().unwrap().insert(id, span.metadata());
+
+        let parent = match span.parent() {
+            Some(parent) => Some(parent.into_u64()),
+            None if span.is_contextual() => self.innermost(),
+            None => None,
+        };
+        if let Some(parent) = parent {
+            self.parents.lock().unwrap().insert(id, parent);
+        }
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        let (_, fields) = &mut spans[span.into_u64() as usize - 1];
+        values.record(&mut Fields(fields));
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn current_span(&self) -> Current {
+        match self.innermost() {
+            Some(id) => Current::new(Id::from_u64(id), self.metadata.lock().unwrap()[&id]),
+            None => Current::none(),
+        }
+    }
+
+    fn enter(&self, span: &Id) {
+        let mut entered = self.entered.lock().unwrap();
+        let stack = entered.entry(thread::current().id()).or_default();
+        stack.push(span.into_u64());
+    }
+
+    fn exit(&self, _span: &Id) {
+        let mut entered = self.entered.lock().unwrap();
+        if let Some(stack) = entered.get_mut(&thread::current().id()) {
+            stack.pop();
+        }
+    }
+}
+
+fn manager() -> PermissionManager {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend
+        .set_status(PermissionType::Camera, PermissionStatus::Denied)
+        .push_response(PermissionType::Camera, Ok(PermissionStatus::Authorized));
+    PermissionManager::with_backend(backend)
+}
+
+#[tokio::test]
+async fn test_check_and_request_spans() {
+    let recorder = Recorder::default();
+    let spans = recorder.spans.clone();
+    let _guard = tracing::subscriber::set_default(recorder);
+
+    let manager = manager();
+    manager.check_permission(PermissionType::Camera).unwrap();
+    manager
+        .request_permission(PermissionType::Camera)
+        .await
+        .unwrap();
+
+    let spans = spans.lock().unwrap();
+    let check = spans
+        .iter()
+        .find(|(name, _)| *name == "check_permission")
+        .map(|(_, fields)| fields)
+        .unwrap();
+    assert_eq!(check["permission"], "camera");
+    assert_eq!(check["backend"], "fake");
+    assert_eq!(check["result"], "denied");
+    assert!(check.contains_key("duration_ms"));
+
+    let request = spans
+        .iter()
+        .find(|(name, _)| *name == "request_permission")
+        .map(|(_, fields)| fields)
+        .unwrap();
+    assert_eq!(request["permission"], "camera");
+    assert_eq!(request["result"], "authorized");
+}
+
+#[tokio::test]
+async fn test_failed_check_records_error() {
+    let recorder = Recorder::default();
+    let spans = recorder.spans.clone();
+    let _guard = tracing::subscriber::set_default(recorder);
+
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend.set_error(
+        PermissionType::Microphone,
+        PermissionError::system("probe crashed"),
+    );
+    let manager = PermissionManager::with_backend(backend);
+    assert!(
+        manager
+            .check_permission(PermissionType::Microphone)
+            .is_err()
+    );
+
+    let spans = spans.lock().unwrap();
+    let (_, check) = spans
+        .iter()
+        .find(|(name, _)| *name == "check_permission")
+        .unwrap();
+    assert_eq!(check["permission"], "microphone");
+    assert_eq!(check["result"], "error");
+}
+
+#[test]
+fn test_spans_follow_sync_calls_onto_private_runtime() {
+    let recorder = Recorder::default();
+    let spans = recorder.spans.clone();
+    let parents = recorder.parents.clone();
+    let _guard = tracing::subscriber::set_default(recorder);
+
+    // The blocking facade runs detailed checks on the crate's own runtime
+    let manager = manager().blocking();
+    tracing::debug_span!("caller").in_scope(|| {
+        manager
+            .check_permission_detailed(PermissionType::Camera)
+            .unwrap()
+    });
+
+    let spans = spans.lock().unwrap();
+    let id = |wanted: &str| {
+        spans
+            .iter()
+            .position(|(name, _)| *name == wanted)
+            .map(|index| index as u64 + 1)
+    };
+    let check = id("check_permission").expect("check span reached the subscriber");
+    assert_eq!(parents.lock().unwrap().get(&check).copied(), id("caller"));
+}
@@ -0,0 +1,49 @@
+//! Integration tests for Linux folder checks against `user-dirs.dirs`
+//!
+//! Kept in their own binary since they point `XDG_CONFIG_HOME` at a temporary
+//! directory for the whole process.
+
+#![cfg(target_os = "linux")]
+
+use std::sync::Arc;
+
+use kodegen_native_permissions::{
+    Confidence, ErrorKind, LinuxHandler, PermissionManager, PermissionStatus, PermissionType,
+};
+
+#[tokio::test]
+async fn test_configured_folder_is_probed() {
+    let dir = tempfile::tempdir().unwrap();
+    let pictures = dir.path().join("Bilder");
+    std::fs::write(
+        dir.path().join("user-dirs.dirs"),
+        format!("XDG_PICTURES_DIR=\"{}\"\n", pictures.display()),
+    )
+    .unwrap();
+    // SAFETY: this is the only test in the binary, and it sets the variable
+    // before anything reads the environment
+    unsafe { std::env::set_var("XDG_CONFIG_HOME", dir.path()) };
+    let manager = PermissionManager::with_backend(Arc::new(LinuxHandler::new()));
+
+    // A configured folder that does not exist is not a denial
+    let missing = manager
+        .check_permission_detailed(PermissionType::Photos)
+        .await
+        .unwrap_err();
+    assert_eq!(missing.kind(), ErrorKind::NotFound);
+    assert_eq!(
+        missing.probe(),
+        Some(pictures.display().to_string().as_str())
+    );
+
+    std::fs::create_dir(&pictures).unwrap();
+    let report = manager
+        .check_permission_detailed(PermissionType::Photos)
+        .await
+        .unwrap();
+    assert_eq!(report.status, PermissionStatus::Authorized);
+    assert_eq!(report.confidence, Confidence::Heuristic);
+    assert_eq!(report.evidence[0].probe, "read_dir");
+    assert_eq!(report.evidence[0].target, pictures.display().to_string());
+    assert_eq!(report.path, Some(pictures));
+}
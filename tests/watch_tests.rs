@@ -0,0 +1,171 @@
+//! Integration tests for permission change subscriptions
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::StreamExt;
+use kodegen_native_permissions::testing::FakePermissionBackend;
+use kodegen_native_permissions::{
+    CachePolicy, PermissionError, PermissionHandler, PermissionManager, PermissionStatus,
+    PermissionType, Policy, PolicyRule,
+};
+use tokio::sync::{mpsc, oneshot};
+
+/// Fake backend that reports Location changes natively
+struct NativeLocation {
+    inner: FakePermissionBackend,
+    notify: Mutex<Option<mpsc::UnboundedSender<PermissionType>>>,
+}
+
+impl PermissionHandler for NativeLocation {
+    fn check_permission(&self, typ: PermissionType) -> Result<PermissionStatus, PermissionError> {
+        self.inner.check_permission(typ)
+    }
+
+    fn request_permission(
+        &self,
+        typ: PermissionType,
+        tx: oneshot::Sender<Result<PermissionStatus, PermissionError>>,
+    ) {
+        self.inner.request_permission(typ, tx)
+    }
+
+    fn watch_changes(
+        &self,
+        types: &[PermissionType],
+        _interval: Duration,
+        tx: mpsc::UnboundedSender<PermissionType>,
+    ) -> Vec<PermissionType> {
+        *self.notify.lock().unwrap() = Some(tx);
+        types
+            .iter()
+            .copied()
+            .filter(|t| *t == PermissionType::Location)
+            .collect()
+    }
+}
+
+/// Wait until the watcher has taken its initial snapshot
+async fn wait_for_checks(backend: &FakePermissionBackend, typ: PermissionType, count: usize) {
+    while backend.check_count(typ) < count {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+#[tokio::test]
+async fn test_polled_change_is_reported() {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend.set_status(PermissionType::Camera, PermissionStatus::Denied);
+    let manager = PermissionManager::with_backend(backend.clone());
+
+    let mut changes = manager.watch_with_interval(
+        &[PermissionType::Camera, PermissionType::Camera],
+        Duration::from_millis(20),
+    );
+    wait_for_checks(&backend, PermissionType::Camera, 1).await;
+    backend.set_status(PermissionType::Camera, PermissionStatus::Authorized);
+
+    let change = tokio::time::timeout(Duration::from_secs(5), changes.next())
+        .await
+        .expect("no change observed")
+        .unwrap();
+    assert_eq!(change.permission, PermissionType::Camera);
+    assert_eq!(change.old, PermissionStatus::Denied);
+    assert_eq!(change.new, PermissionStatus::Authorized);
+}
+
+#[tokio::test]
+async fn test_native_notification_refreshes_cache() {
+    let backend = Arc::new(NativeLocation {
+        inner: FakePermissionBackend::new(),
+        notify: Mutex::new(None),
+    });
+    backend
+        .inner
+        .set_status(PermissionType::Location, PermissionStatus::Authorized);
+    let manager = PermissionManager::with_backend(backend.clone())
+        .with_cache_policy(CachePolicy::never_expire());
+    assert_eq!(
+        manager.check_permission(PermissionType::Location).unwrap(),
+        PermissionStatus::Authorized
+    );
+
+    // Polling interval is far longer than the test, so only the notification can fire
+    let mut changes =
+        manager.watch_with_interval(&[PermissionType::Location], Duration::from_secs(3600));
+    wait_for_checks(&backend.inner, PermissionType::Location, 2).await;
+    backend
+        .inner
+        .set_status(PermissionType::Location, PermissionStatus::Denied);
+    let notify = backend.notify.lock().unwrap().clone().unwrap();
+    notify.send(PermissionType::Location).unwrap();
+
+    let change = tokio::time::timeout(Duration::from_secs(5), changes.next())
+        .await
+        .expect("no change observed")
+        .unwrap();
+    assert_eq!(change.old, PermissionStatus::Authorized);
+    assert_eq!(change.new, PermissionStatus::Denied);
+    assert_eq!(
+        manager.check_permission(PermissionType::Location).unwrap(),
+        PermissionStatus::Denied
+    );
+
+    // Dropping the stream ends the backend subscription
+    drop(changes);
+    tokio::time::timeout(Duration::from_secs(5), notify.closed())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_policy_decides_watched_status() {
+    let backend = Arc::new(FakePermissionBackend::new());
+    backend.set_status(PermissionType::Camera, PermissionStatus::Denied);
+    backend.set_status(PermissionType::Microphone, PermissionStatus::Denied);
+    let policy = Policy::new().with_rule(PolicyRule::forbid("no-camera", [PermissionType::Camera]));
+    let manager = PermissionManager::with_backend(backend.clone()).with_policy(policy);
+
+    let mut changes = manager.watch_with_interval(
+        &[PermissionType::Camera, PermissionType::Microphone],
+        Duration::from_millis(20),
+    );
+    wait_for_checks(&backend, PermissionType::Microphone, 1).await;
+    backend.set_status(PermissionType::Camera, PermissionStatus::Authorized);
+    backend.set_status(PermissionType::Microphone, PermissionStatus::Authorized);
+
+    let change = tokio::time::timeout(Duration::from_secs(5), changes.next())
+        .await
+        .expect("no change observed")
+        .unwrap();
+    assert_eq!(change.permission, PermissionType::Microphone);
+    assert_eq!(backend.check_count(PermissionType::Camera), 0);
+}
+
+#[tokio::test]
+async fn test_ended_subscription_falls_back_to_polling() {
+    let backend = Arc::new(NativeLocation {
+        inner: FakePermissionBackend::new(),
+        notify: Mutex::new(None),
+    });
+    backend
+        .inner
+        .set_status(PermissionType::Location, PermissionStatus::Authorized);
+    let manager = PermissionManager::with_backend(backend.clone());
+
+    let mut changes =
+        manager.watch_with_interval(&[PermissionType::Location], Duration::from_millis(20));
+    wait_for_checks(&backend.inner, PermissionType::Location, 1).await;
+    // The backend's change source goes away, as when a signal stream ends
+    drop(backend.notify.lock().unwrap().take());
+    backend
+        .inner
+        .set_status(PermissionType::Location, PermissionStatus::Denied);
+
+    let change = tokio::time::timeout(Duration::from_secs(5), changes.next())
+        .await
+        .expect("no change observed")
+        .unwrap();
+    assert_eq!(change.old, PermissionStatus::Authorized);
+    assert_eq!(change.new, PermissionStatus::Denied);
+}